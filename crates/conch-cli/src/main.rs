@@ -1,8 +1,12 @@
 use clap::{Parser, Subcommand};
 use conch_core::{
     isomorphic::RetrievalSource,
-    memory::{MemoryKind, RememberResult},
-    ConchDB, ValidationConfig, ValidationEngine, DEFAULT_MYCELIUM_URL,
+    memory::{Feedback, MemoryKind, RememberResult},
+    normalize_language, Cardinality, ConchDB, ConsolidateMode, ConsolidateOptions,
+    ConsolidateProgress, ConsolidateStage, ContextFormat, ContextWeights, EmbedProgress,
+    EvalReport, EvalSuite, FusionStrategy, ObjectType, RecallContext, RecallOptions, TuneMetric,
+    ValidationConfig, ValidationEngine, DEFAULT_DIVERSITY_LAMBDA, DEFAULT_LANGUAGE,
+    DEFAULT_MYCELIUM_URL,
 };
 use std::io;
 
@@ -100,6 +104,45 @@ enum Command {
         /// Filter results to only memories with this tag
         #[arg(long)]
         tag: Option<String>,
+        /// Only recall memories in this topic (see `conch topics`)
+        #[arg(long)]
+        topic: Option<i64>,
+        /// Rerank the top candidates with a local cross-encoder (slower, more precise)
+        #[arg(long, conflicts_with = "no_rerank")]
+        rerank: bool,
+        /// Skip the rerank stage even if the namespace policy enables it
+        #[arg(long)]
+        no_rerank: bool,
+        /// Don't expand the query through entities in the fact graph
+        #[arg(long)]
+        no_expand: bool,
+        /// Graph hops spreading activation may follow to add related facts (0 disables)
+        #[arg(long)]
+        hops: Option<usize>,
+        /// Fixed vector similarity cutoff (default: adaptive per query)
+        #[arg(long)]
+        vector_threshold: Option<f32>,
+        /// Diversify results so near-duplicates don't crowd out distinct memories
+        #[arg(long)]
+        diverse: bool,
+        /// MMR lambda for --diverse: 1.0 = pure relevance, 0.0 = pure novelty
+        #[arg(long, requires = "diverse")]
+        diversity_lambda: Option<f64>,
+        /// Fusion strategy: rrf[:k=60,keyword=1,vector=1], convex[:keyword=0.5], vector, keyword
+        #[arg(long)]
+        fusion: Option<FusionStrategy>,
+        /// Recall session: skip memories already returned in it and prime on its recent recalls
+        #[arg(long)]
+        session_id: Option<String>,
+        /// Current channel; memories learned in it are favoured
+        #[arg(long)]
+        channel: Option<String>,
+        /// Current source (e.g. "discord", "cron"); memories from it are favoured
+        #[arg(long)]
+        source: Option<String>,
+        /// Working directory; memories about its project are favoured
+        #[arg(long)]
+        cwd: Option<String>,
     },
     /// Isomorphic recall: pattern-based retrieval via Mycelium cross-domain reasoning
     ///
@@ -130,8 +173,12 @@ enum Command {
     /// Show database statistics
     Stats,
     /// Generate embeddings for all memories missing them
-    Embed,
-    /// Graph traversal: find related facts connected to a subject
+    Embed {
+        /// Memories embedded per batch; each batch is committed in its own transaction
+        #[arg(long, default_value_t = ConchDB::DEFAULT_EMBED_BATCH_SIZE)]
+        batch_size: usize,
+    },
+    /// Graph traversal: find facts and memories mentioning a subject, and what they connect to
     Related {
         subject: String,
         /// Max traversal depth (1-3, default 2)
@@ -143,6 +190,95 @@ enum Command {
         /// Memory ID to inspect
         id: i64,
     },
+    /// List the entities memories mention most, or rebuild the mention index
+    Mentions {
+        /// Re-detect mentions in every episode, action and intent (e.g. after adding facts)
+        #[arg(long)]
+        reindex: bool,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// List, alias or merge entities in the registry
+    Entity {
+        #[command(subcommand)]
+        command: EntityCommand,
+    },
+    /// List relations with their usage counts, or manage relation synonyms
+    Relations {
+        #[command(subcommand)]
+        command: Option<RelationCommand>,
+    },
+    /// Declare or list relation schemas (cardinality, object type, inverse)
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+    /// Show facts about an entity, including inverse readings of facts naming it
+    Facts {
+        entity: String,
+        /// Only facts with this relation
+        #[arg(long)]
+        relation: Option<String>,
+    },
+    /// Show memories most often recalled together with a memory
+    Associations {
+        /// Memory ID to inspect
+        id: i64,
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Score recall against a golden query suite (never reinforces memories)
+    Eval {
+        /// YAML or JSON suite of queries and expected memories
+        #[arg(long, required_unless_present = "from_feedback")]
+        suite: Option<String>,
+        /// Build the suite from logged relevance feedback instead
+        #[arg(long, conflicts_with = "suite")]
+        from_feedback: bool,
+        /// Previous report (JSON) to diff against
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Write this run's report (JSON) for use as a future baseline
+        #[arg(long)]
+        save_baseline: Option<String>,
+    },
+    /// Fit recall score exponents and fusion weights to a golden query suite
+    /// and save them to the namespace policy
+    Tune {
+        /// YAML or JSON suite of queries and expected memories (see `eval`)
+        #[arg(long, required_unless_present = "from_feedback")]
+        suite: Option<String>,
+        /// Tune against logged relevance feedback instead
+        #[arg(long, conflicts_with = "suite")]
+        from_feedback: bool,
+        /// Metric to maximise: mrr, ndcg or recall
+        #[arg(long, default_value = "ndcg")]
+        metric: TuneMetric,
+        /// Report the result without changing the policy
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Render recalled memories as a prompt-ready block within a token budget
+    Context {
+        query: String,
+        /// Approximate token budget (4 characters per token)
+        #[arg(long, default_value_t = 1000)]
+        budget: usize,
+        /// Output format: markdown or xml
+        #[arg(long, default_value = "markdown")]
+        format: ContextFormat,
+    },
+    /// Judge a recalled memory: useful reinforces it, irrelevant undoes the
+    /// recall boost, wrong demotes it and tags it needs_review
+    Feedback {
+        /// Memory ID from the recall results
+        id: i64,
+        /// useful, irrelevant or wrong
+        verdict: Feedback,
+        /// The query that returned the memory
+        #[arg(long)]
+        query: String,
+    },
     /// Export all memories as JSON to stdout
     Export,
     /// Import memories from JSON on stdin
@@ -152,6 +288,35 @@ enum Command {
         /// Preview what would be consolidated without making changes
         #[arg(long)]
         dry_run: bool,
+        /// Cosine similarity above which memories are clustered (default 0.80)
+        #[arg(long, value_parser = parse_threshold)]
+        threshold: Option<f32>,
+        /// Replace clusters of episodes or actions with a summary memory and
+        /// archive the originals, instead of keeping only the strongest
+        #[arg(long)]
+        summarize: bool,
+        /// Only look for clusters involving memories added since the last
+        /// consolidation
+        #[arg(long)]
+        incremental: bool,
+    },
+    /// List the topics memories group into, or the memories in one topic
+    Topics {
+        /// Recompute topics from the current embeddings
+        #[arg(long)]
+        rebuild: bool,
+        /// Number of topics when rebuilding (default: about sqrt(n/2), at most 32)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        k: Option<u64>,
+        /// List the memories in this topic instead
+        #[arg(long)]
+        show: Option<i64>,
+    },
+    /// Extract candidate facts from episodes and actions
+    Extract {
+        /// List the candidates without storing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Show or set importance scores for memories
     Importance {
@@ -182,6 +347,71 @@ enum Command {
     Validate { text: String },
     /// Verify audit log tamper-evidence chain
     VerifyAudit,
+    /// Show or update the recall policy for the current namespace
+    Policy {
+        /// Run the cross-encoder rerank stage on every recall
+        #[arg(long)]
+        rerank: Option<bool>,
+        /// Fusion strategy for recall in this namespace (see `recall --fusion`)
+        #[arg(long)]
+        fusion: Option<FusionStrategy>,
+        /// Language for memories whose language was not detected (e.g. de, spa, french)
+        #[arg(long, value_parser = parse_language)]
+        language: Option<String>,
+        /// Context boost weights, e.g. channel=0.3,source=0.1,session=0.2,cwd=0.2
+        #[arg(long)]
+        context_weights: Option<ContextWeights>,
+        /// Extract candidate facts from episodes and actions as they are written
+        #[arg(long)]
+        extract_facts: Option<bool>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EntityCommand {
+    /// List entities with their aliases and fact counts
+    List,
+    /// Add another name for an entity
+    Alias {
+        /// The new name
+        alias: String,
+        /// The entity it refers to
+        entity: String,
+    },
+    /// Merge one entity into another; its facts and aliases move over
+    Merge { from: String, into: String },
+}
+
+#[derive(Subcommand)]
+enum RelationCommand {
+    /// Map a synonym to a canonical relation; existing facts are rewritten
+    Synonym {
+        /// The relation name to fold away
+        synonym: String,
+        /// The canonical relation it means
+        relation: String,
+    },
+    /// Rewrite existing facts to their canonical relations
+    Normalize,
+}
+
+#[derive(Subcommand)]
+enum SchemaCommand {
+    /// Declare (or replace) the schema of a relation
+    Set {
+        relation: String,
+        /// one: a new object supersedes the old; many: objects accumulate
+        #[arg(long, default_value = "many")]
+        cardinality: Cardinality,
+        /// Object type: entity, string, number, date or url
+        #[arg(long = "type", default_value = "entity")]
+        object_type: ObjectType,
+        /// Relation reading the fact from the object's side (e.g. child_of for parent_of)
+        #[arg(long)]
+        inverse: Option<String>,
+    },
+    /// List declared relation schemas
+    List,
 }
 
 fn default_db_path() -> String {
@@ -189,6 +419,36 @@ fn default_db_path() -> String {
     format!("{home}/.conch/default.db")
 }
 
+fn load_suite(
+    db: &ConchDB,
+    path: Option<&str>,
+    from_feedback: bool,
+) -> Result<EvalSuite, Box<dyn std::error::Error>> {
+    match path {
+        Some(path) if !from_feedback => Ok(EvalSuite::load(path)?),
+        _ => {
+            let suite = EvalSuite::from_feedback(&db.feedback_log()?);
+            if suite.cases.is_empty() {
+                return Err("no useful feedback recorded in this namespace yet".into());
+            }
+            Ok(suite)
+        }
+    }
+}
+
+fn parse_threshold(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(t) if t > 0.0 && t <= 1.0 => Ok(t),
+        _ => Err(format!("threshold must be a number in (0, 1], got {s}")),
+    }
+}
+
+fn parse_language(s: &str) -> Result<String, String> {
+    normalize_language(s)
+        .map(str::to_string)
+        .ok_or_else(|| format!("unsupported language: {s}"))
+}
+
 fn parse_duration_secs(s: &str) -> Result<i64, String> {
     let s = s.trim();
     if s.is_empty() {
@@ -425,8 +685,53 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                 println!("Remembered intent: {text}");
             }
         }
-        Command::Recall { query, limit, tag } => {
-            let results = db.recall_with_tag(query, *limit, tag.as_deref())?;
+        Command::Recall {
+            query,
+            limit,
+            tag,
+            topic,
+            rerank,
+            no_rerank,
+            no_expand,
+            hops,
+            vector_threshold,
+            diverse,
+            diversity_lambda,
+            fusion,
+            session_id,
+            channel,
+            source,
+            cwd,
+        } => {
+            let context = RecallContext {
+                channel: channel.clone(),
+                source: source.clone(),
+                session_id: session_id.clone(),
+                cwd: cwd.clone(),
+            };
+            let options = RecallOptions {
+                tag: tag.clone(),
+                rerank: if *rerank {
+                    Some(true)
+                } else if *no_rerank {
+                    Some(false)
+                } else {
+                    None
+                },
+                disable_expansion: *no_expand,
+                spread_hops: *hops,
+                vector_threshold: *vector_threshold,
+                diversity: diverse.then(|| diversity_lambda.unwrap_or(DEFAULT_DIVERSITY_LAMBDA)),
+                fusion: *fusion,
+                language: None,
+                peek: false,
+                coefficients: None,
+                session_id: session_id.clone(),
+                context: (!context.is_empty()).then_some(context),
+                context_weights: None,
+                topic: *topic,
+            };
+            let results = db.recall_with_options(query, *limit, &options)?;
             if cli.json {
                 println!("{}", serde_json::to_string_pretty(&results)?);
             } else if !cli.quiet {
@@ -440,7 +745,7 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                         format!(" [{}]", r.memory.tags.join(", "))
                     };
                     let explain = &r.explain;
-                    let rank_line = format!(
+                    let mut rank_line = format!(
                         "rrf#{} bm25#{} vec#{}",
                         explain.rrf_rank,
                         explain
@@ -452,6 +757,15 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                             .map(|v| v.to_string())
                             .unwrap_or_else(|| "-".to_string()),
                     );
+                    if let Some(rr) = explain.rerank_score {
+                        rank_line.push_str(&format!(" rerank {rr:.2}"));
+                    }
+                    if let Some(probe) = &explain.expansion_probe {
+                        rank_line.push_str(&format!(" via \"{probe}\""));
+                    }
+                    if let (Some(hop), Some(via)) = (explain.graph_hop, &explain.graph_via) {
+                        rank_line.push_str(&format!(" graph hop {hop} via \"{via}\""));
+                    }
                     match &r.memory.kind {
                         MemoryKind::Fact(f) => println!(
                             "[fact] {} {} {} (str: {:.2}, score: {:.3}, {}){tag_suffix}",
@@ -637,15 +951,29 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
-        Command::Embed => {
-            let count = db.embed_all()?;
+        Command::Embed { batch_size } => {
+            let show_progress = !cli.json && !cli.quiet;
+            let report = db.embed_all_batched(*batch_size, |progress| {
+                if show_progress {
+                    render_embed_progress(progress);
+                }
+            })?;
+            if show_progress && report.batches > 0 {
+                eprintln!();
+            }
             if cli.json {
-                println!("{}", serde_json::json!({ "embedded": count }));
+                println!("{}", serde_json::to_string_pretty(&report)?);
             } else if !cli.quiet {
-                if count == 0 {
+                if report.embedded == 0 && report.failures.is_empty() {
                     println!("All memories have embeddings.");
                 } else {
-                    println!("Embedded {count} memories.");
+                    println!("Embedded {} memories.", report.embedded);
+                }
+                if !report.failures.is_empty() {
+                    println!("Failed to embed {} memories:", report.failures.len());
+                    for f in &report.failures {
+                        println!("  id: {} error: {}", f.id, f.error);
+                    }
                 }
             }
         }
@@ -726,15 +1054,25 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                     if !mem.tags.is_empty() {
                         println!("  Tags:           {}", mem.tags.join(", "));
                     }
+                    if let Some(at) = &info.archived_at {
+                        println!("  Archived:       {at}");
+                    }
+                    for link in &info.lineage {
+                        if link.memory_id == mem.id {
+                            println!("  {}:   #{}", link.relation, link.source_id);
+                        } else {
+                            println!("  Source of:      #{} ({})", link.memory_id, link.relation);
+                        }
+                    }
                     if !info.related.is_empty() {
-                        println!("  Related facts:");
+                        println!("  Related:");
                         for node in &info.related {
-                            if let MemoryKind::Fact(f) = &node.memory.kind {
-                                println!(
-                                    "    - {} {} {} (via: {})",
-                                    f.subject, f.relation, f.object, node.connected_via
-                                );
-                            }
+                            println!(
+                                "    - [id:{}] {} (via: {})",
+                                node.memory.id,
+                                node.memory.text_for_embedding(),
+                                node.connected_via
+                            );
                         }
                     }
                 }
@@ -747,6 +1085,303 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         },
+        Command::Entity { command } => {
+            let entities = match command {
+                EntityCommand::List => db.entities()?,
+                EntityCommand::Alias { alias, entity } => vec![db.alias_entity(alias, entity)?],
+                EntityCommand::Merge { from, into } => vec![db.merge_entities(from, into)?],
+            };
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&entities)?);
+            } else if !cli.quiet {
+                if entities.is_empty() {
+                    println!("No entities registered.");
+                }
+                for entity in &entities {
+                    if entity.aliases.is_empty() {
+                        println!("{} — {} fact(s)", entity.name, entity.facts);
+                    } else {
+                        println!(
+                            "{} (aka {}) — {} fact(s)",
+                            entity.name,
+                            entity.aliases.join(", "),
+                            entity.facts
+                        );
+                    }
+                }
+            }
+        }
+        Command::Mentions { reindex, limit } => {
+            if *reindex {
+                let count = db.reindex_mentions()?;
+                if !cli.json && !cli.quiet {
+                    println!("Indexed {count} mention(s).");
+                }
+            }
+            let counts = db.mention_counts(*limit)?;
+            if cli.json {
+                let entries: Vec<_> = counts
+                    .iter()
+                    .map(|(entity, memories)| {
+                        serde_json::json!({ "entity": entity, "memories": memories })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else if !cli.quiet {
+                if counts.is_empty() {
+                    println!("No mentions indexed.");
+                }
+                for (entity, memories) in &counts {
+                    println!("{entity} — {memories} memories");
+                }
+            }
+        }
+        Command::Relations { command } => {
+            let rewritten = match command {
+                Some(RelationCommand::Synonym { synonym, relation }) => {
+                    Some(db.add_relation_synonym(synonym, relation)?)
+                }
+                Some(RelationCommand::Normalize) => Some(db.normalize_relations()?),
+                None => None,
+            };
+            if let Some(count) = rewritten {
+                if !cli.json && !cli.quiet {
+                    println!("Rewrote {count} fact(s).");
+                }
+            }
+            let relations = db.relations()?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&relations)?);
+            } else if !cli.quiet {
+                if relations.is_empty() {
+                    println!("No relations in use.");
+                }
+                for usage in &relations {
+                    if usage.synonyms.is_empty() {
+                        println!("{} — {} fact(s)", usage.relation, usage.facts);
+                    } else {
+                        println!(
+                            "{} — {} fact(s) (synonyms: {})",
+                            usage.relation,
+                            usage.facts,
+                            usage.synonyms.join(", ")
+                        );
+                    }
+                }
+            }
+        }
+        Command::Schema { command } => {
+            let schemas = match command {
+                SchemaCommand::Set {
+                    relation,
+                    cardinality,
+                    object_type,
+                    inverse,
+                } => vec![db.set_relation_schema(
+                    relation,
+                    *cardinality,
+                    *object_type,
+                    inverse.as_deref(),
+                )?],
+                SchemaCommand::List => db.relation_schemas()?,
+            };
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&schemas)?);
+            } else if !cli.quiet {
+                if schemas.is_empty() {
+                    println!("No relation schemas declared.");
+                }
+                for schema in &schemas {
+                    let inverse = schema
+                        .inverse
+                        .as_deref()
+                        .map(|inverse| format!(", inverse {inverse}"))
+                        .unwrap_or_default();
+                    println!(
+                        "{} — {}, {}{inverse}",
+                        schema.relation, schema.cardinality, schema.object_type
+                    );
+                }
+            }
+        }
+        Command::Facts { entity, relation } => {
+            let views = db.facts_about(entity, relation.as_deref())?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&views)?);
+            } else if !cli.quiet {
+                if views.is_empty() {
+                    println!("No facts about \"{entity}\".");
+                }
+                for view in &views {
+                    let inverted = if view.inverted { ", inverse" } else { "" };
+                    println!(
+                        "{} {} {} (#{}{inverted})",
+                        view.fact.subject, view.fact.relation, view.fact.object, view.memory_id
+                    );
+                }
+            }
+        }
+        Command::Associations { id, limit } => {
+            let associations = db.associations(*id, *limit)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&associations)?);
+            } else if !cli.quiet {
+                if associations.is_empty() {
+                    println!("No co-recall associations for memory #{id}.");
+                }
+                for assoc in &associations {
+                    let kind_str = match &assoc.memory.kind {
+                        MemoryKind::Fact(f) => {
+                            format!("{} {} {}", f.subject, f.relation, f.object)
+                        }
+                        MemoryKind::Episode(e) => truncate(&e.text, 60),
+                        MemoryKind::Action(a) => truncate(&a.text, 60),
+                        MemoryKind::Intent(i) => truncate(&i.text, 60),
+                    };
+                    println!(
+                        "  #{} weight {:.3} ({}x, last {}) {kind_str}",
+                        assoc.memory.id,
+                        assoc.weight,
+                        assoc.count,
+                        assoc.last_seen.format("%Y-%m-%d %H:%M")
+                    );
+                }
+            }
+        }
+        Command::Eval {
+            suite,
+            from_feedback,
+            baseline,
+            save_baseline,
+        } => {
+            let suite = load_suite(db, suite.as_deref(), *from_feedback)?;
+            let report = db.evaluate(&suite)?;
+            let diff = match baseline {
+                Some(path) => Some(report.diff(&EvalReport::load(path)?)),
+                None => None,
+            };
+            if let Some(path) = save_baseline {
+                report.save(path)?;
+            }
+            if cli.json {
+                let output = serde_json::json!({ "report": report, "diff": diff });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else if !cli.quiet {
+                let k = report.k;
+                let delta = |d: Option<f64>| d.map(|d| format!(" ({d:+.3})")).unwrap_or_default();
+                println!(
+                    "{} cases: MRR {:.3}{}  nDCG@{k} {:.3}{}  recall@{k} {:.3}{}",
+                    report.cases.len(),
+                    report.mrr,
+                    delta(diff.as_ref().map(|d| d.mrr)),
+                    report.ndcg,
+                    delta(diff.as_ref().map(|d| d.ndcg)),
+                    report.recall,
+                    delta(diff.as_ref().map(|d| d.recall)),
+                );
+                let rank = |r: Option<usize>| r.map_or("-".to_string(), |r| r.to_string());
+                for case in &report.cases {
+                    println!(
+                        "  rank {:>2}  nDCG {:.3}  recall {:.3}  {}",
+                        rank(case.first_relevant_rank),
+                        case.ndcg,
+                        case.recall,
+                        case.name
+                    );
+                    if !case.missing.is_empty() {
+                        println!("           missing: {}", case.missing.join(", "));
+                    }
+                }
+                if let Some(diff) = &diff {
+                    if diff.changed.is_empty() {
+                        println!("No per-query changes against baseline.");
+                    } else {
+                        println!("Changed against baseline:");
+                    }
+                    for case in &diff.changed {
+                        println!(
+                            "  rank {} -> {}  nDCG {:+.3}  recall {:+.3}  {}",
+                            rank(case.baseline_rank),
+                            rank(case.rank),
+                            case.ndcg,
+                            case.recall,
+                            case.name
+                        );
+                    }
+                    for name in &diff.added {
+                        println!("  new: {name}");
+                    }
+                    for name in &diff.removed {
+                        println!("  removed: {name}");
+                    }
+                }
+            }
+        }
+        Command::Tune {
+            suite,
+            from_feedback,
+            metric,
+            dry_run,
+        } => {
+            let suite = load_suite(db, suite.as_deref(), *from_feedback)?;
+            let result = db.tune(&suite, *metric)?;
+            let saved = !*dry_run && result.gain() > 0.0;
+            if saved {
+                let mut policy = db.policy()?;
+                policy.coefficients = Some(result.params.coefficients);
+                policy.fusion = Some(result.params.fusion);
+                db.set_policy(&policy)?;
+            }
+            if cli.json {
+                let output = serde_json::json!({ "result": result, "saved": saved });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else if !cli.quiet {
+                let c = result.params.coefficients;
+                println!(
+                    "{}: {:.3} -> {:.3} ({:+.3}) over {} evaluations",
+                    result.metric,
+                    result.baseline,
+                    result.tuned,
+                    result.gain(),
+                    result.evaluations
+                );
+                println!(
+                    "  coefficients: rrf_exp={} decay_exp={} recency_exp={} access_exp={}",
+                    c.rrf_exp, c.decay_exp, c.recency_exp, c.access_exp
+                );
+                println!("  fusion: {}", result.params.fusion);
+                if saved {
+                    println!("Saved to the policy for namespace \"{}\".", db.namespace());
+                } else if *dry_run {
+                    println!("Dry run; policy unchanged.");
+                } else {
+                    println!("No improvement; policy unchanged.");
+                }
+            }
+        }
+        Command::Context {
+            query,
+            budget,
+            format,
+        } => {
+            let block = db.context(query, *budget, *format)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&block)?);
+            } else if !cli.quiet {
+                print!("{}", block.text);
+            }
+        }
+        Command::Feedback { id, verdict, query } => {
+            let entry = db.feedback(*id, query, *verdict)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&entry)?);
+            } else if !cli.quiet {
+                println!(
+                    "Recorded {} for #{}: strength {:.3} -> {:.3}",
+                    entry.feedback, entry.memory_id, entry.strength_before, entry.strength_after
+                );
+            }
+        }
         Command::Export => {
             let data = db.export()?;
             println!("{}", serde_json::to_string_pretty(&data)?);
@@ -761,9 +1396,38 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                 println!("Imported {count} memories.");
             }
         }
-        Command::Consolidate { dry_run } => {
+        Command::Consolidate {
+            dry_run,
+            threshold,
+            summarize,
+            incremental,
+        } => {
+            let options = ConsolidateOptions {
+                threshold: *threshold,
+                mode: if *summarize {
+                    ConsolidateMode::Summarize
+                } else {
+                    ConsolidateMode::Merge
+                },
+                incremental: *incremental,
+            };
+            let show_progress = !cli.json && !cli.quiet;
+            let mut last_stage = None;
+            let mut on_progress = |progress: &ConsolidateProgress| {
+                if !show_progress {
+                    return;
+                }
+                if last_stage.is_some_and(|stage| stage != progress.stage) {
+                    eprintln!();
+                }
+                last_stage = Some(progress.stage);
+                render_consolidate_progress(progress);
+            };
             if *dry_run {
-                let clusters = db.consolidate_clusters()?;
+                let clusters = db.consolidate_clusters(&options, &mut on_progress)?;
+                if last_stage.is_some() {
+                    eprintln!();
+                }
                 if cli.json {
                     println!("{}", serde_json::to_string_pretty(&clusters)?);
                 } else if !cli.quiet {
@@ -790,18 +1454,104 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                                     dup.strength
                                 );
                             }
+                            for pair in &cluster.similarities {
+                                println!(
+                                    "    Similarity: id:{} ~ id:{} = {:.3}",
+                                    pair.a, pair.b, pair.similarity
+                                );
+                            }
+                            if let Some(summary) = &cluster.summary {
+                                println!("    Summary:   {summary}");
+                            }
                         }
                     }
                 }
             } else {
-                let result = db.consolidate(false)?;
+                let result = db.consolidate(false, &options, &mut on_progress)?;
+                if last_stage.is_some() {
+                    eprintln!();
+                }
                 if cli.json {
                     println!("{}", serde_json::to_string_pretty(&result)?);
+                } else if !cli.quiet && *summarize {
+                    println!(
+                        "Summarized {} cluster(s) into {} new memories; {} originals archived.",
+                        result.clusters, result.summarized, result.archived
+                    );
                 } else if !cli.quiet {
                     println!("Consolidated {} cluster(s): {} memories archived, {} canonical memories boosted.", result.clusters, result.archived, result.boosted);
                 }
             }
         }
+        Command::Topics { rebuild, k, show } => {
+            if let Some(topic_id) = show {
+                let memories = db.topic_memories(*topic_id)?;
+                if cli.json {
+                    println!("{}", serde_json::to_string_pretty(&memories)?);
+                } else if !cli.quiet {
+                    if memories.is_empty() {
+                        println!("No memories in topic {topic_id}.");
+                    }
+                    for mem in &memories {
+                        println!(
+                            "[id:{}] {} (str: {:.2})",
+                            mem.id,
+                            mem.text_for_embedding(),
+                            mem.strength
+                        );
+                    }
+                }
+            } else {
+                let mut topics = db.topics()?;
+                if *rebuild || topics.is_empty() {
+                    topics = db.build_topics(k.map(|k| k as usize))?;
+                }
+                if cli.json {
+                    println!("{}", serde_json::to_string_pretty(&topics)?);
+                } else if !cli.quiet {
+                    if topics.is_empty() {
+                        println!("No embedded memories to group into topics.");
+                    }
+                    for topic in &topics {
+                        println!(
+                            "[topic:{}] {} — {} memories (avg str: {:.2})",
+                            topic.id, topic.label, topic.size, topic.strength
+                        );
+                    }
+                }
+            }
+        }
+        Command::Extract { dry_run } => {
+            let report = db.extract_facts(*dry_run)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else if !cli.quiet {
+                for candidate in &report.candidates {
+                    let fact = &candidate.fact;
+                    println!(
+                        "[id:{}] {} {} {} ({}, conf: {:.2})",
+                        candidate.source_id,
+                        fact.subject,
+                        fact.relation,
+                        fact.object,
+                        fact.rule,
+                        fact.confidence
+                    );
+                }
+                if *dry_run {
+                    println!(
+                        "Dry run: {} candidate(s) from {} memories.",
+                        report.candidates.len(),
+                        report.scanned
+                    );
+                } else {
+                    println!(
+                        "Scanned {} memories: {} fact(s) created, {} existing fact(s) linked.",
+                        report.scanned, report.created, report.linked
+                    );
+                }
+            }
+        }
         Command::Importance { id, set, score } => {
             if let (Some(mem_id), Some(value)) = (id, set) {
                 db.set_importance(*mem_id, *value)?;
@@ -938,10 +1688,97 @@ fn run(cli: &Cli, db: &ConchDB) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+        Command::Policy {
+            rerank,
+            fusion,
+            language,
+            context_weights,
+            extract_facts,
+        } => {
+            let mut policy = db.policy()?;
+            let changed = rerank.is_some()
+                || fusion.is_some()
+                || language.is_some()
+                || context_weights.is_some()
+                || extract_facts.is_some();
+            if let Some(rerank) = rerank {
+                policy.rerank = *rerank;
+            }
+            if let Some(fusion) = fusion {
+                policy.fusion = Some(*fusion);
+            }
+            if let Some(language) = language {
+                policy.language = Some(language.clone());
+            }
+            if let Some(weights) = context_weights {
+                policy.context_weights = Some(*weights);
+            }
+            if let Some(extract) = extract_facts {
+                policy.extract_facts = *extract;
+            }
+            if changed {
+                db.set_policy(&policy)?;
+            }
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&policy)?);
+            } else if !cli.quiet {
+                println!("Recall policy for namespace \"{}\":", db.namespace());
+                println!("  rerank: {}", policy.rerank);
+                println!("  fusion: {}", policy.fusion.unwrap_or_default());
+                println!(
+                    "  language: {}",
+                    policy.language.as_deref().unwrap_or(DEFAULT_LANGUAGE)
+                );
+                if let Some(c) = policy.coefficients {
+                    println!(
+                        "  coefficients: rrf_exp={} decay_exp={} recency_exp={} access_exp={}",
+                        c.rrf_exp, c.decay_exp, c.recency_exp, c.access_exp
+                    );
+                }
+                println!(
+                    "  context weights: {}",
+                    policy.context_weights.unwrap_or_default()
+                );
+                println!("  extract facts: {}", policy.extract_facts);
+            }
+        }
     }
     Ok(())
 }
 
+fn render_embed_progress(progress: &EmbedProgress) {
+    eprint!("\r{}", progress_bar(progress.processed, progress.total));
+    if progress.failed > 0 {
+        eprint!(" ({} failed)", progress.failed);
+    }
+    let _ = io::Write::flush(&mut io::stderr());
+}
+
+fn render_consolidate_progress(progress: &ConsolidateProgress) {
+    let stage = match progress.stage {
+        ConsolidateStage::Indexing => "Indexing ",
+        ConsolidateStage::Comparing => "Comparing",
+    };
+    eprint!(
+        "\r{stage} {}",
+        progress_bar(progress.processed, progress.total)
+    );
+    let _ = io::Write::flush(&mut io::stderr());
+}
+
+fn progress_bar(processed: usize, total: usize) -> String {
+    const WIDTH: usize = 30;
+    let filled = processed
+        .saturating_mul(WIDTH)
+        .checked_div(total)
+        .map_or(WIDTH, |w| w.min(WIDTH));
+    format!(
+        "[{}{}] {processed}/{total}",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled)
+    )
+}
+
 fn truncate(s: &str, max: usize) -> String {
     if s.len() <= max {
         s.to_string()
@@ -1,9 +1,27 @@
-use crate::embed::cosine_similarity;
-use crate::memory::MemoryRecord;
+use std::collections::HashMap;
+
+use chrono::Utc;
+
+use crate::embed::{cosine_similarity, EmbedError, Embedder};
+use crate::lsh::SimHasher;
+use crate::memory::{MemoryKind, MemoryRecord};
+use crate::relations::normalize_relation;
 use crate::store::MemoryStore;
+use crate::summarize::Summarizer;
 
 /// Minimum cosine similarity to consider two memories as belonging to the same cluster.
-const CONSOLIDATION_THRESHOLD: f32 = 0.80;
+pub const CONSOLIDATION_THRESHOLD: f32 = 0.80;
+
+/// Namespaces with at most this many embedded memories compare every pair;
+/// larger ones find candidate neighbours through the LSH index.
+pub const EXACT_COMPARISON_LIMIT: usize = 2_000;
+
+/// Memories hashed or compared per page; bounds memory use on large stores.
+const PAGE_SIZE: usize = 1_000;
+
+/// LSH buckets shared by more memories than this are skipped: they hold
+/// generic content and would make candidate generation quadratic again.
+const MAX_BUCKET_SIZE: usize = 256;
 
 /// Result of a consolidation pass.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -14,6 +32,62 @@ pub struct ConsolidateResult {
     pub archived: usize,
     /// Number of canonical memories that were boosted.
     pub boosted: usize,
+    /// Number of summary memories created (summarize mode).
+    pub summarized: usize,
+    /// Ids of the summary memories created (summarize mode).
+    pub summaries: Vec<i64>,
+}
+
+/// How a cluster is consolidated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsolidateMode {
+    /// Keep the strongest memory, merge tags into it and delete the rest.
+    #[default]
+    Merge,
+    /// Replace a cluster of episodes or actions with a new summary memory
+    /// linked to its sources, which are archived rather than deleted.
+    /// Clusters of facts and intents are left alone.
+    Summarize,
+}
+
+/// Options for a consolidation pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConsolidateOptions {
+    /// Cosine similarity above which memories cluster (default
+    /// [`CONSOLIDATION_THRESHOLD`]).
+    pub threshold: Option<f32>,
+    pub mode: ConsolidateMode,
+    /// Only look for clusters involving memories added since the last
+    /// (non-dry-run) consolidation pass.
+    pub incremental: bool,
+}
+
+/// Phase of a consolidation pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsolidateStage {
+    /// Hashing new embeddings into the LSH index (large namespaces only).
+    Indexing,
+    /// Comparing memories with their candidate neighbours.
+    Comparing,
+}
+
+/// Progress snapshot emitted during a consolidation pass.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct ConsolidateProgress {
+    pub stage: ConsolidateStage,
+    /// Memories hashed or compared so far in this stage.
+    pub processed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConsolidateError {
+    #[error("database error: {0}")]
+    Db(#[from] rusqlite::Error),
+    #[error("summarizer error: {0}")]
+    Summarize(#[from] EmbedError),
 }
 
 /// A cluster of related memories found during consolidation.
@@ -23,90 +97,395 @@ pub struct ConsolidateCluster {
     pub canonical: MemoryRecord,
     /// The weaker duplicates that would be archived.
     pub duplicates: Vec<MemoryRecord>,
+    /// Cosine similarity of every pair of members.
+    pub similarities: Vec<PairSimilarity>,
+    /// The summary that would replace the cluster (summarize mode preview).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+impl ConsolidateCluster {
+    /// All members, oldest first.
+    pub fn members_by_age(&self) -> Vec<&MemoryRecord> {
+        let mut members: Vec<&MemoryRecord> = std::iter::once(&self.canonical)
+            .chain(self.duplicates.iter())
+            .collect();
+        members.sort_by_key(|m| (m.created_at, m.id));
+        members
+    }
+
+    /// Whether summarize mode applies: a cluster of episodes or actions.
+    pub fn is_summarizable(&self) -> bool {
+        matches!(
+            self.canonical.kind,
+            MemoryKind::Episode(_) | MemoryKind::Action(_)
+        )
+    }
+}
+
+/// Cosine similarity between two members of a cluster (`a < b`).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct PairSimilarity {
+    pub a: i64,
+    pub b: i64,
+    pub similarity: f32,
+}
+
+/// Whether two memories may be merged: they must be of the same kind, and
+/// two facts about the same subject and relation must agree on the object
+/// ("lives in Austin" never absorbs "lives in Seattle").
+pub fn can_merge(a: &MemoryRecord, b: &MemoryRecord) -> bool {
+    match (&a.kind, &b.kind) {
+        (MemoryKind::Fact(fa), MemoryKind::Fact(fb)) => {
+            let norm = |s: &str| s.trim().to_lowercase();
+            norm(&fa.subject) != norm(&fb.subject)
+                || normalize_relation(&fa.relation) != normalize_relation(&fb.relation)
+                || norm(&fa.object) == norm(&fb.object)
+        }
+        (ka, kb) => std::mem::discriminant(ka) == std::mem::discriminant(kb),
+    }
 }
 
-/// Find clusters of related memories by pairwise cosine similarity.
+/// Find clusters of related memories in `namespace` by pairwise cosine similarity.
 /// Returns clusters where each cluster has 2+ members with similarity > threshold.
+///
+/// Clustering is single-linkage, except that two groups only join if every
+/// member of one [can merge](can_merge) with every member of the other.
+/// Strongest links are joined first.
 pub fn find_clusters(
     store: &MemoryStore,
+    namespace: &str,
     threshold: Option<f32>,
 ) -> Result<Vec<ConsolidateCluster>, rusqlite::Error> {
-    let threshold = threshold.unwrap_or(CONSOLIDATION_THRESHOLD);
-    let all = store.all_embeddings()?;
-    if all.len() < 2 {
-        return Ok(vec![]);
-    }
+    let options = ConsolidateOptions {
+        threshold,
+        ..Default::default()
+    };
+    find_clusters_with(store, namespace, &options, &mut |_| {})
+}
+
+/// [`find_clusters`] with full options and progress reporting.
+///
+/// Namespaces with up to [`EXACT_COMPARISON_LIMIT`] embedded memories compare
+/// every pair. Larger ones hash embeddings into a persistent LSH index and
+/// only compare memories sharing a bucket, a page at a time, so time and
+/// memory grow with the number of near neighbours rather than with the square
+/// of the namespace. With `incremental`, only pairs involving a memory added
+/// since the last consolidation pass are considered.
+pub fn find_clusters_with(
+    store: &MemoryStore,
+    namespace: &str,
+    options: &ConsolidateOptions,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<Vec<ConsolidateCluster>, rusqlite::Error> {
+    Ok(cluster_pass(
+        store,
+        namespace,
+        options,
+        EXACT_COMPARISON_LIMIT,
+        on_progress,
+    )?
+    .clusters)
+}
+
+/// Clusters found by one pass, with the highest memory id the pass covered.
+struct ClusterPass {
+    clusters: Vec<ConsolidateCluster>,
+    through: i64,
+}
 
-    // Build adjacency: for each pair with sim > threshold, record the link.
-    let n = all.len();
-    let mut assigned = vec![false; n];
-    let mut clusters: Vec<Vec<usize>> = Vec::new();
+/// Two memories (`a < b`) above the threshold, with their similarity.
+type Edge = (i64, i64, f32);
 
-    // Simple single-linkage clustering
-    for i in 0..n {
-        if assigned[i] {
-            continue;
+/// Candidate members by id, with their embeddings held separately so the
+/// returned records stay small.
+type Members = HashMap<i64, (MemoryRecord, Vec<f32>)>;
+
+fn cluster_pass(
+    store: &MemoryStore,
+    namespace: &str,
+    options: &ConsolidateOptions,
+    exact_limit: usize,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<ClusterPass, rusqlite::Error> {
+    let threshold = options.threshold.unwrap_or(CONSOLIDATION_THRESHOLD);
+    let since = if options.incremental {
+        store.consolidation_state(namespace)?.consolidated_through
+    } else {
+        0
+    };
+    let through = store.max_memory_id_ns(namespace)?;
+    let (edges, members) = if store.count_embedded_ns(namespace)? <= exact_limit {
+        exact_edges(store, namespace, threshold, since, through, on_progress)?
+    } else {
+        index_embeddings(store, namespace, on_progress)?;
+        lsh_edges(store, namespace, threshold, since, through, on_progress)?
+    };
+    Ok(ClusterPass {
+        clusters: build_clusters(edges, &members),
+        through,
+    })
+}
+
+/// Compare every pair in memory, skipping pairs already covered by `since`.
+fn exact_edges(
+    store: &MemoryStore,
+    namespace: &str,
+    threshold: f32,
+    since: i64,
+    through: i64,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<(Vec<Edge>, Members), rusqlite::Error> {
+    let all: Vec<(MemoryRecord, Vec<f32>)> = store
+        .active_memories_ns(namespace)?
+        .into_iter()
+        .filter(|mem| mem.id <= through)
+        .filter_map(|mut mem| mem.embedding.take().map(|emb| (mem, emb)))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (i, (a, emb_a)) in all.iter().enumerate() {
+        for (b, emb_b) in &all[i + 1..] {
+            if a.id.max(b.id) <= since {
+                continue;
+            }
+            let sim = cosine_similarity(emb_a, emb_b);
+            if sim > threshold {
+                edges.push((a.id.min(b.id), a.id.max(b.id), sim));
+            }
         }
-        let mut cluster = vec![i];
-        assigned[i] = true;
-
-        // Find all unassigned items similar to any member of this cluster
-        let mut frontier = vec![i];
-        while let Some(current) = frontier.pop() {
-            for j in 0..n {
-                if assigned[j] {
-                    continue;
-                }
-                let sim = cosine_similarity(&all[current].1, &all[j].1);
+        if (i + 1) % PAGE_SIZE == 0 || i + 1 == all.len() {
+            on_progress(&ConsolidateProgress {
+                stage: ConsolidateStage::Comparing,
+                processed: i + 1,
+                total: all.len(),
+            });
+        }
+    }
+    let members = all
+        .into_iter()
+        .map(|(mem, emb)| (mem.id, (mem, emb)))
+        .collect();
+    Ok((edges, members))
+}
+
+/// Hash embeddings not yet in the LSH index, a page at a time.
+fn index_embeddings(
+    store: &MemoryStore,
+    namespace: &str,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<(), rusqlite::Error> {
+    store.prune_consolidation_index(namespace)?;
+    let total = store.count_unindexed_embeddings_ns(namespace)?;
+    let mut hashers: HashMap<usize, SimHasher> = HashMap::new();
+    let mut processed = 0;
+    let mut after_id = 0;
+    loop {
+        let page = store.unindexed_embeddings_page_ns(namespace, after_id, PAGE_SIZE)?;
+        let Some(&(last, _)) = page.last() else {
+            break;
+        };
+        after_id = last;
+
+        let keys: Vec<(i64, usize, Vec<i64>)> = page
+            .iter()
+            .map(|(id, emb)| {
+                let hasher = hashers
+                    .entry(emb.len())
+                    .or_insert_with(|| SimHasher::new(emb.len()));
+                (*id, emb.len(), hasher.band_keys(emb))
+            })
+            .collect();
+        store.add_consolidation_keys(namespace, &keys)?;
+        processed += page.len();
+        on_progress(&ConsolidateProgress {
+            stage: ConsolidateStage::Indexing,
+            processed,
+            total: total.max(processed),
+        });
+    }
+    Ok(())
+}
+
+/// Verify candidate pairs from the LSH index, one page of memories at a time.
+fn lsh_edges(
+    store: &MemoryStore,
+    namespace: &str,
+    threshold: f32,
+    since: i64,
+    through: i64,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<(Vec<Edge>, Members), rusqlite::Error> {
+    let skip = store.oversized_consolidation_buckets(namespace, MAX_BUCKET_SIZE)?;
+    let total = store.count_embedded_ns(namespace)?;
+    let mut edges = Vec::new();
+    let mut processed = 0;
+    let mut after_id = 0;
+    loop {
+        let ids = store.indexed_ids_page_ns(namespace, after_id, PAGE_SIZE)?;
+        let Some(&last) = ids.last() else {
+            break;
+        };
+        let pairs =
+            store.consolidation_candidates(namespace, after_id, last, since, through, &skip)?;
+        after_id = last;
+
+        let embeddings = load_embeddings(store, pairs.iter().flat_map(|&(a, b)| [a, b]))?;
+        for (a, b) in pairs {
+            if let (Some(emb_a), Some(emb_b)) = (embeddings.get(&a), embeddings.get(&b)) {
+                let sim = cosine_similarity(emb_a, emb_b);
                 if sim > threshold {
-                    assigned[j] = true;
-                    cluster.push(j);
-                    frontier.push(j);
+                    edges.push((a, b, sim));
                 }
             }
         }
-
-        if cluster.len() >= 2 {
-            clusters.push(cluster);
-        }
+        processed += ids.len();
+        on_progress(&ConsolidateProgress {
+            stage: ConsolidateStage::Comparing,
+            processed,
+            total: total.max(processed),
+        });
     }
 
-    // Convert index clusters to MemoryRecord clusters
-    let mut result = Vec::new();
-    for cluster_indices in clusters {
-        let mut members: Vec<MemoryRecord> = Vec::new();
-        for &idx in &cluster_indices {
-            let id = all[idx].0;
-            if let Some(mem) = store.get_memory(id)? {
-                members.push(mem);
-            }
+    let members = store
+        .memories_by_ids(&sorted_ids(edges.iter().flat_map(|&(a, b, _)| [a, b])))?
+        .into_iter()
+        .filter_map(|mut mem| mem.embedding.take().map(|emb| (mem.id, (mem, emb))))
+        .collect();
+    Ok((edges, members))
+}
+
+fn load_embeddings(
+    store: &MemoryStore,
+    ids: impl Iterator<Item = i64>,
+) -> Result<HashMap<i64, Vec<f32>>, rusqlite::Error> {
+    Ok(store
+        .memories_by_ids(&sorted_ids(ids))?
+        .into_iter()
+        .filter_map(|mem| mem.embedding.map(|emb| (mem.id, emb)))
+        .collect())
+}
+
+fn sorted_ids(ids: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut ids: Vec<i64> = ids.collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// Join edges strongest first, refusing joins that would put two memories
+/// that [can't merge](can_merge) in one cluster.
+fn build_clusters(mut edges: Vec<Edge>, members: &Members) -> Vec<ConsolidateCluster> {
+    edges.sort_by(|x, y| {
+        y.2.partial_cmp(&x.2)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| (x.0, x.1).cmp(&(y.0, y.1)))
+    });
+
+    // Each memory points at its group's root; each root owns its member list.
+    let mut root: HashMap<i64, i64> = HashMap::new();
+    let mut groups: HashMap<i64, Vec<i64>> = HashMap::new();
+    for &(a, b, _) in &edges {
+        if !members.contains_key(&a) || !members.contains_key(&b) {
+            continue;
         }
-        if members.len() < 2 {
+        let ra = *root.entry(a).or_insert(a);
+        let rb = *root.entry(b).or_insert(b);
+        if ra == rb {
             continue;
         }
-        // Pick the strongest as canonical
-        members.sort_by(|a, b| {
-            b.strength
-                .partial_cmp(&a.strength)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        let canonical = members.remove(0);
-        result.push(ConsolidateCluster {
-            canonical,
-            duplicates: members,
+        let group_a = groups.remove(&ra).unwrap_or_else(|| vec![a]);
+        let group_b = groups.remove(&rb).unwrap_or_else(|| vec![b]);
+        let compatible = group_a.iter().all(|x| {
+            group_b
+                .iter()
+                .all(|y| can_merge(&members[x].0, &members[y].0))
         });
+        if !compatible {
+            groups.insert(ra, group_a);
+            groups.insert(rb, group_b);
+            continue;
+        }
+        for id in &group_b {
+            root.insert(*id, ra);
+        }
+        let mut joined = group_a;
+        joined.extend(group_b);
+        groups.insert(ra, joined);
     }
 
-    Ok(result)
+    let mut groups: Vec<Vec<i64>> = groups
+        .into_values()
+        .filter(|g| g.len() >= 2)
+        .map(|mut g| {
+            g.sort_unstable();
+            g
+        })
+        .collect();
+    groups.sort_by_key(|g| g[0]);
+
+    groups
+        .into_iter()
+        .map(|ids| {
+            let mut similarities = Vec::new();
+            for (n, a) in ids.iter().enumerate() {
+                for b in &ids[n + 1..] {
+                    similarities.push(PairSimilarity {
+                        a: *a,
+                        b: *b,
+                        similarity: cosine_similarity(&members[a].1, &members[b].1),
+                    });
+                }
+            }
+            let mut records: Vec<MemoryRecord> =
+                ids.iter().map(|id| members[id].0.clone()).collect();
+            // Pick the strongest as canonical
+            records.sort_by(|a, b| {
+                b.strength
+                    .partial_cmp(&a.strength)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            let canonical = records.remove(0);
+            ConsolidateCluster {
+                canonical,
+                duplicates: records,
+                similarities,
+                summary: None,
+            }
+        })
+        .collect()
 }
 
-/// Run consolidation: find clusters, boost canonical, archive duplicates.
-/// Merges tags from duplicates into the canonical memory.
+/// Run consolidation in `namespace`: find clusters, boost canonical, archive
+/// duplicates. Merges tags from duplicates into the canonical memory.
 pub fn consolidate(
     store: &MemoryStore,
+    namespace: &str,
     threshold: Option<f32>,
 ) -> Result<ConsolidateResult, rusqlite::Error> {
-    let clusters = find_clusters(store, threshold)?;
+    let options = ConsolidateOptions {
+        threshold,
+        ..Default::default()
+    };
+    consolidate_with(store, namespace, &options, &mut |_| {})
+}
+
+/// [`consolidate`] with full options and progress reporting. Records the pass
+/// so a later incremental pass starts after it.
+pub fn consolidate_with(
+    store: &MemoryStore,
+    namespace: &str,
+    options: &ConsolidateOptions,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<ConsolidateResult, rusqlite::Error> {
+    let pass = cluster_pass(
+        store,
+        namespace,
+        options,
+        EXACT_COMPARISON_LIMIT,
+        on_progress,
+    )?;
+    let clusters = pass.clusters;
     let num_clusters = clusters.len();
     let mut archived = 0;
     let mut boosted = 0;
@@ -136,17 +515,199 @@ pub fn consolidate(
             archived += 1;
         }
     }
+    store.set_consolidated_through(namespace, pass.through, Utc::now())?;
 
     Ok(ConsolidateResult {
         clusters: num_clusters,
         archived,
         boosted,
+        summarized: 0,
+        summaries: Vec::new(),
     })
 }
 
+/// Summarizable clusters in `namespace`, each with the summary that would
+/// replace it. Changes nothing.
+pub fn preview_summaries(
+    store: &MemoryStore,
+    namespace: &str,
+    options: &ConsolidateOptions,
+    summarizer: &dyn Summarizer,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<Vec<ConsolidateCluster>, ConsolidateError> {
+    let pass = cluster_pass(
+        store,
+        namespace,
+        options,
+        EXACT_COMPARISON_LIMIT,
+        on_progress,
+    )?;
+    Ok(summarize_clusters(pass.clusters, summarizer)?)
+}
+
+fn summarize_clusters(
+    mut clusters: Vec<ConsolidateCluster>,
+    summarizer: &dyn Summarizer,
+) -> Result<Vec<ConsolidateCluster>, EmbedError> {
+    clusters.retain(ConsolidateCluster::is_summarizable);
+    for cluster in &mut clusters {
+        let texts: Vec<String> = cluster
+            .members_by_age()
+            .iter()
+            .map(|m| m.text_for_embedding())
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        cluster.summary = Some(summarizer.summarize(&texts)?);
+    }
+    Ok(clusters)
+}
+
+/// Run summarizing consolidation in `namespace`: each cluster of episodes or
+/// actions becomes one new memory of the same kind holding a summary of the
+/// cluster and the union of its tags. The summary is linked to every member
+/// with a `derived_from` lineage link, and the members are archived.
+pub fn consolidate_with_summaries(
+    store: &MemoryStore,
+    namespace: &str,
+    options: &ConsolidateOptions,
+    summarizer: &dyn Summarizer,
+    embedder: &dyn Embedder,
+    on_progress: &mut dyn FnMut(&ConsolidateProgress),
+) -> Result<ConsolidateResult, ConsolidateError> {
+    let pass = cluster_pass(
+        store,
+        namespace,
+        options,
+        EXACT_COMPARISON_LIMIT,
+        on_progress,
+    )?;
+    let clusters = summarize_clusters(pass.clusters, summarizer)?;
+    let mut result = ConsolidateResult {
+        clusters: clusters.len(),
+        archived: 0,
+        boosted: 0,
+        summarized: 0,
+        summaries: Vec::new(),
+    };
+
+    for cluster in &clusters {
+        let summary = cluster.summary.as_deref().unwrap_or_default().trim();
+        if summary.is_empty() {
+            continue;
+        }
+        let members = cluster.members_by_age();
+        let mut tags: Vec<String> = Vec::new();
+        for tag in members.iter().flat_map(|m| &m.tags) {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        let embedding = embedder.embed_one(summary)?;
+        let source_ids: Vec<i64> = members.iter().map(|m| m.id).collect();
+        // The summary only goes live together with its sources' retirement.
+        let (id, archived) = store.insert_summary_ns(
+            summary,
+            matches!(cluster.canonical.kind, MemoryKind::Action(_)),
+            &embedding,
+            &tags,
+            "consolidation",
+            &source_ids,
+            namespace,
+            Utc::now(),
+        )?;
+        result.archived += archived;
+        result.summarized += 1;
+        result.summaries.push(id);
+    }
+    store.set_consolidated_through(namespace, pass.through, Utc::now())?;
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::embed::Embedding;
+    use crate::memory::LineageRelation;
+    use crate::summarize::ExtractiveSummarizer;
+
+    struct FixedEmbedder;
+
+    impl Embedder for FixedEmbedder {
+        fn embed(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+            Ok(texts.iter().map(|_| vec![1.0, 0.0, 0.0]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            3
+        }
+    }
+
+    #[test]
+    fn summarize_mode_derives_a_summary_and_archives_sources() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let a = store
+            .remember_episode("Deployed billing to production.", Some(&[1.0, 0.0, 0.0]))
+            .unwrap();
+        let b = store
+            .remember_episode_with_tags(
+                "Deployed billing to production. The migration took 40 minutes.",
+                Some(&[0.99, 0.1, 0.0]),
+                &["deploy".to_string()],
+            )
+            .unwrap();
+        // Facts are never summarized.
+        store
+            .remember_fact("Billing", "runs_on", "db01", Some(&[0.0, 0.0, 1.0]))
+            .unwrap();
+        store
+            .remember_fact("Billing", "hosted_on", "db01", Some(&[0.0, 0.01, 1.0]))
+            .unwrap();
+
+        let result = consolidate_with_summaries(
+            &store,
+            "default",
+            &ConsolidateOptions {
+                threshold: Some(0.80),
+                mode: ConsolidateMode::Summarize,
+                incremental: false,
+            },
+            &ExtractiveSummarizer::default(),
+            &FixedEmbedder,
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(result.clusters, 1);
+        assert_eq!(result.summarized, 1);
+        assert_eq!(result.archived, 2);
+
+        // Nothing is deleted; the sources are archived and out of recall.
+        assert_eq!(store.all_memories().unwrap().len(), 5);
+        assert!(store.archived_at(a).unwrap().is_some());
+        let active = store.active_memories_ns("default").unwrap();
+        assert_eq!(active.len(), 3);
+        let summary = active
+            .iter()
+            .find(|m| m.source.as_deref() == Some("consolidation"))
+            .unwrap();
+        assert_eq!(
+            summary.text_for_embedding(),
+            "Deployed billing to production. The migration took 40 minutes."
+        );
+        assert_eq!(summary.tags, vec!["deploy".to_string()]);
+        assert!(store
+            .all_memories_with_text()
+            .unwrap()
+            .iter()
+            .all(|(m, _)| m.id != a && m.id != b));
+
+        let lineage = store.lineage(summary.id).unwrap();
+        let sources: Vec<i64> = lineage.iter().map(|l| l.source_id).collect();
+        assert_eq!(sources, vec![a, b]);
+        assert!(lineage
+            .iter()
+            .all(|l| l.relation == LineageRelation::DerivedFrom));
+    }
 
     #[test]
     fn find_clusters_groups_similar_embeddings() {
@@ -156,14 +717,14 @@ mod tests {
             .remember_fact("A", "is", "B", Some(&[1.0, 0.0, 0.0]))
             .unwrap();
         store
-            .remember_fact("A", "is", "C", Some(&[0.99, 0.1, 0.0]))
+            .remember_fact("A", "equals", "B", Some(&[0.99, 0.1, 0.0]))
             .unwrap();
         // One very different embedding
         store
             .remember_fact("X", "is", "Y", Some(&[0.0, 0.0, 1.0]))
             .unwrap();
 
-        let clusters = find_clusters(&store, Some(0.80)).unwrap();
+        let clusters = find_clusters(&store, "default", Some(0.80)).unwrap();
         assert_eq!(
             clusters.len(),
             1,
@@ -186,13 +747,184 @@ mod tests {
             .remember_fact("E", "is", "F", Some(&[0.0, 0.0, 1.0]))
             .unwrap();
 
-        let clusters = find_clusters(&store, Some(0.80)).unwrap();
+        let clusters = find_clusters(&store, "default", Some(0.80)).unwrap();
         assert!(
             clusters.is_empty(),
             "should find no clusters for orthogonal embeddings"
         );
     }
 
+    #[test]
+    fn find_clusters_stays_in_namespace() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let emb = [1.0, 0.0, 0.0];
+        for ns in ["agent-a", "agent-b"] {
+            store
+                .remember_episode_ns("deploy finished", Some(&emb), &[], None, None, None, ns)
+                .unwrap();
+        }
+
+        assert!(find_clusters(&store, "agent-a", Some(0.80))
+            .unwrap()
+            .is_empty());
+        consolidate(&store, "agent-a", Some(0.80)).unwrap();
+        assert_eq!(store.all_memories_ns("agent-b").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn find_clusters_never_merges_kinds_or_conflicting_facts() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let emb = [1.0, 0.0, 0.0];
+        store
+            .remember_fact("Jared", "lives_in", "Austin", Some(&emb))
+            .unwrap();
+        store
+            .remember_fact("Jared", "lives_in", "Seattle", Some(&emb))
+            .unwrap();
+        store
+            .remember_episode("Jared lives in Austin", Some(&emb))
+            .unwrap();
+        store
+            .remember_action("Jared lives in Austin", Some(&emb))
+            .unwrap();
+
+        let clusters = find_clusters(&store, "default", Some(0.80)).unwrap();
+        assert!(clusters.is_empty(), "got {clusters:?}");
+    }
+
+    #[test]
+    fn find_clusters_reports_pairwise_similarities() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let a = store
+            .remember_episode("deploy finished", Some(&[1.0, 0.0, 0.0]))
+            .unwrap();
+        let b = store
+            .remember_episode("deployment done", Some(&[0.99, 0.1, 0.0]))
+            .unwrap();
+        let c = store
+            .remember_episode("deploy complete", Some(&[0.98, 0.0, 0.1]))
+            .unwrap();
+
+        let clusters = find_clusters(&store, "default", Some(0.80)).unwrap();
+        assert_eq!(clusters.len(), 1);
+        let pairs: Vec<(i64, i64)> = clusters[0]
+            .similarities
+            .iter()
+            .map(|p| (p.a, p.b))
+            .collect();
+        assert_eq!(pairs, vec![(a, b), (a, c), (b, c)]);
+        assert!(clusters[0].similarities.iter().all(|p| p.similarity > 0.80));
+
+        // A stricter threshold splits them.
+        assert!(find_clusters(&store, "default", Some(0.999))
+            .unwrap()
+            .is_empty());
+    }
+
+    fn cluster_ids(clusters: &[ConsolidateCluster]) -> Vec<Vec<i64>> {
+        clusters
+            .iter()
+            .map(|c| {
+                let mut ids: Vec<i64> = c.members_by_age().iter().map(|m| m.id).collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect()
+    }
+
+    fn seed_two_topics(store: &MemoryStore) {
+        for (i, emb) in [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.98, 0.1, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.05, 0.99, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+        .iter()
+        .enumerate()
+        {
+            store
+                .remember_episode(&format!("note {i}"), Some(emb))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn lsh_path_finds_the_same_clusters_as_exact_comparison() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        seed_two_topics(&store);
+        let options = ConsolidateOptions::default();
+
+        let exact = find_clusters(&store, "default", None).unwrap();
+        let mut progress = Vec::new();
+        let lsh = cluster_pass(&store, "default", &options, 0, &mut |p| progress.push(*p)).unwrap();
+        assert_eq!(cluster_ids(&lsh.clusters), vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(cluster_ids(&lsh.clusters), cluster_ids(&exact));
+
+        // Progress covers indexing then comparison, ending complete.
+        let stages: Vec<ConsolidateStage> = progress.iter().map(|p| p.stage).collect();
+        assert_eq!(
+            stages,
+            vec![ConsolidateStage::Indexing, ConsolidateStage::Comparing]
+        );
+        assert!(progress.iter().all(|p| p.processed == 5 && p.total == 5));
+
+        // The index persists: a second pass hashes nothing new.
+        progress.clear();
+        cluster_pass(&store, "default", &options, 0, &mut |p| progress.push(*p)).unwrap();
+        assert!(progress
+            .iter()
+            .all(|p| p.stage == ConsolidateStage::Comparing));
+    }
+
+    #[test]
+    fn incremental_pass_only_considers_new_memories() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        seed_two_topics(&store);
+        store
+            .set_consolidated_through("default", 5, Utc::now())
+            .unwrap();
+        let options = ConsolidateOptions {
+            incremental: true,
+            ..Default::default()
+        };
+        for exact_limit in [EXACT_COMPARISON_LIMIT, 0] {
+            let pass = cluster_pass(&store, "default", &options, exact_limit, &mut |_| {}).unwrap();
+            assert!(pass.clusters.is_empty(), "old pairs are not revisited");
+        }
+
+        let new = store
+            .remember_episode("note 5", Some(&[0.0, 0.0, 0.1, 1.0]))
+            .unwrap();
+        for exact_limit in [EXACT_COMPARISON_LIMIT, 0] {
+            let pass = cluster_pass(&store, "default", &options, exact_limit, &mut |_| {}).unwrap();
+            assert_eq!(cluster_ids(&pass.clusters), vec![vec![5, new]]);
+            assert_eq!(pass.through, new);
+        }
+
+        // A real pass moves the watermark.
+        consolidate_with(&store, "default", &options, &mut |_| {}).unwrap();
+        assert_eq!(
+            store
+                .consolidation_state("default")
+                .unwrap()
+                .consolidated_through,
+            new
+        );
+    }
+
+    #[test]
+    fn lsh_index_drops_archived_memories() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        seed_two_topics(&store);
+        let options = ConsolidateOptions::default();
+        cluster_pass(&store, "default", &options, 0, &mut |_| {}).unwrap();
+
+        store.archive_memories(&[2], Utc::now()).unwrap();
+        let pass = cluster_pass(&store, "default", &options, 0, &mut |_| {}).unwrap();
+        assert_eq!(cluster_ids(&pass.clusters), vec![vec![3, 4]]);
+    }
+
     #[test]
     fn consolidate_merges_tags_and_archives() {
         let store = MemoryStore::open_in_memory().unwrap();
@@ -209,14 +941,14 @@ mod tests {
         let _id2 = store
             .remember_fact_with_tags(
                 "A",
-                "is",
-                "C",
+                "equals",
+                "B",
                 Some(&[0.99, 0.1, 0.0]),
                 &["tag2".to_string()],
             )
             .unwrap();
 
-        let result = consolidate(&store, Some(0.80)).unwrap();
+        let result = consolidate(&store, "default", Some(0.80)).unwrap();
         assert_eq!(result.clusters, 1);
         assert_eq!(result.archived, 1);
         assert_eq!(result.boosted, 1);
@@ -257,7 +989,7 @@ mod tests {
             )
             .unwrap();
 
-        let result = consolidate(&store, Some(0.80)).unwrap();
+        let result = consolidate(&store, "default", Some(0.80)).unwrap();
         assert_eq!(result.clusters, 1);
 
         let remaining = store.all_memories().unwrap();
@@ -272,11 +1004,11 @@ mod tests {
             .remember_fact("A", "is", "B", Some(&[1.0, 0.0, 0.0]))
             .unwrap();
         store
-            .remember_fact("A", "is", "C", Some(&[0.99, 0.1, 0.0]))
+            .remember_fact("A", "equals", "B", Some(&[0.99, 0.1, 0.0]))
             .unwrap();
 
         // Dry run: just find clusters without consolidating
-        let clusters = find_clusters(&store, Some(0.80)).unwrap();
+        let clusters = find_clusters(&store, "default", Some(0.80)).unwrap();
         assert_eq!(clusters.len(), 1);
 
         // Original memories should still exist
@@ -0,0 +1,275 @@
+use serde::Serialize;
+
+use crate::memory::{MemoryKind, MemoryRecord};
+
+/// Rough characters-per-token ratio used to estimate prompt size without a
+/// model-specific tokenizer. Errs towards overestimating for English text.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// How a context block is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextFormat {
+    #[default]
+    Markdown,
+    Xml,
+}
+
+impl std::fmt::Display for ContextFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextFormat::Markdown => write!(f, "markdown"),
+            ContextFormat::Xml => write!(f, "xml"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContextFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ContextFormat::Markdown),
+            "xml" => Ok(ContextFormat::Xml),
+            other => Err(format!(
+                "unknown context format \"{other}\" (expected markdown or xml)"
+            )),
+        }
+    }
+}
+
+/// A prompt-ready block of memories that fits a token budget.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextBlock {
+    pub text: String,
+    /// Ids of the memories cited in `text`, in relevance order.
+    pub memory_ids: Vec<i64>,
+    /// Estimated tokens in `text` (see [`estimate_tokens`]).
+    pub tokens: usize,
+    /// Recalled memories left out because they did not fit the budget.
+    pub omitted: usize,
+}
+
+/// Approximate token count: one token per [`CHARS_PER_TOKEN`] characters.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Sections in the order they are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Facts,
+    Intents,
+    Actions,
+    Episodes,
+}
+
+impl Section {
+    fn of(mem: &MemoryRecord) -> Self {
+        match mem.kind {
+            MemoryKind::Fact(_) => Section::Facts,
+            MemoryKind::Intent(_) => Section::Intents,
+            MemoryKind::Action(_) => Section::Actions,
+            MemoryKind::Episode(_) => Section::Episodes,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Section::Facts => "Facts",
+            Section::Intents => "Pending intents",
+            Section::Actions => "Recent actions",
+            Section::Episodes => "Episodes",
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            Section::Facts => "facts",
+            Section::Intents => "intents",
+            Section::Actions => "actions",
+            Section::Episodes => "episodes",
+        }
+    }
+
+    /// Text the section adds around its memories.
+    fn frame(self, format: ContextFormat) -> (String, String) {
+        match format {
+            ContextFormat::Markdown => (format!("### {}\n", self.title()), "\n".to_string()),
+            ContextFormat::Xml => (
+                format!("  <{}>\n", self.tag()),
+                format!("  </{}>\n", self.tag()),
+            ),
+        }
+    }
+}
+
+/// Render memories (most relevant first) into a block of at most
+/// `budget_tokens` estimated tokens.
+///
+/// Memories are admitted greedily in relevance order, each paying for its
+/// own line and, if it opens one, its section header; a memory that does not
+/// fit is skipped so a shorter one further down can still be included. The
+/// admitted memories are then grouped by kind: facts, pending intents by
+/// deadline, recent actions newest first, then episodes.
+pub fn render_context(
+    memories: &[MemoryRecord],
+    budget_tokens: usize,
+    format: ContextFormat,
+) -> ContextBlock {
+    let (open, close) = match format {
+        ContextFormat::Markdown => (String::new(), String::new()),
+        ContextFormat::Xml => ("<memories>\n".to_string(), "</memories>\n".to_string()),
+    };
+    let mut used = estimate_tokens(&open) + estimate_tokens(&close);
+    let mut included: Vec<(Section, &MemoryRecord, String)> = Vec::new();
+    let mut omitted = 0;
+    for mem in memories {
+        let section = Section::of(mem);
+        let line = render_line(mem, format);
+        let mut cost = estimate_tokens(&line);
+        if !included.iter().any(|(s, _, _)| *s == section) {
+            let (header, footer) = section.frame(format);
+            cost += estimate_tokens(&header) + estimate_tokens(&footer);
+        }
+        if used + cost > budget_tokens {
+            omitted += 1;
+            continue;
+        }
+        used += cost;
+        included.push((section, mem, line));
+    }
+
+    let memory_ids = included.iter().map(|(_, mem, _)| mem.id).collect();
+    if included.is_empty() {
+        return ContextBlock {
+            text: String::new(),
+            memory_ids,
+            tokens: 0,
+            omitted,
+        };
+    }
+
+    // Stable sort keeps relevance order within facts and episodes.
+    included.sort_by(|(a, am, _), (b, bm, _)| {
+        a.cmp(b).then_with(|| match a {
+            Section::Intents => deadline(am)
+                .is_none()
+                .cmp(&deadline(bm).is_none())
+                .then_with(|| deadline(am).cmp(&deadline(bm))),
+            Section::Actions => bm.created_at.cmp(&am.created_at),
+            _ => std::cmp::Ordering::Equal,
+        })
+    });
+
+    let mut text = open;
+    let mut current: Option<Section> = None;
+    for (section, _, line) in &included {
+        if current != Some(*section) {
+            if let Some(previous) = current {
+                text.push_str(&previous.frame(format).1);
+            }
+            text.push_str(&section.frame(format).0);
+            current = Some(*section);
+        }
+        text.push_str(line);
+    }
+    if let Some(last) = current {
+        text.push_str(&last.frame(format).1);
+    }
+    text.push_str(&close);
+    let text = text.trim_end().to_string() + "\n";
+
+    ContextBlock {
+        tokens: estimate_tokens(&text),
+        text,
+        memory_ids,
+        omitted,
+    }
+}
+
+/// Deadline of a pending intent, if it has one.
+fn deadline(mem: &MemoryRecord) -> Option<chrono::DateTime<chrono::Utc>> {
+    mem.temporal
+        .as_ref()
+        .filter(|t| t.status.eq_ignore_ascii_case("pending"))
+        .map(|t| t.resolved_end_at.unwrap_or(t.resolved_at))
+}
+
+fn render_line(mem: &MemoryRecord, format: ContextFormat) -> String {
+    let content = match &mem.kind {
+        MemoryKind::Fact(f) => format!(
+            "{} {} {}",
+            f.subject,
+            f.relation.replace('_', " "),
+            f.object
+        ),
+        MemoryKind::Episode(e) => e.text.clone(),
+        MemoryKind::Action(a) => a.text.clone(),
+        MemoryKind::Intent(i) => i.text.clone(),
+    };
+    let note = match &mem.kind {
+        MemoryKind::Intent(_) => deadline(mem).map(|d| ("due", format_time(d))),
+        MemoryKind::Action(_) => Some(("at", format_time(mem.created_at))),
+        _ => None,
+    };
+    match format {
+        ContextFormat::Markdown => {
+            let note = note
+                .map(|(label, when)| format!(" ({label} {when})"))
+                .unwrap_or_default();
+            format!("- {}{note} [#{}]\n", content.trim(), mem.id)
+        }
+        ContextFormat::Xml => {
+            let note = note
+                .map(|(label, when)| format!(" {label}=\"{when}\""))
+                .unwrap_or_default();
+            format!(
+                "    <memory id=\"{}\"{note}>{}</memory>\n",
+                mem.id,
+                escape_xml(content.trim())
+            )
+        }
+    }
+}
+
+fn format_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_four_characters_per_token() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn escapes_xml_markup() {
+        assert_eq!(
+            escape_xml("a < b && \"c\" > d"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; d"
+        );
+    }
+
+    #[test]
+    fn format_parses_by_name() {
+        assert_eq!(
+            "MD".parse::<ContextFormat>().unwrap(),
+            ContextFormat::Markdown
+        );
+        assert_eq!("xml".parse::<ContextFormat>().unwrap(), ContextFormat::Xml);
+        assert!("html".parse::<ContextFormat>().is_err());
+    }
+}
@@ -44,7 +44,7 @@ pub fn run_decay_ns(
 
     // Delete memories that have decayed below minimum strength (namespace-scoped)
     let deleted: usize = store.conn().execute(
-        "DELETE FROM memories WHERE strength < ?1 AND namespace = ?2",
+        "DELETE FROM memories WHERE strength < ?1 AND namespace = ?2 AND archived_at IS NULL",
         rusqlite::params![MIN_STRENGTH, namespace],
     )?;
 
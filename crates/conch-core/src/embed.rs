@@ -37,6 +37,34 @@ impl FastEmbedder {
             dimension: 384,
         })
     }
+
+    /// Multilingual paraphrase model (50+ languages, 384 dimensions), so
+    /// queries and memories in different languages land near each other.
+    ///
+    /// Its vectors are not comparable with the default model's; re-embed
+    /// existing memories after switching.
+    pub fn multilingual() -> Result<Self, EmbedError> {
+        let options =
+            fastembed::TextInitOptions::new(fastembed::EmbeddingModel::ParaphraseMLMiniLML12V2);
+        let model = fastembed::TextEmbedding::try_new(options)
+            .map_err(|e| EmbedError::Model(e.to_string()))?;
+        Ok(Self {
+            model: Mutex::new(model),
+            dimension: 384,
+        })
+    }
+
+    /// The model selected by `CONCH_EMBED_MODEL` (`multilingual`, or the
+    /// default English model when unset).
+    pub fn from_env() -> Result<Self, EmbedError> {
+        match std::env::var("CONCH_EMBED_MODEL").as_deref() {
+            Ok("multilingual") => Self::multilingual(),
+            Ok("") | Ok("default") | Err(_) => Self::new(),
+            Ok(other) => Err(EmbedError::Model(format!(
+                "unknown CONCH_EMBED_MODEL {other:?} (expected \"default\" or \"multilingual\")"
+            ))),
+        }
+    }
 }
 
 impl Embedder for FastEmbedder {
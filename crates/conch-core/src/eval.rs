@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::memory::{Feedback, FeedbackEntry};
+use crate::recall::{FusionStrategy, RecallOptions, RecallResult};
+
+/// Cut-off used for nDCG@k and recall@k when the suite does not set one.
+pub const DEFAULT_EVAL_K: usize = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid eval file: {0}")]
+    Parse(String),
+}
+
+/// A golden query set: queries with the memories they should recall.
+///
+/// Loaded from YAML or JSON (JSON is valid YAML):
+///
+/// ```yaml
+/// name: ops
+/// k: 5
+/// cases:
+///   - query: where does Jared work
+///     expected_ids: [12]
+///   - query: deploy credentials
+///     tag: ops
+///     expected_contains: ["PLAT-4412"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalSuite {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default = "default_k")]
+    pub k: usize,
+    pub cases: Vec<EvalCase>,
+}
+
+/// One golden query. A case passes an expectation when any of the top-k
+/// results has an expected id, or contains an expected snippet (case-insensitive).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvalCase {
+    /// Label used in reports and to match cases against a baseline
+    /// (defaults to the query).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub query: String,
+    /// Only consider memories carrying this tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Fusion strategy for this query (default: the namespace policy).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<FusionStrategy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_ids: Vec<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_contains: Vec<String>,
+}
+
+fn default_k() -> usize {
+    DEFAULT_EVAL_K
+}
+
+impl EvalSuite {
+    /// Load a suite from a YAML or JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EvalError> {
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a suite from YAML (or JSON) text.
+    pub fn from_yaml(text: &str) -> Result<Self, EvalError> {
+        let suite: Self =
+            serde_yaml::from_str(text).map_err(|e| EvalError::Parse(e.to_string()))?;
+        suite.validate()?;
+        Ok(suite)
+    }
+
+    /// Build a suite from logged relevance feedback: one case per query, expecting
+    /// the memories whose latest verdict for that query was useful. Queries
+    /// without a useful memory are skipped.
+    pub fn from_feedback(entries: &[FeedbackEntry]) -> Self {
+        let mut queries: Vec<&str> = Vec::new();
+        let mut latest: HashMap<(&str, i64), Feedback> = HashMap::new();
+        for entry in entries {
+            if !queries.contains(&entry.query.as_str()) {
+                queries.push(&entry.query);
+            }
+            latest.insert((entry.query.as_str(), entry.memory_id), entry.feedback);
+        }
+        let cases = queries
+            .into_iter()
+            .filter_map(|query| {
+                let mut expected_ids: Vec<i64> = latest
+                    .iter()
+                    .filter(|((q, _), f)| *q == query && **f == Feedback::Useful)
+                    .map(|((_, id), _)| *id)
+                    .collect();
+                if expected_ids.is_empty() {
+                    return None;
+                }
+                expected_ids.sort_unstable();
+                Some(EvalCase {
+                    query: query.to_string(),
+                    expected_ids,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Self {
+            name: Some("feedback".to_string()),
+            k: DEFAULT_EVAL_K,
+            cases,
+        }
+    }
+
+    fn validate(&self) -> Result<(), EvalError> {
+        if self.k == 0 {
+            return Err(EvalError::Parse("k must be at least 1".to_string()));
+        }
+        for case in &self.cases {
+            if case.expected_ids.is_empty() && case.expected_contains.is_empty() {
+                return Err(EvalError::Parse(format!(
+                    "case {:?} needs expected_ids or expected_contains",
+                    case.label()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EvalCase {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.query)
+    }
+
+    /// Recall options for this case. Evaluation always runs in peek mode so
+    /// it never reinforces the memories it measures.
+    pub fn options(&self) -> RecallOptions {
+        RecallOptions {
+            tag: self.tag.clone(),
+            fusion: self.fusion,
+            peek: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Metrics for one case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseReport {
+    pub name: String,
+    pub query: String,
+    /// Ids of the top-k results, in rank order.
+    pub retrieved: Vec<i64>,
+    /// 1-based rank of the first relevant result within the top k.
+    pub first_relevant_rank: Option<usize>,
+    pub reciprocal_rank: f64,
+    pub ndcg: f64,
+    pub recall: f64,
+    /// Expectations no top-k result satisfied (`#id` or the snippet).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+/// Metrics for a whole suite: per-case results and their means.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+    pub k: usize,
+    pub mrr: f64,
+    pub ndcg: f64,
+    pub recall: f64,
+    pub cases: Vec<CaseReport>,
+}
+
+/// Change of each metric against a baseline report (positive = better).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EvalDiff {
+    pub mrr: f64,
+    pub ndcg: f64,
+    pub recall: f64,
+    /// Cases present in both reports whose metrics or rank changed.
+    pub changed: Vec<CaseDiff>,
+    /// Cases missing from the baseline.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    /// Baseline cases no longer in the suite.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CaseDiff {
+    pub name: String,
+    pub baseline_rank: Option<usize>,
+    pub rank: Option<usize>,
+    pub reciprocal_rank: f64,
+    pub ndcg: f64,
+    pub recall: f64,
+}
+
+impl EvalReport {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EvalError> {
+        serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| EvalError::Parse(e.to_string()))
+    }
+
+    /// Save as JSON, for use as a baseline in later runs.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EvalError> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| EvalError::Parse(e.to_string()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Compare against a baseline, matching cases by name.
+    pub fn diff(&self, baseline: &EvalReport) -> EvalDiff {
+        let before: HashMap<&str, &CaseReport> = baseline
+            .cases
+            .iter()
+            .map(|c| (c.name.as_str(), c))
+            .collect();
+        let mut changed = Vec::new();
+        let mut added = Vec::new();
+        for case in &self.cases {
+            let Some(old) = before.get(case.name.as_str()) else {
+                added.push(case.name.clone());
+                continue;
+            };
+            let diff = CaseDiff {
+                name: case.name.clone(),
+                baseline_rank: old.first_relevant_rank,
+                rank: case.first_relevant_rank,
+                reciprocal_rank: case.reciprocal_rank - old.reciprocal_rank,
+                ndcg: case.ndcg - old.ndcg,
+                recall: case.recall - old.recall,
+            };
+            let moved = diff.baseline_rank != diff.rank
+                || [diff.reciprocal_rank, diff.ndcg, diff.recall]
+                    .iter()
+                    .any(|d| d.abs() > 1e-9);
+            if moved {
+                changed.push(diff);
+            }
+        }
+        let current: Vec<&str> = self.cases.iter().map(|c| c.name.as_str()).collect();
+        let removed = baseline
+            .cases
+            .iter()
+            .filter(|c| !current.contains(&c.name.as_str()))
+            .map(|c| c.name.clone())
+            .collect();
+        EvalDiff {
+            mrr: self.mrr - baseline.mrr,
+            ndcg: self.ndcg - baseline.ndcg,
+            recall: self.recall - baseline.recall,
+            changed,
+            added,
+            removed,
+        }
+    }
+}
+
+/// Run every case through `recall` (called with the case and k) and score it.
+pub fn evaluate<E>(
+    suite: &EvalSuite,
+    mut recall: impl FnMut(&EvalCase, usize) -> Result<Vec<RecallResult>, E>,
+) -> Result<EvalReport, E> {
+    let mut cases = Vec::with_capacity(suite.cases.len());
+    for case in &suite.cases {
+        let results = recall(case, suite.k)?;
+        let ranked: Vec<(i64, String)> = results
+            .iter()
+            .map(|r| (r.memory.id, r.memory.text_for_embedding()))
+            .collect();
+        cases.push(score_case(case, suite.k, &ranked));
+    }
+    let mean = |metric: fn(&CaseReport) -> f64| {
+        if cases.is_empty() {
+            0.0
+        } else {
+            cases.iter().map(metric).sum::<f64>() / cases.len() as f64
+        }
+    };
+    Ok(EvalReport {
+        suite: suite.name.clone(),
+        k: suite.k,
+        mrr: mean(|c| c.reciprocal_rank),
+        ndcg: mean(|c| c.ndcg),
+        recall: mean(|c| c.recall),
+        cases,
+    })
+}
+
+/// Score one ranking of `(id, text)` pairs. Each expectation counts once,
+/// credited to the highest-ranked result that satisfies it.
+fn score_case(case: &EvalCase, k: usize, ranked: &[(i64, String)]) -> CaseReport {
+    let contains: Vec<String> = case
+        .expected_contains
+        .iter()
+        .map(|s| s.to_lowercase())
+        .collect();
+    let total = case.expected_ids.len() + contains.len();
+    let mut satisfied = vec![false; total];
+    let mut first_relevant_rank = None;
+    let mut dcg = 0.0;
+
+    for (i, (id, text)) in ranked.iter().take(k).enumerate() {
+        let text = text.to_lowercase();
+        let matches = |e: usize| match case.expected_ids.get(e) {
+            Some(expected) => expected == id,
+            None => text.contains(&contains[e - case.expected_ids.len()]),
+        };
+        if !(0..total).any(matches) {
+            continue;
+        }
+        first_relevant_rank.get_or_insert(i + 1);
+        if let Some(e) = (0..total).find(|&e| !satisfied[e] && matches(e)) {
+            satisfied[e] = true;
+            dcg += 1.0 / ((i + 2) as f64).log2();
+        }
+    }
+
+    let ideal: f64 = (0..total.min(k))
+        .map(|i| 1.0 / ((i + 2) as f64).log2())
+        .sum();
+    let found = satisfied.iter().filter(|s| **s).count();
+    let missing = (0..total)
+        .filter(|&e| !satisfied[e])
+        .map(|e| match case.expected_ids.get(e) {
+            Some(id) => format!("#{id}"),
+            None => format!("{:?}", case.expected_contains[e - case.expected_ids.len()]),
+        })
+        .collect();
+    CaseReport {
+        name: case.label().to_string(),
+        query: case.query.clone(),
+        retrieved: ranked.iter().take(k).map(|(id, _)| *id).collect(),
+        first_relevant_rank,
+        reciprocal_rank: first_relevant_rank.map_or(0.0, |r| 1.0 / r as f64),
+        ndcg: if ideal > 0.0 { dcg / ideal } else { 0.0 },
+        recall: if total > 0 {
+            found as f64 / total as f64
+        } else {
+            0.0
+        },
+        missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(ids: &[i64], contains: &[&str]) -> EvalCase {
+        EvalCase {
+            query: "q".to_string(),
+            expected_ids: ids.to_vec(),
+            expected_contains: contains.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ranking(items: &[(i64, &str)]) -> Vec<(i64, String)> {
+        items.iter().map(|(id, t)| (*id, t.to_string())).collect()
+    }
+
+    #[test]
+    fn perfect_ranking_scores_one() {
+        let report = score_case(&case(&[1, 2], &[]), 10, &ranking(&[(1, "a"), (2, "b")]));
+        assert_eq!(report.first_relevant_rank, Some(1));
+        assert!((report.reciprocal_rank - 1.0).abs() < 1e-12);
+        assert!((report.ndcg - 1.0).abs() < 1e-12);
+        assert!((report.recall - 1.0).abs() < 1e-12);
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn late_and_missing_hits_lower_the_metrics() {
+        let ranked = ranking(&[
+            (7, "noise"),
+            (3, "Rotated PLAT-4412 keys"),
+            (9, "more noise"),
+        ]);
+        let report = score_case(&case(&[5], &["plat-4412"]), 10, &ranked);
+        assert_eq!(report.first_relevant_rank, Some(2));
+        assert!((report.reciprocal_rank - 0.5).abs() < 1e-12);
+        assert!((report.recall - 0.5).abs() < 1e-12);
+        let expected_ndcg = (1.0 / 3f64.log2()) / (1.0 + 1.0 / 3f64.log2());
+        assert!((report.ndcg - expected_ndcg).abs() < 1e-12);
+        assert_eq!(report.missing, vec!["#5"]);
+    }
+
+    #[test]
+    fn hits_beyond_k_do_not_count() {
+        let ranked = ranking(&[(7, "noise"), (1, "hit")]);
+        let report = score_case(&case(&[1], &[]), 1, &ranked);
+        assert_eq!(report.first_relevant_rank, None);
+        assert_eq!(report.reciprocal_rank, 0.0);
+        assert_eq!(report.retrieved, vec![7]);
+    }
+
+    #[test]
+    fn suite_parses_yaml_and_rejects_cases_without_expectations() {
+        let suite = EvalSuite::from_yaml(
+            "name: ops\ncases:\n  - query: deploy keys\n    tag: ops\n    expected_contains: [PLAT-4412]\n",
+        )
+        .unwrap();
+        assert_eq!(suite.k, DEFAULT_EVAL_K);
+        assert_eq!(suite.cases[0].tag.as_deref(), Some("ops"));
+        assert!(suite.cases[0].options().peek);
+
+        let err = EvalSuite::from_yaml("cases:\n  - query: nothing expected\n").unwrap_err();
+        assert!(err.to_string().contains("nothing expected"));
+    }
+
+    #[test]
+    fn feedback_suite_expects_latest_useful_memories() {
+        let entry = |memory_id: i64, query: &str, feedback: Feedback| FeedbackEntry {
+            id: 0,
+            memory_id,
+            namespace: "default".to_string(),
+            query: query.to_string(),
+            feedback,
+            strength_before: 1.0,
+            strength_after: 1.0,
+            created_at: chrono::Utc::now(),
+        };
+        let suite = EvalSuite::from_feedback(&[
+            entry(1, "deploy", Feedback::Useful),
+            entry(2, "deploy", Feedback::Useful),
+            entry(2, "deploy", Feedback::Wrong),
+            entry(3, "lunch", Feedback::Irrelevant),
+        ]);
+        assert_eq!(suite.cases.len(), 1);
+        assert_eq!(suite.cases[0].query, "deploy");
+        assert_eq!(suite.cases[0].expected_ids, vec![1]);
+    }
+
+    #[test]
+    fn diff_reports_changed_cases_against_baseline() {
+        let suite = EvalSuite {
+            name: None,
+            k: 10,
+            cases: vec![case(&[1], &[])],
+        };
+        let baseline = evaluate(&suite, |_, _| Ok::<_, ()>(Vec::new())).unwrap();
+        let mut current = baseline.clone();
+        current.cases[0].first_relevant_rank = Some(1);
+        current.cases[0].reciprocal_rank = 1.0;
+        current.mrr = 1.0;
+
+        let diff = current.diff(&baseline);
+        assert!((diff.mrr - 1.0).abs() < 1e-12);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].baseline_rank, None);
+        assert_eq!(diff.changed[0].rank, Some(1));
+        assert!(baseline.diff(&baseline).changed.is_empty());
+    }
+}
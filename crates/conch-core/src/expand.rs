@@ -0,0 +1,327 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::memory::{MemoryKind, MemoryRecord};
+use crate::relations::normalize_relation;
+
+/// Relations whose subject and object name the same entity.
+const ALIAS_RELATIONS: &[&str] = &["alias", "aka", "also_known_as", "known_as", "nickname"];
+
+/// Upper bound on extra probes per query, so expansion stays cheap on dense graphs.
+pub const MAX_EXPANSION_PROBES: usize = 8;
+
+/// Words too common to count as an entity mention even if a fact uses them.
+pub(crate) const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "at", "be", "by", "do", "does", "for", "from", "has", "have", "he",
+    "her", "his", "i", "in", "is", "it", "its", "me", "my", "of", "on", "or", "our", "she", "that",
+    "the", "their", "them", "they", "this", "to", "was", "we", "what", "when", "where", "which",
+    "who", "why", "with", "you", "your",
+];
+
+/// Entities a query mentions, and the probe terms derived from them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryExpansion {
+    /// Entity names (as stored in facts) that the query mentions directly or via an alias.
+    pub entities: Vec<String>,
+    /// Extra search terms: the matched entities, their aliases and their 1-hop neighbours.
+    pub probes: Vec<String>,
+}
+
+impl QueryExpansion {
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+}
+
+/// Expand a query through the fact graph (research item 5.2 F).
+///
+/// Fact subjects and objects named in the query are matched case-insensitively
+/// on word boundaries. Facts with an alias relation ("JG aka Jared") fold names
+/// into one entity. The probes are every name of a matched entity plus the
+/// entities one fact away from it, so "What does Max eat?" probes "Max" and,
+/// through "Jared has_pet Max", "Jared".
+pub fn expand_query(query: &str, memories: &[(MemoryRecord, String)]) -> QueryExpansion {
+    expand_query_with_aliases(query, memories, &[])
+}
+
+/// Like [`expand_query`], with the entity registry's aliases as extra
+/// `(alias, canonical name)` pairs. An alias named in the query counts as a
+/// mention of its canonical entity.
+pub fn expand_query_with_aliases(
+    query: &str,
+    memories: &[(MemoryRecord, String)],
+    registry_aliases: &[(String, String)],
+) -> QueryExpansion {
+    let facts: Vec<(&str, &str, &str)> = memories
+        .iter()
+        .filter_map(|(mem, _)| match &mem.kind {
+            MemoryKind::Fact(f) => {
+                Some((f.subject.as_str(), f.relation.as_str(), f.object.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    if facts.is_empty() {
+        return QueryExpansion::default();
+    }
+
+    // Display name for every entity key, plus alias groups via union-find.
+    let mut names: HashMap<String, &str> = HashMap::new();
+    let mut aliases = AliasGroups::default();
+    for &(subject, relation, object) in &facts {
+        names.entry(entity_key(subject)).or_insert(subject);
+        names.entry(entity_key(object)).or_insert(object);
+        if is_alias_relation(relation) {
+            aliases.union(&entity_key(subject), &entity_key(object));
+        }
+    }
+    let mut canonical: HashMap<String, &str> = HashMap::new();
+    for (alias, name) in registry_aliases {
+        let (alias_key, name_key) = (entity_key(alias), entity_key(name));
+        names.entry(alias_key.clone()).or_insert(alias);
+        names.entry(name_key.clone()).or_insert(name);
+        aliases.union(&alias_key, &name_key);
+        canonical.insert(alias_key, name);
+    }
+
+    let query_tokens = tokenize(query);
+    let mut matched_keys: Vec<&String> = names
+        .keys()
+        .filter(|key| mentions(&query_tokens, key.as_str()))
+        .collect();
+    if matched_keys.is_empty() {
+        return QueryExpansion::default();
+    }
+    matched_keys.sort();
+
+    let matched_roots: HashSet<String> = matched_keys
+        .iter()
+        .map(|k| aliases.find(k.as_str()))
+        .collect();
+    let in_matched_group = |key: &str| matched_roots.contains(&aliases.find(key));
+
+    let mut probes = ProbeSet::default();
+    let mut group_keys: Vec<&String> = names
+        .keys()
+        .filter(|k| in_matched_group(k.as_str()))
+        .collect();
+    group_keys.sort();
+    for key in &group_keys {
+        probes.push(names[*key]);
+    }
+    for &(subject, relation, object) in &facts {
+        if is_alias_relation(relation) {
+            continue;
+        }
+        let (s_key, o_key) = (entity_key(subject), entity_key(object));
+        let s_in = in_matched_group(s_key.as_str());
+        let o_in = in_matched_group(o_key.as_str());
+        if s_in && !o_in {
+            probes.push(object);
+        } else if o_in && !s_in {
+            probes.push(subject);
+        }
+    }
+
+    let mut entities: Vec<String> = Vec::new();
+    for key in matched_keys {
+        let name = canonical.get(key).copied().unwrap_or(names[key]);
+        if !entities.iter().any(|e| e == name) {
+            entities.push(name.to_string());
+        }
+    }
+    QueryExpansion {
+        entities,
+        probes: probes.into_vec(),
+    }
+}
+
+/// True if `name` appears in the (tokenized) query as a whole word sequence,
+/// ignoring case and punctuation. Stopword-only names never match.
+pub(crate) fn mentions_name(query_tokens: &[String], name: &str) -> bool {
+    mentions(query_tokens, &entity_key(name))
+}
+
+fn entity_key(name: &str) -> String {
+    tokenize(name).join(" ")
+}
+
+fn is_alias_relation(relation: &str) -> bool {
+    ALIAS_RELATIONS.contains(&normalize_relation(relation).as_str())
+}
+
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// True if `key` (a space-joined token sequence) appears in the query on word boundaries.
+fn mentions(query_tokens: &[String], key: &str) -> bool {
+    let key_tokens: Vec<&str> = key.split(' ').filter(|t| !t.is_empty()).collect();
+    if key_tokens.is_empty()
+        || key_tokens
+            .iter()
+            .all(|t| t.len() < 2 || STOPWORDS.contains(t))
+    {
+        return false;
+    }
+    query_tokens
+        .windows(key_tokens.len())
+        .any(|window| window.iter().zip(&key_tokens).all(|(q, k)| q == k))
+}
+
+#[derive(Default)]
+struct AliasGroups {
+    parent: HashMap<String, String>,
+}
+
+impl AliasGroups {
+    fn find(&self, key: &str) -> String {
+        let mut current = key.to_string();
+        while let Some(parent) = self.parent.get(&current) {
+            if *parent == current {
+                break;
+            }
+            current = parent.clone();
+        }
+        current
+    }
+
+    fn union(&mut self, a: &str, b: &str) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent.insert(root_a, root_b);
+        }
+    }
+}
+
+/// Ordered, case-insensitively deduplicated probe list capped at [`MAX_EXPANSION_PROBES`].
+#[derive(Default)]
+struct ProbeSet {
+    seen: HashSet<String>,
+    probes: Vec<String>,
+}
+
+impl ProbeSet {
+    fn push(&mut self, term: &str) {
+        let key = entity_key(term);
+        if key.is_empty() || self.probes.len() >= MAX_EXPANSION_PROBES {
+            return;
+        }
+        if self.seen.insert(key) {
+            self.probes.push(term.to_string());
+        }
+    }
+
+    fn into_vec(self) -> Vec<String> {
+        self.probes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Episode, Fact};
+    use chrono::Utc;
+
+    fn record(id: i64, kind: MemoryKind) -> (MemoryRecord, String) {
+        let mem = MemoryRecord {
+            id,
+            kind,
+            strength: 1.0,
+            embedding: None,
+            created_at: Utc::now(),
+            last_accessed_at: Utc::now(),
+            access_count: 0,
+            tags: vec![],
+            source: None,
+            session_id: None,
+            channel: None,
+            importance: 0.5,
+            namespace: "default".to_string(),
+            checksum: None,
+            temporal: None,
+            language: None,
+        };
+        let text = mem.text_for_embedding();
+        (mem, text)
+    }
+
+    fn fact(id: i64, subject: &str, relation: &str, object: &str) -> (MemoryRecord, String) {
+        record(
+            id,
+            MemoryKind::Fact(Fact {
+                subject: subject.to_string(),
+                relation: relation.to_string(),
+                object: object.to_string(),
+            }),
+        )
+    }
+
+    #[test]
+    fn matches_object_and_adds_neighbour() {
+        let memories = vec![
+            fact(1, "Jared", "has_pet", "Max"),
+            fact(2, "Jared", "works_at", "Microsoft"),
+        ];
+        let expansion = expand_query("What does Max eat?", &memories);
+        assert_eq!(expansion.entities, vec!["Max"]);
+        assert_eq!(expansion.probes, vec!["Max", "Jared"]);
+    }
+
+    #[test]
+    fn matching_is_case_insensitive_and_word_bounded() {
+        let memories = vec![fact(1, "Max", "is_a", "dog")];
+        assert_eq!(
+            expand_query("how is MAX's diet", &memories).entities,
+            vec!["Max"]
+        );
+        assert!(expand_query("maximum throughput", &memories).is_empty());
+    }
+
+    #[test]
+    fn aliases_fold_into_one_entity() {
+        let memories = vec![
+            fact(1, "JG", "aka", "Jared"),
+            fact(2, "Jared", "lives_in", "Austin"),
+        ];
+        let expansion = expand_query("where does JG live", &memories);
+        assert!(expansion.probes.contains(&"Jared".to_string()));
+        assert!(expansion.probes.contains(&"Austin".to_string()));
+    }
+
+    #[test]
+    fn registry_aliases_resolve_to_the_canonical_entity() {
+        let memories = vec![fact(1, "Jared", "lives_in", "Austin")];
+        let aliases = vec![("Jared Grimes".to_string(), "Jared".to_string())];
+        let expansion =
+            expand_query_with_aliases("where does jared grimes live", &memories, &aliases);
+        assert_eq!(expansion.entities, vec!["Jared"]);
+        assert!(expansion.probes.contains(&"Jared".to_string()));
+        assert!(expansion.probes.contains(&"Austin".to_string()));
+    }
+
+    #[test]
+    fn ignores_stopword_entities_and_non_facts() {
+        let memories = vec![
+            fact(1, "it", "is", "late"),
+            record(
+                2,
+                MemoryKind::Episode(Episode {
+                    text: "Max went to the vet".to_string(),
+                }),
+            ),
+        ];
+        assert!(expand_query("is it Max", &memories).is_empty());
+    }
+
+    #[test]
+    fn probes_are_capped() {
+        let memories: Vec<_> = (0..20)
+            .map(|i| fact(i, "Hub", "links", &format!("Spoke{i}")))
+            .collect();
+        let expansion = expand_query("tell me about hub", &memories);
+        assert_eq!(expansion.probes.len(), MAX_EXPANSION_PROBES);
+    }
+}
@@ -0,0 +1,439 @@
+use serde::Serialize;
+
+use crate::expand::STOPWORDS;
+use crate::memory::{MemoryKind, MemoryRecord};
+use crate::summarize::split_sentences;
+
+/// Source recorded on facts created by extraction.
+pub const EXTRACTION_SOURCE: &str = "extraction";
+
+/// Longest subject or object name, in words.
+pub(crate) const MAX_NAME_WORDS: usize = 4;
+
+/// Longest free-form object (e.g. "dark mode"), in words.
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Capitalized words that are not part of a name: pronouns, and adverbs
+/// that often open a sentence.
+const NOT_NAMES: &[&str] = &[
+    "i",
+    "he",
+    "she",
+    "they",
+    "we",
+    "it",
+    "you",
+    "this",
+    "that",
+    "there",
+    "who",
+    "yesterday",
+    "today",
+    "tonight",
+    "tomorrow",
+    "later",
+    "earlier",
+    "recently",
+    "finally",
+    "meanwhile",
+    "apparently",
+    "now",
+    "then",
+    "also",
+];
+
+/// What may follow a rule's cue as the object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectShape {
+    /// A capitalized name, e.g. "Microsoft" or "New York".
+    Name,
+    /// A short run of content words, e.g. "dark mode".
+    Phrase,
+}
+
+/// A pattern rule: `<Name> <cue> <object>` yields `(Name, relation, object)`.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtractionRule {
+    pub name: &'static str,
+    pub relation: &'static str,
+    /// Lowercase word sequences linking the subject to the object.
+    pub cues: &'static [&'static str],
+    pub object: ObjectShape,
+    /// Initial strength of facts this rule extracts. Below 1.0, so an
+    /// extracted fact ranks under a stated one until it is reinforced.
+    pub confidence: f64,
+}
+
+/// Built-in extraction rules.
+pub const RULES: &[ExtractionRule] = &[
+    ExtractionRule {
+        name: "employment",
+        relation: "works_at",
+        cues: &[
+            "works at",
+            "works for",
+            "started working at",
+            "started working for",
+            "is employed by",
+            "joined",
+        ],
+        object: ObjectShape::Name,
+        confidence: 0.6,
+    },
+    ExtractionRule {
+        name: "residence",
+        relation: "lives_in",
+        cues: &["lives in", "moved to", "relocated to", "is based in"],
+        object: ObjectShape::Name,
+        confidence: 0.6,
+    },
+    ExtractionRule {
+        name: "birthplace",
+        relation: "born_in",
+        cues: &["was born in"],
+        object: ObjectShape::Name,
+        confidence: 0.7,
+    },
+    ExtractionRule {
+        name: "reporting",
+        relation: "reports_to",
+        cues: &["reports to"],
+        object: ObjectShape::Name,
+        confidence: 0.6,
+    },
+    ExtractionRule {
+        name: "management",
+        relation: "manages",
+        cues: &["manages", "leads"],
+        object: ObjectShape::Name,
+        confidence: 0.5,
+    },
+    ExtractionRule {
+        name: "founding",
+        relation: "founded",
+        cues: &["founded", "co-founded"],
+        object: ObjectShape::Name,
+        confidence: 0.6,
+    },
+    ExtractionRule {
+        name: "marriage",
+        relation: "married_to",
+        cues: &["is married to", "married"],
+        object: ObjectShape::Name,
+        confidence: 0.6,
+    },
+    ExtractionRule {
+        name: "preference",
+        relation: "prefers",
+        cues: &["prefers", "likes", "loves", "enjoys"],
+        object: ObjectShape::Phrase,
+        confidence: 0.5,
+    },
+    ExtractionRule {
+        name: "usage",
+        relation: "uses",
+        cues: &["uses", "is using", "switched to", "migrated to"],
+        object: ObjectShape::Phrase,
+        confidence: 0.5,
+    },
+];
+
+/// A candidate triple pulled from text by an [`ExtractionRule`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtractedFact {
+    pub subject: String,
+    pub relation: String,
+    pub object: String,
+    /// Name of the rule that matched.
+    pub rule: &'static str,
+    pub confidence: f64,
+}
+
+/// A candidate fact and the memory it was extracted from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionCandidate {
+    pub source_id: i64,
+    #[serde(flatten)]
+    pub fact: ExtractedFact,
+}
+
+/// Outcome of an extraction pass.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExtractReport {
+    /// Episodes and actions examined.
+    pub scanned: usize,
+    /// Every candidate found, whether new or already known.
+    pub candidates: Vec<ExtractionCandidate>,
+    /// New facts stored.
+    pub created: usize,
+    /// Candidates matching an existing fact, which was linked to the source
+    /// instead.
+    pub linked: usize,
+}
+
+impl ExtractionRule {
+    /// Apply this rule to one sentence.
+    pub fn apply(&self, sentence: &str) -> Vec<ExtractedFact> {
+        let words = words(sentence);
+        let mut facts = Vec::new();
+        for cue in self.cues {
+            let cue: Vec<&str> = cue.split(' ').collect();
+            for start in 1..words.len() {
+                let end = start + cue.len();
+                if end > words.len()
+                    || words[start..end]
+                        .iter()
+                        .zip(&cue)
+                        .any(|(w, c)| w.lower != *c)
+                    // A clause break inside the cue ("moved, to") breaks it.
+                    || words[start..end - 1].iter().any(|w| w.ends_clause)
+                {
+                    continue;
+                }
+                let Some(subject) = subject_before(&words, start) else {
+                    continue;
+                };
+                let object = match self.object {
+                    ObjectShape::Name => name_after(&words, end),
+                    ObjectShape::Phrase => phrase_after(&words, end),
+                };
+                if let Some(object) = object {
+                    facts.push(ExtractedFact {
+                        subject,
+                        relation: self.relation.to_string(),
+                        object,
+                        rule: self.name,
+                        confidence: self.confidence,
+                    });
+                }
+            }
+        }
+        facts
+    }
+}
+
+/// Candidate facts in `text` from every rule in [`RULES`], without duplicates.
+pub fn extract_facts(text: &str) -> Vec<ExtractedFact> {
+    let mut facts: Vec<ExtractedFact> = Vec::new();
+    for sentence in split_sentences(text) {
+        for rule in RULES {
+            for fact in rule.apply(&sentence) {
+                let seen = facts.iter().any(|f| {
+                    f.relation == fact.relation
+                        && f.subject.eq_ignore_ascii_case(&fact.subject)
+                        && f.object.eq_ignore_ascii_case(&fact.object)
+                });
+                if !seen {
+                    facts.push(fact);
+                }
+            }
+        }
+    }
+    facts
+}
+
+/// Candidate facts in an episode or action. Other kinds yield none.
+pub fn extract_from_memory(mem: &MemoryRecord) -> Vec<ExtractedFact> {
+    match &mem.kind {
+        MemoryKind::Episode(e) => extract_facts(&e.text),
+        MemoryKind::Action(a) => extract_facts(&a.text),
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) struct Word {
+    pub(crate) text: String,
+    pub(crate) lower: String,
+    /// Followed by a comma, semicolon or colon.
+    pub(crate) ends_clause: bool,
+}
+
+impl Word {
+    pub(crate) fn is_name(&self) -> bool {
+        self.text.chars().next().is_some_and(char::is_uppercase)
+            && !NOT_NAMES.contains(&self.lower.as_str())
+            && !STOPWORDS.contains(&self.lower.as_str())
+    }
+
+    fn is_content(&self) -> bool {
+        self.lower.chars().any(char::is_alphanumeric) && !STOPWORDS.contains(&self.lower.as_str())
+    }
+}
+
+/// Words with surrounding punctuation stripped; inner punctuation
+/// ("co-founded", "Node.js") is kept.
+pub(crate) fn words(sentence: &str) -> Vec<Word> {
+    sentence
+        .split_whitespace()
+        .filter_map(|raw| {
+            let text = raw.trim_matches(|c: char| !c.is_alphanumeric());
+            (!text.is_empty()).then(|| Word {
+                text: text.to_string(),
+                lower: text.to_lowercase(),
+                ends_clause: raw
+                    .trim_end_matches(['"', '\'', ')'])
+                    .ends_with([',', ';', ':']),
+            })
+        })
+        .collect()
+}
+
+/// The capitalized name ending right before the cue at `cue_start`.
+fn subject_before(words: &[Word], cue_start: usize) -> Option<String> {
+    let mut start = cue_start;
+    while start > 0
+        && cue_start - start < MAX_NAME_WORDS
+        && words[start - 1].is_name()
+        && (start == cue_start || !words[start - 1].ends_clause)
+    {
+        start -= 1;
+    }
+    join(&words[start..cue_start])
+}
+
+/// The capitalized name right after the cue, skipping an article.
+fn name_after(words: &[Word], from: usize) -> Option<String> {
+    let from = skip_article(words, from);
+    let mut end = from;
+    while end < words.len() && end - from < MAX_NAME_WORDS && words[end].is_name() {
+        end += 1;
+        if words[end - 1].ends_clause {
+            break;
+        }
+    }
+    join(&words[from..end])
+}
+
+/// Content words right after the cue, up to the next stopword or clause break.
+fn phrase_after(words: &[Word], from: usize) -> Option<String> {
+    let from = skip_article(words, from);
+    let mut end = from;
+    while end < words.len() && end - from < MAX_PHRASE_WORDS && words[end].is_content() {
+        end += 1;
+        if words[end - 1].ends_clause {
+            break;
+        }
+    }
+    join(&words[from..end])
+}
+
+fn skip_article(words: &[Word], from: usize) -> usize {
+    match words.get(from) {
+        Some(w) if matches!(w.lower.as_str(), "a" | "an" | "the") => from + 1,
+        _ => from,
+    }
+}
+
+fn join(words: &[Word]) -> Option<String> {
+    (!words.is_empty()).then(|| {
+        words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str) -> &'static ExtractionRule {
+        RULES.iter().find(|r| r.name == name).unwrap()
+    }
+
+    fn triples(rule_name: &str, sentence: &str) -> Vec<(String, String, String)> {
+        rule(rule_name)
+            .apply(sentence)
+            .into_iter()
+            .map(|f| (f.subject, f.relation, f.object))
+            .collect()
+    }
+
+    fn triple(s: &str, r: &str, o: &str) -> (String, String, String) {
+        (s.to_string(), r.to_string(), o.to_string())
+    }
+
+    #[test]
+    fn employment_rule() {
+        assert_eq!(
+            triples(
+                "employment",
+                "Jared started working at Microsoft last month"
+            ),
+            vec![triple("Jared", "works_at", "Microsoft")]
+        );
+        assert_eq!(
+            triples("employment", "Ana Lima joined the Platform Team."),
+            vec![triple("Ana Lima", "works_at", "Platform Team")]
+        );
+        assert!(triples("employment", "Jared no longer works at Microsoft").is_empty());
+    }
+
+    #[test]
+    fn residence_and_birthplace_rules() {
+        assert_eq!(
+            triples("residence", "Last week Priya moved to New York, finally"),
+            vec![triple("Priya", "lives_in", "New York")]
+        );
+        assert_eq!(
+            triples("birthplace", "Tomas was born in Lisbon"),
+            vec![triple("Tomas", "born_in", "Lisbon")]
+        );
+    }
+
+    #[test]
+    fn organisation_rules() {
+        assert_eq!(
+            triples("reporting", "Sam reports to Dana"),
+            vec![triple("Sam", "reports_to", "Dana")]
+        );
+        assert_eq!(
+            triples("management", "Dana leads Infra"),
+            vec![triple("Dana", "manages", "Infra")]
+        );
+        assert_eq!(
+            triples("founding", "Lee co-founded Acme Labs in 2019"),
+            vec![triple("Lee", "founded", "Acme Labs")]
+        );
+        assert_eq!(
+            triples("marriage", "Ana is married to Rui"),
+            vec![triple("Ana", "married_to", "Rui")]
+        );
+    }
+
+    #[test]
+    fn phrase_object_rules() {
+        assert_eq!(
+            triples("preference", "Jared prefers dark mode in every editor"),
+            vec![triple("Jared", "prefers", "dark mode")]
+        );
+        assert_eq!(
+            triples(
+                "usage",
+                "Billing switched to Postgres 16; the rollout is done"
+            ),
+            vec![triple("Billing", "uses", "Postgres 16")]
+        );
+        assert!(triples("preference", "He likes pizza").is_empty());
+    }
+
+    #[test]
+    fn extract_facts_covers_every_sentence_once() {
+        let facts = extract_facts(
+            "Jared joined Microsoft. Jared joined Microsoft! Later Jared moved to Seattle.",
+        );
+        let found: Vec<(&str, &str, &str)> = facts
+            .iter()
+            .map(|f| (f.subject.as_str(), f.relation.as_str(), f.object.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Jared", "works_at", "Microsoft"),
+                ("Jared", "lives_in", "Seattle")
+            ]
+        );
+        assert!(facts.iter().all(|f| f.confidence < 1.0));
+    }
+}
@@ -142,6 +142,7 @@ mod tests {
             namespace: "default".to_string(),
             checksum: None,
             temporal: None,
+            language: None,
         }
     }
 
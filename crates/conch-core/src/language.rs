@@ -0,0 +1,129 @@
+use std::sync::OnceLock;
+
+use whatlang::{Detector, Lang};
+
+/// Language assumed for memories whose language could not be detected.
+pub const DEFAULT_LANGUAGE: &str = "eng";
+
+/// Languages with BM25 stemming and stop words, as ISO 639-3 codes with their
+/// ISO 639-1 codes and English names.
+const SUPPORTED: &[(&str, &str, &str, Lang, bm25::Language)] = &[
+    ("ara", "ar", "arabic", Lang::Ara, bm25::Language::Arabic),
+    ("dan", "da", "danish", Lang::Dan, bm25::Language::Danish),
+    ("nld", "nl", "dutch", Lang::Nld, bm25::Language::Dutch),
+    ("eng", "en", "english", Lang::Eng, bm25::Language::English),
+    ("fra", "fr", "french", Lang::Fra, bm25::Language::French),
+    ("deu", "de", "german", Lang::Deu, bm25::Language::German),
+    ("ell", "el", "greek", Lang::Ell, bm25::Language::Greek),
+    (
+        "hun",
+        "hu",
+        "hungarian",
+        Lang::Hun,
+        bm25::Language::Hungarian,
+    ),
+    ("ita", "it", "italian", Lang::Ita, bm25::Language::Italian),
+    (
+        "nob",
+        "no",
+        "norwegian",
+        Lang::Nob,
+        bm25::Language::Norwegian,
+    ),
+    (
+        "por",
+        "pt",
+        "portuguese",
+        Lang::Por,
+        bm25::Language::Portuguese,
+    ),
+    ("ron", "ro", "romanian", Lang::Ron, bm25::Language::Romanian),
+    ("rus", "ru", "russian", Lang::Rus, bm25::Language::Russian),
+    ("spa", "es", "spanish", Lang::Spa, bm25::Language::Spanish),
+    ("swe", "sv", "swedish", Lang::Swe, bm25::Language::Swedish),
+    ("tam", "ta", "tamil", Lang::Tam, bm25::Language::Tamil),
+    ("tur", "tr", "turkish", Lang::Tur, bm25::Language::Turkish),
+];
+
+/// Texts shorter than this (in characters) are too short to classify.
+const MIN_DETECTION_CHARS: usize = 12;
+
+/// Minimum detector confidence to record a language. whatlang's own
+/// `is_reliable` cut-off rejects most single-sentence memories.
+const MIN_DETECTION_CONFIDENCE: f64 = 0.5;
+
+/// Detect the language of a memory's text.
+///
+/// Returns an ISO 639-3 code (e.g. `"deu"`) for languages conch can stem, or
+/// `None` when the text is too short or the detector is not confident. Callers
+/// fall back to the namespace's default language in that case.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    static DETECTOR: OnceLock<Detector> = OnceLock::new();
+    if text.trim().chars().count() < MIN_DETECTION_CHARS {
+        return None;
+    }
+    let detector = DETECTOR
+        .get_or_init(|| Detector::with_allowlist(SUPPORTED.iter().map(|entry| entry.3).collect()));
+    let info = detector.detect(text)?;
+    if info.confidence() < MIN_DETECTION_CONFIDENCE {
+        return None;
+    }
+    SUPPORTED
+        .iter()
+        .find(|entry| entry.3 == info.lang())
+        .map(|entry| entry.0)
+}
+
+/// Normalise a user-supplied language (`"de"`, `"deu"` or `"German"`) to the
+/// ISO 639-3 code stored in the database. Returns `None` if unsupported.
+pub fn normalize_language(language: &str) -> Option<&'static str> {
+    let language = language.trim().to_ascii_lowercase();
+    SUPPORTED
+        .iter()
+        .find(|entry| entry.0 == language || entry.1 == language || entry.2 == language)
+        .map(|entry| entry.0)
+}
+
+/// The BM25 tokenizer language (stemmer and stop words) for a stored code.
+pub(crate) fn bm25_language(code: &str) -> bm25::Language {
+    SUPPORTED
+        .iter()
+        .find(|entry| entry.0 == code)
+        .map(|entry| entry.4.clone())
+        .unwrap_or(bm25::Language::English)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_common_languages() {
+        assert_eq!(
+            detect_language("The deployment finished and the service restarted cleanly."),
+            Some("eng")
+        );
+        assert_eq!(
+            detect_language("Die Bereitstellung wurde abgeschlossen und der Dienst neu gestartet."),
+            Some("deu")
+        );
+        assert_eq!(
+            detect_language("El despliegue terminó y el servicio se reinició sin problemas."),
+            Some("spa")
+        );
+    }
+
+    #[test]
+    fn short_text_is_undetected() {
+        assert_eq!(detect_language("ok"), None);
+        assert_eq!(detect_language("   "), None);
+    }
+
+    #[test]
+    fn normalizes_codes_and_names() {
+        assert_eq!(normalize_language("de"), Some("deu"));
+        assert_eq!(normalize_language("DEU"), Some("deu"));
+        assert_eq!(normalize_language("Spanish"), Some("spa"));
+        assert_eq!(normalize_language("klingon"), None);
+    }
+}
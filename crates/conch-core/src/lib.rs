@@ -1,17 +1,42 @@
 pub mod consolidate;
+pub mod context;
 pub mod decay;
 pub mod embed;
+pub mod eval;
+pub mod expand;
+pub mod extract;
 pub mod importance;
 pub mod isomorphic;
+pub mod language;
+mod lsh;
 pub mod memory;
+pub mod mentions;
+pub mod policy;
 pub mod recall;
+pub mod relations;
+pub mod rerank;
+pub mod schema;
+pub mod session;
 pub mod store;
+pub mod summarize;
 pub mod temporal;
+pub mod topics;
+pub mod tune;
 pub mod validate;
 
-pub use consolidate::{consolidate, find_clusters, ConsolidateCluster, ConsolidateResult};
+pub use consolidate::{
+    can_merge, consolidate, find_clusters, ConsolidateCluster, ConsolidateError, ConsolidateMode,
+    ConsolidateOptions, ConsolidateProgress, ConsolidateResult, ConsolidateStage, PairSimilarity,
+    CONSOLIDATION_THRESHOLD, EXACT_COMPARISON_LIMIT,
+};
+pub use context::{estimate_tokens, ContextBlock, ContextFormat};
 pub use decay::{run_decay, DecayResult};
 pub use embed::{cosine_similarity, EmbedError, Embedder, FastEmbedder, SharedEmbedder};
+pub use eval::{
+    CaseDiff, CaseReport, EvalCase, EvalDiff, EvalError, EvalReport, EvalSuite, DEFAULT_EVAL_K,
+};
+pub use expand::{expand_query, expand_query_with_aliases, QueryExpansion};
+pub use extract::{ExtractReport, ExtractedFact, ExtractionCandidate, ExtractionRule};
 pub use importance::{
     compute_importance, list_importance, score_all as score_importance, ImportanceInfo,
 };
@@ -19,20 +44,37 @@ pub use isomorphic::{
     isomorphic_recall, IsomorphicRecallResult, IsomorphicResult, RetrievalSource,
     DEFAULT_MYCELIUM_URL,
 };
+pub use language::{detect_language, normalize_language, DEFAULT_LANGUAGE};
 pub use memory::{
-    Action, AuditEntry, AuditIntegrityResult, CorruptedMemory, Episode, ExportData, Fact,
-    GraphNode, Intent, MemoryKind, MemoryRecord, MemoryStats, OperationWriteRetryStats,
-    ProvenanceInfo, RememberResult, TamperedAuditEntry, TemporalMetadata, VerifyResult,
-    WriteRetryStats,
+    Action, Association, AuditEntry, AuditIntegrityResult, CoRecallLink, CorruptedMemory,
+    EmbedFailure, EmbedProgress, EmbedReport, Entity, Episode, ExportData, Fact, FactView,
+    Feedback, FeedbackEntry, GraphNode, Intent, LineageLink, LineageRelation, MemoryKind,
+    MemoryRecord, MemoryStats, OperationWriteRetryStats, ProvenanceInfo, RelationUsage,
+    RememberResult, TamperedAuditEntry, TemporalMetadata, Topic, VerifyResult, WriteRetryStats,
+    NEEDS_REVIEW_TAG,
 };
+pub use policy::NamespacePolicy;
 pub use recall::{
-    recall, recall_with_tag_filter, RecallError, RecallResult, RecallScoreCoefficients,
-    RecallScoreExplain,
+    recall, recall_with_options, recall_with_tag_filter, ContextBoost, ContextWeights,
+    FusionStrategy, RecallContext, RecallError, RecallOptions, RecallResult,
+    RecallScoreCoefficients, RecallScoreExplain, DEFAULT_DIVERSITY_LAMBDA, DEFAULT_SPREAD_HOPS,
 };
+pub use relations::{canonical_relation, normalize_relation, BUILTIN_SYNONYMS};
+pub use rerank::{FastReranker, Reranker};
+pub use schema::{Cardinality, ObjectType, RelationSchema};
+pub use session::{SessionState, SESSION_IDLE_TIMEOUT_MINUTES};
 pub use store::MemoryStore;
+pub use summarize::{ExtractiveSummarizer, Summarizer};
+pub use topics::{default_topic_count, MAX_TOPICS};
+pub use tune::{TuneMetric, TuneParams, TuneResult};
 pub use validate::{ValidationConfig, ValidationEngine, ValidationResult, Violation};
 
 use chrono::Duration;
+use std::sync::OnceLock;
+
+/// Memories recalled as candidates for a context block before the budget
+/// decides how many are rendered.
+const CONTEXT_CANDIDATES: usize = 40;
 
 /// High-level API wrapping storage + embeddings.
 pub struct ConchDB {
@@ -40,6 +82,10 @@ pub struct ConchDB {
     embedder: Box<dyn Embedder>,
     namespace: String,
     validation_config: Option<ValidationConfig>,
+    /// Cross-encoder for the rerank stage, loaded on first use.
+    reranker: OnceLock<Box<dyn Reranker>>,
+    /// Builds summary memories in summarizing consolidation.
+    summarizer: Box<dyn Summarizer>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -54,6 +100,15 @@ pub enum ConchError {
     ValidationError { violations: String },
 }
 
+impl From<ConsolidateError> for ConchError {
+    fn from(e: ConsolidateError) -> Self {
+        match e {
+            ConsolidateError::Db(e) => ConchError::Db(e),
+            ConsolidateError::Summarize(e) => ConchError::Embed(e),
+        }
+    }
+}
+
 impl ConchDB {
     pub fn open(path: &str) -> Result<Self, ConchError> {
         Self::open_with_namespace(path, "default")
@@ -61,12 +116,14 @@ impl ConchDB {
 
     pub fn open_with_namespace(path: &str, namespace: &str) -> Result<Self, ConchError> {
         let store = MemoryStore::open(path)?;
-        let embedder = embed::FastEmbedder::new()?;
+        let embedder = embed::FastEmbedder::from_env()?;
         Ok(Self {
             store,
             embedder: Box::new(embedder),
             namespace: namespace.to_string(),
             validation_config: None,
+            reranker: OnceLock::new(),
+            summarizer: Box::new(ExtractiveSummarizer::default()),
         })
     }
 
@@ -82,6 +139,8 @@ impl ConchDB {
             embedder,
             namespace: namespace.to_string(),
             validation_config: None,
+            reranker: OnceLock::new(),
+            summarizer: Box::new(ExtractiveSummarizer::default()),
         })
     }
 
@@ -99,6 +158,8 @@ impl ConchDB {
             embedder,
             namespace: namespace.to_string(),
             validation_config: None,
+            reranker: OnceLock::new(),
+            summarizer: Box::new(ExtractiveSummarizer::default()),
         })
     }
 
@@ -128,6 +189,38 @@ impl ConchDB {
         Ok(())
     }
 
+    /// Use a custom reranker for the rerank stage instead of the default local model.
+    pub fn set_reranker(&mut self, reranker: Box<dyn Reranker>) {
+        self.reranker = OnceLock::from(reranker);
+    }
+
+    /// Use a custom summarizer (e.g. a language model) for summarizing
+    /// consolidation instead of the extractive default.
+    pub fn set_summarizer(&mut self, summarizer: Box<dyn Summarizer>) {
+        self.summarizer = summarizer;
+    }
+
+    fn reranker(&self) -> Result<&dyn Reranker, ConchError> {
+        if self.reranker.get().is_none() {
+            let model = FastReranker::new()?;
+            let _ = self.reranker.set(Box::new(model));
+        }
+        Ok(self
+            .reranker
+            .get()
+            .expect("reranker initialised above")
+            .as_ref())
+    }
+
+    /// Recall policy for the current namespace.
+    pub fn policy(&self) -> Result<NamespacePolicy, ConchError> {
+        Ok(self.store.namespace_policy(&self.namespace)?)
+    }
+
+    pub fn set_policy(&self, policy: &NamespacePolicy) -> Result<(), ConchError> {
+        Ok(self.store.set_namespace_policy(&self.namespace, policy)?)
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -165,18 +258,22 @@ impl ConchDB {
         session_id: Option<&str>,
         channel: Option<&str>,
     ) -> Result<MemoryRecord, ConchError> {
+        let relation = self.canonical_relation(relation)?;
+        let schema = self.checked_schema(&relation, object)?;
+        let (subject, object) = self.register_fact_entities(subject, object, schema.as_ref())?;
         let text = format!("{subject} {relation} {object}");
         let embedding = self.embedder.embed_one(&text)?;
-        let id = self.store.remember_fact_ns(
-            subject,
-            relation,
-            object,
-            Some(&embedding),
+        let (id, _) = self.write_fact(
+            &subject,
+            &relation,
+            &object,
+            schema.as_ref(),
+            false,
+            &embedding,
             tags,
             source,
             session_id,
             channel,
-            &self.namespace,
         )?;
         Ok(self.store.get_memory(id)?.expect("just inserted"))
     }
@@ -200,18 +297,22 @@ impl ConchDB {
         object: &str,
         tags: &[String],
     ) -> Result<(MemoryRecord, bool), ConchError> {
+        let relation = self.canonical_relation(relation)?;
+        let schema = self.checked_schema(&relation, object)?;
+        let (subject, object) = self.register_fact_entities(subject, object, schema.as_ref())?;
         let text = format!("{subject} {relation} {object}");
         let embedding = self.embedder.embed_one(&text)?;
-        let (id, was_updated) = self.store.upsert_fact_ns(
-            subject,
-            relation,
-            object,
-            Some(&embedding),
+        let (id, was_updated) = self.write_fact(
+            &subject,
+            &relation,
+            &object,
+            schema.as_ref(),
+            true,
+            &embedding,
             tags,
             None,
             None,
             None,
-            &self.namespace,
         )?;
         Ok((
             self.store.get_memory(id)?.expect("just upserted"),
@@ -249,7 +350,10 @@ impl ConchDB {
             channel,
             &self.namespace,
         )?;
-        Ok(self.store.get_memory(id)?.expect("just inserted"))
+        let record = self.store.get_memory(id)?.expect("just inserted");
+        self.index_mentions(&record)?;
+        self.extract_on_write(&record)?;
+        Ok(record)
     }
 
     pub fn remember_action(&self, text: &str) -> Result<MemoryRecord, ConchError> {
@@ -282,7 +386,10 @@ impl ConchDB {
             channel,
             &self.namespace,
         )?;
-        Ok(self.store.get_memory(id)?.expect("just inserted"))
+        let record = self.store.get_memory(id)?.expect("just inserted");
+        self.index_mentions(&record)?;
+        self.extract_on_write(&record)?;
+        Ok(record)
     }
 
     pub fn remember_intent(&self, text: &str) -> Result<MemoryRecord, ConchError> {
@@ -315,7 +422,9 @@ impl ConchDB {
             channel,
             &self.namespace,
         )?;
-        Ok(self.store.get_memory(id)?.expect("just inserted"))
+        let record = self.store.get_memory(id)?.expect("just inserted");
+        self.index_mentions(&record)?;
+        Ok(record)
     }
 
     // ── Dedup-aware remember ──────────────────────────────────
@@ -392,8 +501,11 @@ impl ConchDB {
         session_id: Option<&str>,
         channel: Option<&str>,
     ) -> Result<RememberResult, ConchError> {
+        self.run_validation(&format!("{subject} {relation} {object}"))?;
+        let relation = self.canonical_relation(relation)?;
+        let schema = self.checked_schema(&relation, object)?;
+        let (subject, object) = self.register_fact_entities(subject, object, schema.as_ref())?;
         let text = format!("{subject} {relation} {object}");
-        self.run_validation(&text)?;
 
         // Fast path: exact-content dedup by checksum avoids embedding generation and
         // full similarity scans for replayed writes/retries.
@@ -417,16 +529,18 @@ impl ConchDB {
         let embedding = self.embedder.embed_one(&text)?;
 
         // Step 1: Upsert — check for existing fact with same subject+relation
-        let (id, was_updated) = self.store.upsert_fact_ns(
-            subject,
-            relation,
-            object,
-            Some(&embedding),
+        // (unless the relation's schema declares it multi-valued)
+        let (id, was_updated) = self.write_fact(
+            &subject,
+            &relation,
+            &object,
+            schema.as_ref(),
+            true,
+            &embedding,
             tags,
             source,
             session_id,
             channel,
-            &self.namespace,
         )?;
         if was_updated {
             let record = self.store.get_memory(id)?.expect("just upserted");
@@ -524,6 +638,8 @@ impl ConchDB {
             &self.namespace,
         )?;
         let record = self.store.get_memory(id)?.expect("just inserted");
+        self.index_mentions(&record)?;
+        self.extract_on_write(&record)?;
         Ok(RememberResult::Created(record))
     }
 
@@ -537,13 +653,72 @@ impl ConchDB {
         limit: usize,
         tag: Option<&str>,
     ) -> Result<Vec<RecallResult>, ConchError> {
-        recall::recall_with_tag_filter_ns(
+        let options = RecallOptions {
+            tag: tag.map(str::to_string),
+            ..Default::default()
+        };
+        self.recall_with_options(query, limit, &options)
+    }
+
+    /// Run a golden query suite in peek mode and score the results.
+    pub fn evaluate(&self, suite: &EvalSuite) -> Result<EvalReport, ConchError> {
+        eval::evaluate(suite, |case, k| {
+            self.recall_with_options(&case.query, k, &case.options())
+        })
+    }
+
+    /// Fit the score exponents and fusion weights to a golden query suite by
+    /// coordinate search, starting from the namespace's current settings.
+    /// Does not change the policy; store `params` there to apply the result.
+    pub fn tune(&self, suite: &EvalSuite, metric: TuneMetric) -> Result<TuneResult, ConchError> {
+        let policy = self.policy()?;
+        let start = TuneParams {
+            coefficients: policy
+                .coefficients
+                .unwrap_or_else(recall::recall_score_coefficients_from_env),
+            fusion: policy.fusion.unwrap_or_default(),
+        };
+        tune::coordinate_search(start, metric, |params| {
+            eval::evaluate(suite, |case, k| {
+                let options = RecallOptions {
+                    coefficients: Some(params.coefficients),
+                    fusion: Some(case.fusion.unwrap_or(params.fusion)),
+                    ..case.options()
+                };
+                self.recall_with_options(&case.query, k, &options)
+            })
+        })
+    }
+
+    /// Recall with per-call options. Options left unset fall back to the
+    /// namespace policy.
+    pub fn recall_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &RecallOptions,
+    ) -> Result<Vec<RecallResult>, ConchError> {
+        let policy = self.store.namespace_policy(&self.namespace)?;
+        let reranker = if options.rerank.unwrap_or(policy.rerank) {
+            Some(self.reranker()?)
+        } else {
+            None
+        };
+        let options = RecallOptions {
+            fusion: options.fusion.or(policy.fusion),
+            language: options.language.clone().or(policy.language),
+            coefficients: options.coefficients.or(policy.coefficients),
+            context_weights: options.context_weights.or(policy.context_weights),
+            ..options.clone()
+        };
+        recall::recall_with_options(
             &self.store,
             query,
             self.embedder.as_ref(),
+            reranker,
             limit,
-            tag,
             &self.namespace,
+            &options,
         )
         .map_err(|e| match e {
             RecallError::Db(e) => ConchError::Db(e),
@@ -580,8 +755,10 @@ impl ConchDB {
         })
     }
 
+    /// Forget every fact about `subject`, or about the entity it is an alias of.
     pub fn forget_by_subject(&self, subject: &str) -> Result<usize, ConchError> {
-        Ok(self.store.forget_by_subject_ns(subject, &self.namespace)?)
+        let subject = self.canonical_entity(subject)?;
+        Ok(self.store.forget_by_subject_ns(&subject, &self.namespace)?)
     }
 
     pub fn forget_by_id(&self, id: &str) -> Result<usize, ConchError> {
@@ -616,56 +793,121 @@ impl ConchDB {
         Ok(self.store.write_retry_stats()?)
     }
 
+    /// Default number of memories embedded per batch (and per transaction).
+    pub const DEFAULT_EMBED_BATCH_SIZE: usize = 256;
+
+    /// Embed every memory in the current namespace that lacks an embedding.
+    /// Returns the number of memories embedded.
     pub fn embed_all(&self) -> Result<usize, ConchError> {
-        let missing = self.store.memories_missing_embeddings()?;
-        if missing.is_empty() {
-            return Ok(0);
+        Ok(self
+            .embed_all_batched(Self::DEFAULT_EMBED_BATCH_SIZE, |_| {})?
+            .embedded)
+    }
+
+    /// Embed missing embeddings in the current namespace, `batch_size` memories at a time.
+    ///
+    /// Each batch is written in its own transaction, so an interrupted run keeps
+    /// every completed batch and the next run picks up the remainder. Memories
+    /// that fail to embed are reported individually instead of aborting the pass.
+    pub fn embed_all_batched<F>(
+        &self,
+        batch_size: usize,
+        mut on_progress: F,
+    ) -> Result<EmbedReport, ConchError>
+    where
+        F: FnMut(&EmbedProgress),
+    {
+        if batch_size == 0 {
+            return Err(ConchError::InvalidArgument(
+                "batch size must be at least 1".to_string(),
+            ));
         }
-        let texts: Vec<String> = missing.iter().map(|m| m.text_for_embedding()).collect();
+        let total = self.store.count_missing_embeddings_ns(&self.namespace)?;
+        let mut report = EmbedReport::default();
+        let mut after_id = 0;
+        loop {
+            let batch = self.store.memories_missing_embeddings_page_ns(
+                &self.namespace,
+                after_id,
+                batch_size,
+            )?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            let embedded = self.embed_batch(&batch, &mut report.failures);
+            self.store.update_embeddings_batch(&embedded)?;
+            report.embedded += embedded.len();
+            report.batches += 1;
+            on_progress(&EmbedProgress {
+                processed: report.embedded + report.failures.len(),
+                total,
+                embedded: report.embedded,
+                failed: report.failures.len(),
+            });
+        }
+        Ok(report)
+    }
+
+    /// Embed one batch. If the batched call fails, fall back to embedding each
+    /// memory on its own so one bad row doesn't take its neighbours down with it.
+    fn embed_batch(
+        &self,
+        batch: &[MemoryRecord],
+        failures: &mut Vec<EmbedFailure>,
+    ) -> Vec<(i64, Vec<f32>)> {
+        let texts: Vec<String> = batch.iter().map(|m| m.text_for_embedding()).collect();
         let text_refs: Vec<&str> = texts.iter().map(|s| s.as_str()).collect();
-        let embeddings = self.embedder.embed(&text_refs)?;
-        for (mem, emb) in missing.iter().zip(embeddings.iter()) {
-            self.store.update_embedding(mem.id, emb)?;
+        match self.embedder.embed(&text_refs) {
+            Ok(embeddings) if embeddings.len() == batch.len() => {
+                batch.iter().map(|m| m.id).zip(embeddings).collect()
+            }
+            _ => {
+                let mut embedded = Vec::with_capacity(batch.len());
+                for (mem, text) in batch.iter().zip(&text_refs) {
+                    match self.embedder.embed_one(text) {
+                        Ok(emb) => embedded.push((mem.id, emb)),
+                        Err(e) => failures.push(EmbedFailure {
+                            id: mem.id,
+                            error: e.to_string(),
+                        }),
+                    }
+                }
+                embedded
+            }
         }
-        Ok(missing.len())
     }
 
     // ── Graph traversal ──────────────────────────────────────
 
-    /// Find all facts related to a subject via graph traversal up to `max_depth` hops.
-    /// Returns a list of GraphNodes with hop distance.
+    /// Find all memories related to a subject via graph traversal up to `max_depth` hops.
+    /// Facts link through their subject and object, other memories through the
+    /// entities they mention. Returns a list of GraphNodes with hop distance.
     pub fn related(&self, subject: &str, max_depth: usize) -> Result<Vec<GraphNode>, ConchError> {
         let max_depth = max_depth.min(3);
         let mut result: Vec<GraphNode> = Vec::new();
         let mut seen_ids = std::collections::HashSet::new();
         // Entities to explore at each depth level
-        let mut frontier = vec![subject.to_string()];
+        let mut frontier = vec![self.canonical_entity(subject)?];
 
         for depth in 0..max_depth {
             let mut next_frontier = Vec::new();
             for entity in &frontier {
-                let facts = self.store.facts_involving(entity)?;
-                for fact in facts {
-                    if seen_ids.contains(&fact.id) {
+                for memory in self.linked_memories(entity)? {
+                    if !seen_ids.insert(memory.id) {
                         continue;
                     }
-                    seen_ids.insert(fact.id);
-                    // Determine the connecting entity and the "other" entity for next hop
-                    let (connected_via, other_entity) = match &fact.kind {
-                        MemoryKind::Fact(f) => {
-                            if f.subject == *entity {
-                                (entity.clone(), f.object.clone())
-                            } else {
-                                (entity.clone(), f.subject.clone())
-                            }
-                        }
-                        _ => continue,
-                    };
-                    next_frontier.push(other_entity);
+                    // The memory's other entities are explored on the next hop
+                    next_frontier.extend(
+                        self.entities_of(&memory)?
+                            .into_iter()
+                            .filter(|e| !e.eq_ignore_ascii_case(entity)),
+                    );
                     result.push(GraphNode {
-                        memory: fact,
+                        memory,
                         depth,
-                        connected_via,
+                        connected_via: entity.clone(),
                     });
                 }
             }
@@ -675,38 +917,432 @@ impl ConchDB {
         Ok(result)
     }
 
+    /// Memories linked to `entity`: facts naming it as subject or object, and
+    /// other memories in this namespace that mention it.
+    fn linked_memories(&self, entity: &str) -> Result<Vec<MemoryRecord>, ConchError> {
+        let mut memories = self.store.facts_involving_ns(entity, &self.namespace)?;
+        memories.extend(self.store.memories_mentioning_ns(entity, &self.namespace)?);
+        Ok(memories)
+    }
+
+    /// Entities a memory links to: a fact's subject and object, or the
+    /// entities any other memory mentions.
+    fn entities_of(&self, mem: &MemoryRecord) -> Result<Vec<String>, ConchError> {
+        Ok(match &mem.kind {
+            MemoryKind::Fact(f) => vec![f.subject.clone(), f.object.clone()],
+            _ => self.store.mentions_of(mem.id)?,
+        })
+    }
+
+    /// Record the entities a new non-fact memory mentions: subjects and
+    /// objects of known facts, and capitalized names.
+    fn index_mentions(&self, mem: &MemoryRecord) -> Result<(), ConchError> {
+        if matches!(mem.kind, MemoryKind::Fact(_)) {
+            return Ok(());
+        }
+        let vocabulary = self.mention_vocabulary()?;
+        let mentions = vocabulary.detect(&mem.text_for_embedding());
+        self.store
+            .set_mentions(mem.id, &self.namespace, &mentions)?;
+        Ok(())
+    }
+
+    /// Names mention detection looks for: fact subjects and objects and
+    /// registry aliases, with the canonical name of each alias.
+    fn mention_vocabulary(&self) -> Result<mentions::MentionVocabulary, ConchError> {
+        let mut known = self.store.fact_entities_ns(&self.namespace)?;
+        let mut canonical = std::collections::HashMap::new();
+        for (alias, name) in self.store.entity_aliases_ns(&self.namespace)? {
+            canonical.insert(alias.to_lowercase(), name);
+            known.push(alias);
+        }
+        Ok(mentions::MentionVocabulary { known, canonical })
+    }
+
+    /// Rebuild the mention index of this namespace, e.g. after facts were
+    /// added that earlier memories mention. Returns the number of mentions.
+    pub fn reindex_mentions(&self) -> Result<usize, ConchError> {
+        let vocabulary = self.mention_vocabulary()?;
+        let mentions: Vec<(i64, Vec<String>)> = self
+            .store
+            .active_memories_ns(&self.namespace)?
+            .into_iter()
+            .filter(|mem| !matches!(mem.kind, MemoryKind::Fact(_)))
+            .map(|mem| (mem.id, vocabulary.detect(&mem.text_for_embedding())))
+            .collect();
+        Ok(self.store.replace_mentions_ns(&self.namespace, &mentions)?)
+    }
+
+    /// Entities mentioned in this namespace with how many memories mention
+    /// each, most mentioned first.
+    pub fn mention_counts(&self, limit: usize) -> Result<Vec<(String, usize)>, ConchError> {
+        Ok(self.store.mention_counts_ns(&self.namespace, limit)?)
+    }
+
+    // ── Entities ────────────────────────────────────────────
+
+    /// Canonical name `name` resolves to in the entity registry, or `name`
+    /// itself if it is not registered.
+    fn canonical_entity(&self, name: &str) -> Result<String, ConchError> {
+        Ok(self
+            .store
+            .canonical_entity_ns(name, &self.namespace)?
+            .unwrap_or_else(|| name.to_string()))
+    }
+
+    /// Resolve a fact's subject and object to their canonical entities,
+    /// registering names not seen before. Objects the relation schema types
+    /// as plain values are kept as they are.
+    fn register_fact_entities(
+        &self,
+        subject: &str,
+        object: &str,
+        schema: Option<&RelationSchema>,
+    ) -> Result<(String, String), ConchError> {
+        let now = chrono::Utc::now();
+        let object_is_entity = schema.is_none_or(|s| s.object_type == ObjectType::Entity);
+        Ok((
+            self.store.register_entity(subject, &self.namespace, now)?,
+            if object_is_entity {
+                self.store.register_entity(object, &self.namespace, now)?
+            } else {
+                object.to_string()
+            },
+        ))
+    }
+
+    /// Registered entities in this namespace, by name.
+    pub fn entities(&self) -> Result<Vec<Entity>, ConchError> {
+        Ok(self.store.entities_ns(&self.namespace)?)
+    }
+
+    fn entity(&self, name: &str) -> Result<Entity, ConchError> {
+        let canonical = self.canonical_entity(name)?;
+        self.entities()?
+            .into_iter()
+            .find(|e| e.name == canonical)
+            .ok_or_else(|| ConchError::InvalidArgument(format!("unknown entity \"{name}\"")))
+    }
+
+    /// Make `alias` another name for the entity `name`. Facts already spelled
+    /// with the alias are rewritten to the canonical name. An alias that is
+    /// itself an entity has to be merged instead.
+    pub fn alias_entity(&self, alias: &str, name: &str) -> Result<Entity, ConchError> {
+        let entity = self.entity(name)?;
+        match self.store.canonical_entity_ns(alias, &self.namespace)? {
+            Some(existing) if existing == entity.name => return Ok(entity),
+            Some(existing) => {
+                return Err(ConchError::InvalidArgument(format!(
+                    "\"{alias}\" already names entity \"{existing}\"; merge them instead"
+                )))
+            }
+            None => {}
+        }
+        self.store
+            .merge_entity_ns(alias, &entity.name, &self.namespace)?;
+        self.entity(&entity.name)
+    }
+
+    /// Merge the entity `from` into `into`: `from` and its aliases become
+    /// aliases of `into`, and its facts and mentions move over.
+    pub fn merge_entities(&self, from: &str, into: &str) -> Result<Entity, ConchError> {
+        let from = self.entity(from)?;
+        let into = self.entity(into)?;
+        if from.id == into.id {
+            return Err(ConchError::InvalidArgument(format!(
+                "\"{}\" and \"{}\" are already the same entity",
+                from.name, into.name
+            )));
+        }
+        self.store
+            .merge_entity_ns(&from.name, &into.name, &self.namespace)?;
+        self.entity(&into.name)
+    }
+
+    // ── Relations ───────────────────────────────────────────
+
+    /// Canonical form of `relation` in this namespace: normalized and mapped
+    /// through the namespace's synonyms and the built-in ones.
+    pub fn canonical_relation(&self, relation: &str) -> Result<String, ConchError> {
+        let synonyms = self.store.relation_synonyms_ns(&self.namespace)?;
+        Ok(relations::canonical_relation(relation, &synonyms))
+    }
+
+    /// Map `synonym` to the canonical `relation` in this namespace and
+    /// rewrite facts already using the synonym. Returns the number of facts
+    /// rewritten.
+    pub fn add_relation_synonym(&self, synonym: &str, relation: &str) -> Result<usize, ConchError> {
+        let synonym = normalize_relation(synonym);
+        let relation = self.canonical_relation(relation)?;
+        if synonym.is_empty() || relation.is_empty() {
+            return Err(ConchError::InvalidArgument(
+                "relation names must not be empty".to_string(),
+            ));
+        }
+        if synonym == relation {
+            return Err(ConchError::InvalidArgument(format!(
+                "\"{synonym}\" is already the canonical relation"
+            )));
+        }
+        self.store.add_relation_synonym(
+            &synonym,
+            &relation,
+            &self.namespace,
+            chrono::Utc::now(),
+        )?;
+        self.normalize_relations()
+    }
+
+    /// Rewrite every fact in this namespace to its canonical relation, e.g.
+    /// after importing facts written before normalization. Returns the number
+    /// of facts rewritten.
+    pub fn normalize_relations(&self) -> Result<usize, ConchError> {
+        let synonyms = self.store.relation_synonyms_ns(&self.namespace)?;
+        Ok(self
+            .store
+            .rewrite_relations_ns(&self.namespace, |relation| {
+                relations::canonical_relation(relation, &synonyms)
+            })?)
+    }
+
+    /// Relations in this namespace with their fact counts and synonyms, most
+    /// used first. Relations that only have synonyms so far are listed too.
+    pub fn relations(&self) -> Result<Vec<RelationUsage>, ConchError> {
+        let synonyms = self.store.relation_synonyms_ns(&self.namespace)?;
+        let mut usage: Vec<RelationUsage> = self
+            .store
+            .relation_counts_ns(&self.namespace)?
+            .into_iter()
+            .map(|(relation, facts)| RelationUsage {
+                relation,
+                facts,
+                synonyms: Vec::new(),
+            })
+            .collect();
+        for target in synonyms.values() {
+            if !usage.iter().any(|u| u.relation == *target) {
+                usage.push(RelationUsage {
+                    relation: target.clone(),
+                    facts: 0,
+                    synonyms: Vec::new(),
+                });
+            }
+        }
+        let builtin = BUILTIN_SYNONYMS
+            .iter()
+            .map(|(synonym, _)| synonym.to_string())
+            .filter(|synonym| !synonyms.contains_key(synonym));
+        for synonym in synonyms.keys().cloned().chain(builtin) {
+            let target = relations::canonical_relation(&synonym, &synonyms);
+            if let Some(u) = usage.iter_mut().find(|u| u.relation == target) {
+                u.synonyms.push(synonym);
+            }
+        }
+        for u in &mut usage {
+            u.synonyms.sort();
+        }
+        Ok(usage)
+    }
+
+    // ── Relation schema ─────────────────────────────────────
+
+    /// Declare the cardinality, object type and inverse of a relation in this
+    /// namespace. Relation names are stored in canonical form; an existing
+    /// schema for the relation is replaced.
+    pub fn set_relation_schema(
+        &self,
+        relation: &str,
+        cardinality: Cardinality,
+        object_type: ObjectType,
+        inverse: Option<&str>,
+    ) -> Result<RelationSchema, ConchError> {
+        let relation = self.canonical_relation(relation)?;
+        if relation.is_empty() {
+            return Err(ConchError::InvalidArgument(
+                "relation names must not be empty".to_string(),
+            ));
+        }
+        let inverse = inverse
+            .map(|inverse| self.canonical_relation(inverse))
+            .transpose()?
+            .filter(|inverse| !inverse.is_empty());
+        if inverse.is_some() && object_type != ObjectType::Entity {
+            return Err(ConchError::InvalidArgument(format!(
+                "\"{relation}\" has {object_type} objects, so it cannot have an inverse"
+            )));
+        }
+        let schema = RelationSchema {
+            relation,
+            cardinality,
+            object_type,
+            inverse,
+        };
+        self.store
+            .set_relation_schema(&schema, &self.namespace, chrono::Utc::now())?;
+        Ok(schema)
+    }
+
+    /// Relation schemas declared in this namespace, by relation.
+    pub fn relation_schemas(&self) -> Result<Vec<RelationSchema>, ConchError> {
+        Ok(self.store.relation_schemas_ns(&self.namespace)?)
+    }
+
+    /// Schema of the canonical `relation`, after checking `object` against
+    /// its declared type.
+    fn checked_schema(
+        &self,
+        relation: &str,
+        object: &str,
+    ) -> Result<Option<RelationSchema>, ConchError> {
+        let schema = self.store.relation_schema_ns(relation, &self.namespace)?;
+        if let Some(schema) = &schema {
+            schema
+                .check_object(object)
+                .map_err(|violations| ConchError::ValidationError { violations })?;
+        }
+        Ok(schema)
+    }
+
+    /// Store a fact the way its relation's schema asks: a single-valued
+    /// relation supersedes the subject's current object, a multi-valued one
+    /// adds a fact unless the same fact exists. Relations without a schema
+    /// upsert only when `upsert` is set. Returns `(id, was_updated)`.
+    #[allow(clippy::too_many_arguments)]
+    fn write_fact(
+        &self,
+        subject: &str,
+        relation: &str,
+        object: &str,
+        schema: Option<&RelationSchema>,
+        upsert: bool,
+        embedding: &[f32],
+        tags: &[String],
+        source: Option<&str>,
+        session_id: Option<&str>,
+        channel: Option<&str>,
+    ) -> Result<(i64, bool), ConchError> {
+        let upsert = match schema.map(|s| s.cardinality) {
+            Some(Cardinality::One) => true,
+            Some(Cardinality::Many) => {
+                let checksum =
+                    crate::store::content_checksum(&format!("{subject} {relation} {object}"));
+                if let Some(existing) = self
+                    .store
+                    .find_fact_by_checksum_ns(&checksum, &self.namespace)?
+                {
+                    return Ok((existing.id, false));
+                }
+                false
+            }
+            None => upsert,
+        };
+        if upsert {
+            Ok(self.store.upsert_fact_ns(
+                subject,
+                relation,
+                object,
+                Some(embedding),
+                tags,
+                source,
+                session_id,
+                channel,
+                &self.namespace,
+            )?)
+        } else {
+            let id = self.store.remember_fact_ns(
+                subject,
+                relation,
+                object,
+                Some(embedding),
+                tags,
+                source,
+                session_id,
+                channel,
+                &self.namespace,
+            )?;
+            Ok((id, false))
+        }
+    }
+
+    /// Facts about `entity`, read from its side: facts naming it as subject,
+    /// and facts naming it as object whose relation has a declared inverse,
+    /// turned around ("Ana parent_of Bo" reads "Bo child_of Ana"). With
+    /// `relation`, only facts with that relation (in either reading).
+    pub fn facts_about(
+        &self,
+        entity: &str,
+        relation: Option<&str>,
+    ) -> Result<Vec<FactView>, ConchError> {
+        let entity = self.canonical_entity(entity)?;
+        let relation = relation
+            .map(|relation| self.canonical_relation(relation))
+            .transpose()?;
+        // Inverses are declared on one side and hold both ways.
+        let mut inverses = std::collections::HashMap::new();
+        for schema in self.relation_schemas()? {
+            if let Some(inverse) = schema.inverse {
+                inverses
+                    .entry(inverse.clone())
+                    .or_insert_with(|| schema.relation.clone());
+                inverses.insert(schema.relation, inverse);
+            }
+        }
+
+        let mut views = Vec::new();
+        for mem in self.store.facts_involving_ns(&entity, &self.namespace)? {
+            let MemoryKind::Fact(fact) = &mem.kind else {
+                continue;
+            };
+            let view = if fact.subject.eq_ignore_ascii_case(&entity) {
+                FactView {
+                    memory_id: mem.id,
+                    fact: fact.clone(),
+                    inverted: false,
+                }
+            } else if let Some(inverse) = inverses.get(&fact.relation) {
+                FactView {
+                    memory_id: mem.id,
+                    fact: Fact {
+                        subject: fact.object.clone(),
+                        relation: inverse.clone(),
+                        object: fact.subject.clone(),
+                    },
+                    inverted: true,
+                }
+            } else {
+                continue;
+            };
+            if relation.as_ref().is_none_or(|r| *r == view.fact.relation) {
+                views.push(view);
+            }
+        }
+        Ok(views)
+    }
+
     // ── Provenance ──────────────────────────────────────────
 
-    /// Get provenance information for a memory by ID, including metadata and 1-hop related facts.
+    /// Get provenance information for a memory by ID, including metadata and 1-hop related memories.
     pub fn why(&self, id: i64) -> Result<Option<ProvenanceInfo>, ConchError> {
         let mem = match self.store.get_memory(id)? {
             Some(m) => m,
             None => return Ok(None),
         };
 
-        // Get 1-hop related facts if it's a fact
-        let related = if let MemoryKind::Fact(ref f) = mem.kind {
-            let mut nodes = Vec::new();
-            let mut seen = std::collections::HashSet::new();
-            seen.insert(mem.id);
-            for entity in [&f.subject, &f.object] {
-                let facts = self.store.facts_involving(entity)?;
-                for fact in facts {
-                    if seen.contains(&fact.id) {
-                        continue;
-                    }
-                    seen.insert(fact.id);
-                    nodes.push(GraphNode {
-                        memory: fact,
+        // Memories 1 hop away through the fact's entities or the mentions
+        let mut related = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(mem.id);
+        for entity in self.entities_of(&mem)? {
+            for memory in self.linked_memories(&entity)? {
+                if seen.insert(memory.id) {
+                    related.push(GraphNode {
+                        memory,
                         depth: 0,
                         connected_via: entity.clone(),
                     });
                 }
             }
-            nodes
-        } else {
-            vec![]
-        };
+        }
 
         Ok(Some(ProvenanceInfo {
             created_at: mem.created_at.to_rfc3339(),
@@ -717,30 +1353,299 @@ impl ConchDB {
             session_id: mem.session_id.clone(),
             channel: mem.channel.clone(),
             related,
+            archived_at: self.store.archived_at(mem.id)?.map(|t| t.to_rfc3339()),
+            lineage: self.store.lineage(mem.id)?,
             memory: mem,
         }))
     }
 
+    /// Recall for `query` and render a prompt-ready block of at most
+    /// `budget_tokens` (estimated) tokens, grouped by kind with each memory
+    /// cited as `[#id]`. Results are diversified so near-duplicates don't
+    /// spend the budget twice.
+    pub fn context(
+        &self,
+        query: &str,
+        budget_tokens: usize,
+        format: ContextFormat,
+    ) -> Result<ContextBlock, ConchError> {
+        let options = RecallOptions {
+            diversity: Some(DEFAULT_DIVERSITY_LAMBDA),
+            ..Default::default()
+        };
+        let results = self.recall_with_options(query, CONTEXT_CANDIDATES, &options)?;
+        let memories: Vec<MemoryRecord> = results.into_iter().map(|r| r.memory).collect();
+        Ok(context::render_context(&memories, budget_tokens, format))
+    }
+
+    /// Record an agent's verdict on a recalled memory. Useful reinforces it,
+    /// irrelevant undoes the recall's boost, and wrong demotes it and tags it
+    /// `needs_review`. Every judgement is logged for evaluation and tuning.
+    pub fn feedback(
+        &self,
+        memory_id: i64,
+        query: &str,
+        feedback: Feedback,
+    ) -> Result<FeedbackEntry, ConchError> {
+        let memory = self
+            .store
+            .get_memory(memory_id)?
+            .filter(|m| m.namespace == self.namespace)
+            .ok_or_else(|| ConchError::InvalidArgument(format!("no memory with id {memory_id}")))?;
+        let now = chrono::Utc::now();
+        let pre_touch = self.store.pre_touch_strength(memory_id)?;
+        let strength = recall::feedback_strength(&memory, feedback, pre_touch, now);
+        Ok(self
+            .store
+            .record_feedback(&memory, query, feedback, strength, now)?)
+    }
+
+    /// All feedback logged in this namespace, oldest first.
+    pub fn feedback_log(&self) -> Result<Vec<FeedbackEntry>, ConchError> {
+        Ok(self.store.feedback_log(&self.namespace)?)
+    }
+
+    /// Current state of a recall session: memories it has seen and what is primed.
+    pub fn session(&self, session_id: &str) -> Result<SessionState, ConchError> {
+        Ok(self
+            .store
+            .recall_session(session_id, &self.namespace, chrono::Utc::now())?)
+    }
+
+    /// Forget a recall session so its memories can be returned again.
+    /// Returns whether the session existed.
+    pub fn end_session(&self, session_id: &str) -> Result<bool, ConchError> {
+        Ok(self.store.end_recall_session(session_id, &self.namespace)?)
+    }
+
+    /// Memories most often recalled together with `id`, strongest association first.
+    pub fn associations(&self, id: i64, limit: usize) -> Result<Vec<Association>, ConchError> {
+        self.store
+            .get_memory(id)?
+            .filter(|m| m.namespace == self.namespace)
+            .ok_or_else(|| ConchError::InvalidArgument(format!("no memory with id {id}")))?;
+        let now = chrono::Utc::now();
+        let rows = self
+            .store
+            .co_recall_associations(id, &self.namespace, limit, now)?;
+        let mut associations = Vec::with_capacity(rows.len());
+        for link in rows {
+            if let Some(memory) = self.store.get_memory(link.memory_id)? {
+                associations.push(Association {
+                    memory,
+                    weight: link.weight,
+                    count: link.count,
+                    last_seen: link.last_seen,
+                });
+            }
+        }
+        Ok(associations)
+    }
+
     pub fn export(&self) -> Result<ExportData, ConchError> {
         let memories = self.store.all_memories_ns(&self.namespace)?;
         Ok(ExportData { memories })
     }
 
-    pub fn consolidate(&self, dry_run: bool) -> Result<ConsolidateResult, ConchError> {
+    /// Consolidate near-duplicate memories in this namespace as set out by
+    /// `options`. `on_progress` is called as the pass indexes and compares
+    /// memories.
+    pub fn consolidate<F>(
+        &self,
+        dry_run: bool,
+        options: &ConsolidateOptions,
+        mut on_progress: F,
+    ) -> Result<ConsolidateResult, ConchError>
+    where
+        F: FnMut(&ConsolidateProgress),
+    {
         if dry_run {
-            let clusters = find_clusters(&self.store, None)?;
+            let clusters = self.consolidate_clusters(options, on_progress)?;
+            let summarize = options.mode == ConsolidateMode::Summarize;
             Ok(ConsolidateResult {
                 clusters: clusters.len(),
-                archived: clusters.iter().map(|c| c.duplicates.len()).sum(),
-                boosted: clusters.len(),
+                archived: clusters
+                    .iter()
+                    .map(|c| c.duplicates.len() + usize::from(summarize))
+                    .sum(),
+                boosted: if summarize { 0 } else { clusters.len() },
+                summarized: if summarize { clusters.len() } else { 0 },
+                summaries: Vec::new(),
             })
         } else {
-            Ok(consolidate::consolidate(&self.store, None)?)
+            match options.mode {
+                ConsolidateMode::Merge => Ok(consolidate::consolidate_with(
+                    &self.store,
+                    &self.namespace,
+                    options,
+                    &mut on_progress,
+                )?),
+                ConsolidateMode::Summarize => {
+                    let result = consolidate::consolidate_with_summaries(
+                        &self.store,
+                        &self.namespace,
+                        options,
+                        self.summarizer.as_ref(),
+                        self.embedder.as_ref(),
+                        &mut on_progress,
+                    )?;
+                    // Summaries take over their sources' place in the graph.
+                    for id in &result.summaries {
+                        if let Some(summary) = self.store.get_memory(*id)? {
+                            self.index_mentions(&summary)?;
+                        }
+                    }
+                    Ok(result)
+                }
+            }
+        }
+    }
+
+    /// Clusters consolidation would act on, without changing anything. In
+    /// summarize mode only clusters of episodes or actions are returned, each
+    /// with its proposed summary.
+    pub fn consolidate_clusters<F>(
+        &self,
+        options: &ConsolidateOptions,
+        mut on_progress: F,
+    ) -> Result<Vec<ConsolidateCluster>, ConchError>
+    where
+        F: FnMut(&ConsolidateProgress),
+    {
+        match options.mode {
+            ConsolidateMode::Merge => Ok(consolidate::find_clusters_with(
+                &self.store,
+                &self.namespace,
+                options,
+                &mut on_progress,
+            )?),
+            ConsolidateMode::Summarize => consolidate::preview_summaries(
+                &self.store,
+                &self.namespace,
+                options,
+                self.summarizer.as_ref(),
+                &mut on_progress,
+            )
+            .map_err(ConchError::from),
+        }
+    }
+
+    /// Lineage links in which `id` is the derived memory or a source.
+    pub fn lineage(&self, id: i64) -> Result<Vec<LineageLink>, ConchError> {
+        Ok(self.store.lineage(id)?)
+    }
+
+    /// Pull candidate facts out of every episode and action in this namespace
+    /// with the pattern rules in [`extract::RULES`]. New facts are stored at
+    /// the rule's confidence as their strength, with source
+    /// [`extract::EXTRACTION_SOURCE`] and a `derived_from` link to the memory
+    /// they came from; a candidate that is already a fact only gains the link.
+    /// With `dry_run`, candidates are reported and nothing is written.
+    pub fn extract_facts(&self, dry_run: bool) -> Result<ExtractReport, ConchError> {
+        let mut report = ExtractReport::default();
+        for mem in self.store.active_memories_ns(&self.namespace)? {
+            if matches!(mem.kind, MemoryKind::Episode(_) | MemoryKind::Action(_)) {
+                report.scanned += 1;
+                self.extract_from(&mem, dry_run, &mut report)?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Run extraction on a newly written memory if the namespace policy asks for it.
+    fn extract_on_write(&self, mem: &MemoryRecord) -> Result<(), ConchError> {
+        if self.store.namespace_policy(&self.namespace)?.extract_facts {
+            self.extract_from(mem, false, &mut ExtractReport::default())?;
+        }
+        Ok(())
+    }
+
+    fn extract_from(
+        &self,
+        mem: &MemoryRecord,
+        dry_run: bool,
+        report: &mut ExtractReport,
+    ) -> Result<(), ConchError> {
+        for fact in extract::extract_from_memory(mem) {
+            let relation = self.canonical_relation(&fact.relation)?;
+            // Candidates the relation schema would reject are not facts.
+            let schema = match self.checked_schema(&relation, &fact.object) {
+                Err(ConchError::ValidationError { .. }) => continue,
+                schema => schema?,
+            };
+            report.candidates.push(ExtractionCandidate {
+                source_id: mem.id,
+                fact: fact.clone(),
+            });
+            if dry_run {
+                continue;
+            }
+            let (subject, object) =
+                self.register_fact_entities(&fact.subject, &fact.object, schema.as_ref())?;
+            let text = format!("{subject} {relation} {object}");
+            let checksum = crate::store::content_checksum(&text);
+            let now = chrono::Utc::now();
+            if let Some(existing) = self
+                .store
+                .find_fact_by_checksum_ns(&checksum, &self.namespace)?
+            {
+                let linked = self
+                    .store
+                    .lineage(existing.id)?
+                    .iter()
+                    .any(|l| l.memory_id == existing.id && l.source_id == mem.id);
+                if !linked {
+                    self.store.add_lineage(
+                        existing.id,
+                        &[mem.id],
+                        LineageRelation::DerivedFrom,
+                        now,
+                    )?;
+                    report.linked += 1;
+                }
+                continue;
+            }
+
+            let embedding = self.embedder.embed_one(&text)?;
+            let (id, _) = self.write_fact(
+                &subject,
+                &relation,
+                &object,
+                schema.as_ref(),
+                false,
+                &embedding,
+                &mem.tags,
+                Some(extract::EXTRACTION_SOURCE),
+                mem.session_id.as_deref(),
+                mem.channel.as_deref(),
+            )?;
+            self.store.set_strength(id, fact.confidence)?;
+            self.store
+                .add_lineage(id, &[mem.id], LineageRelation::DerivedFrom, now)?;
+            report.created += 1;
+        }
+        Ok(())
+    }
+
+    /// Recompute this namespace's topics with `k` clusters (default
+    /// [`default_topic_count`]), replacing the previous ones.
+    pub fn build_topics(&self, k: Option<usize>) -> Result<Vec<Topic>, ConchError> {
+        if k == Some(0) {
+            return Err(ConchError::InvalidArgument(
+                "topic count must be at least 1".to_string(),
+            ));
         }
+        Ok(topics::build_topics(&self.store, &self.namespace, k)?)
+    }
+
+    /// Stored topics of this namespace, largest first.
+    pub fn topics(&self) -> Result<Vec<Topic>, ConchError> {
+        Ok(self.store.topics_ns(&self.namespace)?)
     }
 
-    pub fn consolidate_clusters(&self) -> Result<Vec<ConsolidateCluster>, ConchError> {
-        Ok(find_clusters(&self.store, None)?)
+    /// Members of a topic, strongest first.
+    pub fn topic_memories(&self, topic_id: i64) -> Result<Vec<MemoryRecord>, ConchError> {
+        Ok(self.store.topic_memories(topic_id)?)
     }
 
     pub fn score_importance(&self) -> Result<usize, ConchError> {
@@ -1245,6 +2150,365 @@ mod tests {
         assert_eq!(db.stats().unwrap().total_memories, 2);
     }
 
+    // ── Embedding backfill tests ────────────────────────────
+
+    /// Mock embedder that rejects any batch containing the word "poison".
+    struct PoisonEmbedder;
+
+    impl Embedder for PoisonEmbedder {
+        fn embed(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+            if texts.iter().any(|t| t.contains("poison")) {
+                return Err(EmbedError::Other("poisoned input".into()));
+            }
+            Ok(texts.iter().map(|_| vec![1.0, 0.0, 0.0, 0.0]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+    }
+
+    #[test]
+    fn embed_all_runs_in_batches_and_reports_progress() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        for i in 0..5 {
+            db.store()
+                .remember_episode(&format!("unembedded episode {i}"), None)
+                .unwrap();
+        }
+
+        let mut snapshots = Vec::new();
+        let report = db.embed_all_batched(2, |p| snapshots.push(*p)).unwrap();
+
+        assert_eq!(report.embedded, 5);
+        assert_eq!(report.batches, 3);
+        assert!(report.failures.is_empty());
+        let processed: Vec<usize> = snapshots.iter().map(|p| p.processed).collect();
+        assert_eq!(processed, vec![2, 4, 5]);
+        assert!(snapshots.iter().all(|p| p.total == 5));
+        assert_eq!(
+            db.store().count_missing_embeddings_ns("default").unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn embed_all_only_touches_current_namespace() {
+        let db =
+            ConchDB::open_in_memory_with_namespace(Box::new(IdenticalEmbedder), "team").unwrap();
+        db.store()
+            .remember_episode_ns("team note", None, &[], None, None, None, "team")
+            .unwrap();
+        db.store()
+            .remember_episode_ns("other note", None, &[], None, None, None, "other")
+            .unwrap();
+
+        assert_eq!(db.embed_all().unwrap(), 1);
+        assert_eq!(db.store().count_missing_embeddings_ns("team").unwrap(), 0);
+        assert_eq!(db.store().count_missing_embeddings_ns("other").unwrap(), 1);
+    }
+
+    #[test]
+    fn embed_all_reports_failures_per_memory_and_resumes() {
+        let db = ConchDB::open_in_memory_with(Box::new(PoisonEmbedder)).unwrap();
+        db.store().remember_episode("good one", None).unwrap();
+        let bad = db.store().remember_episode("poison pill", None).unwrap();
+        db.store().remember_episode("good two", None).unwrap();
+
+        let report = db.embed_all_batched(10, |_| {}).unwrap();
+        assert_eq!(
+            report.embedded, 2,
+            "healthy rows in a failing batch still embed"
+        );
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].id, bad);
+
+        // A second pass only revisits what is still missing.
+        let report = db.embed_all_batched(10, |_| {}).unwrap();
+        assert_eq!(report.embedded, 0);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(
+            db.store().count_missing_embeddings_ns("default").unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn embed_all_rejects_zero_batch_size() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        assert!(matches!(
+            db.embed_all_batched(0, |_| {}),
+            Err(ConchError::InvalidArgument(_))
+        ));
+    }
+
+    // ── Rerank policy tests ─────────────────────────────────
+
+    struct CountingReranker {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Reranker for CountingReranker {
+        fn rerank(&self, _query: &str, documents: &[&str]) -> Result<Vec<f32>, EmbedError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![0.0; documents.len()])
+        }
+    }
+
+    #[test]
+    fn rerank_runs_per_call_or_per_namespace_policy() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        db.set_reranker(Box::new(CountingReranker {
+            calls: calls.clone(),
+        }));
+        db.remember_episode("release checklist").unwrap();
+        let count = || calls.load(std::sync::atomic::Ordering::SeqCst);
+
+        db.recall("release", 5).unwrap();
+        assert_eq!(count(), 0, "rerank is off by default");
+
+        let per_call = RecallOptions {
+            rerank: Some(true),
+            ..Default::default()
+        };
+        let results = db.recall_with_options("release", 5, &per_call).unwrap();
+        assert_eq!(count(), 1);
+        assert!(results[0].explain.rerank_score.is_some());
+
+        db.set_policy(&NamespacePolicy {
+            rerank: true,
+            ..Default::default()
+        })
+        .unwrap();
+        db.recall("release", 5).unwrap();
+        assert_eq!(count(), 2, "namespace policy enables rerank");
+
+        let opt_out = RecallOptions {
+            rerank: Some(false),
+            ..Default::default()
+        };
+        db.recall_with_options("release", 5, &opt_out).unwrap();
+        assert_eq!(count(), 2, "per-call option overrides the policy");
+    }
+
+    #[test]
+    fn fusion_comes_from_call_then_namespace_policy() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        db.remember_episode("rotated PLAT-4412 credentials")
+            .unwrap();
+        let fusion_of = |options: &RecallOptions| {
+            db.recall_with_options("PLAT-4412", 5, options).unwrap()[0]
+                .explain
+                .fusion
+        };
+
+        assert_eq!(
+            fusion_of(&RecallOptions::default()),
+            FusionStrategy::default()
+        );
+
+        db.set_policy(&NamespacePolicy {
+            fusion: Some(FusionStrategy::KeywordOnly),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            fusion_of(&RecallOptions::default()),
+            FusionStrategy::KeywordOnly
+        );
+
+        let per_call = RecallOptions {
+            fusion: Some(FusionStrategy::VectorOnly),
+            ..Default::default()
+        };
+        assert_eq!(fusion_of(&per_call), FusionStrategy::VectorOnly);
+    }
+
+    // ── Evaluation tests ────────────────────────────────────
+
+    #[test]
+    fn evaluate_scores_suite_without_reinforcing() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let id = db
+            .remember_episode("rotated PLAT-4412 credentials")
+            .unwrap()
+            .id;
+        let before = db.store().get_memory(id).unwrap().unwrap();
+
+        let suite = EvalSuite::from_yaml(&format!(
+            "cases:\n  - query: PLAT-4412\n    expected_ids: [{id}]\n  - query: credentials\n    expected_contains: [nonexistent]\n"
+        ))
+        .unwrap();
+        let report = db.evaluate(&suite).unwrap();
+
+        assert_eq!(report.cases[0].first_relevant_rank, Some(1));
+        assert!((report.mrr - 0.5).abs() < 1e-12);
+        assert!((report.recall - 0.5).abs() < 1e-12);
+        let after = db.store().get_memory(id).unwrap().unwrap();
+        assert_eq!(after.access_count, before.access_count);
+        assert_eq!(after.strength, before.strength);
+    }
+
+    #[test]
+    fn tune_never_scores_below_the_starting_policy() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        db.remember_episode("rotated PLAT-4412 credentials")
+            .unwrap();
+        db.remember_episode("lunch order for the offsite").unwrap();
+        let suite = EvalSuite::from_yaml(
+            "cases:\n  - query: PLAT-4412 credentials\n    expected_contains: [PLAT-4412]\n",
+        )
+        .unwrap();
+
+        let result = db.tune(&suite, TuneMetric::Mrr).unwrap();
+        assert!(result.tuned >= result.baseline);
+        assert!(result.evaluations > 1);
+        assert_eq!(db.policy().unwrap(), NamespacePolicy::default());
+    }
+
+    // ── Context block tests ─────────────────────────────────
+
+    #[test]
+    fn context_groups_by_kind_and_cites_ids() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        let fact = db
+            .remember_fact("Jared", "works_at", "Microsoft")
+            .unwrap()
+            .id;
+        let action = db
+            .remember_action("deployed the Microsoft billing service")
+            .unwrap()
+            .id;
+
+        let block = db
+            .context("Microsoft", 500, ContextFormat::Markdown)
+            .unwrap();
+        assert!(block
+            .text
+            .contains(&format!("- Jared works at Microsoft [#{fact}]")));
+        assert!(block.text.contains(&format!("[#{action}]")));
+        assert!(
+            block.text.find("### Facts").unwrap() < block.text.find("### Recent actions").unwrap()
+        );
+        assert_eq!(block.memory_ids.len(), 2);
+        assert!(block.tokens <= 500);
+    }
+
+    #[test]
+    fn context_stops_at_the_token_budget() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        for i in 0..5 {
+            db.remember_episode(&format!(
+                "release {i} shipped after a long review of the deployment checklist"
+            ))
+            .unwrap();
+        }
+
+        let block = db
+            .context("release deployment", 40, ContextFormat::Xml)
+            .unwrap();
+        assert!(block.tokens <= 40);
+        assert!(!block.memory_ids.is_empty());
+        assert!(block.omitted > 0);
+        assert!(block.text.starts_with("<memories>\n  <episodes>\n"));
+        assert_eq!(block.memory_ids.len() + block.omitted, 5);
+    }
+
+    // ── Feedback tests ──────────────────────────────────────
+
+    #[test]
+    fn irrelevant_feedback_undoes_the_recall_boost() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let id = db.remember_episode("standup notes from monday").unwrap().id;
+        db.store()
+            .touch_memory_with_strength(id, 0.5, chrono::Utc::now())
+            .unwrap();
+
+        db.recall("standup", 5).unwrap();
+        let boosted = db.store().get_memory(id).unwrap().unwrap().strength;
+        assert!(boosted > 0.5);
+
+        let entry = db.feedback(id, "standup", Feedback::Irrelevant).unwrap();
+        assert!((entry.strength_after - 0.5).abs() < 1e-6);
+        assert_eq!(db.feedback_log().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn irrelevant_feedback_restores_a_decayed_memory() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let id = db.remember_episode("retro notes from march").unwrap().id;
+        let aged = chrono::Utc::now() - chrono::Duration::days(5);
+        db.store()
+            .touch_memory_with_strength(id, 0.5, aged)
+            .unwrap();
+        let before = db.store().get_memory(id).unwrap().unwrap();
+
+        db.recall("retro", 5).unwrap();
+        let touched = db.store().get_memory(id).unwrap().unwrap();
+        assert!(touched.last_accessed_at > before.last_accessed_at);
+
+        let entry = db.feedback(id, "retro", Feedback::Irrelevant).unwrap();
+        assert!((entry.strength_after - 0.5).abs() < 1e-6);
+        let restored = db.store().get_memory(id).unwrap().unwrap();
+        assert!((restored.strength - 0.5).abs() < 1e-6);
+        assert_eq!(restored.last_accessed_at, before.last_accessed_at);
+    }
+
+    #[test]
+    fn repeated_irrelevant_feedback_undoes_one_touch() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let id = db.remember_episode("sprint planning notes").unwrap().id;
+        db.store()
+            .touch_memory_with_strength(id, 0.5, chrono::Utc::now())
+            .unwrap();
+        let before = db.store().get_memory(id).unwrap().unwrap();
+
+        db.recall("planning", 5).unwrap();
+        db.feedback(id, "planning", Feedback::Irrelevant).unwrap();
+        let once = db.store().get_memory(id).unwrap().unwrap();
+        assert_eq!(once.access_count, before.access_count);
+
+        let entry = db.feedback(id, "planning", Feedback::Irrelevant).unwrap();
+        let twice = db.store().get_memory(id).unwrap().unwrap();
+        assert_eq!(twice.access_count, before.access_count);
+        assert!((entry.strength_after - once.strength).abs() < 1e-9);
+    }
+
+    #[test]
+    fn feedback_rejects_memories_from_other_namespaces() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let id = db.remember_episode("private note").unwrap().id;
+        let other = ConchDB {
+            namespace: "other".to_string(),
+            ..db
+        };
+        assert!(other.feedback(id, "note", Feedback::Useful).is_err());
+    }
+
+    #[test]
+    fn associations_stay_in_the_namespace() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        let a = db.remember_episode("deploy checklist").unwrap().id;
+        let b = db.remember_episode("rollback plan").unwrap().id;
+        db.store()
+            .record_co_recall(&[a, b], "default", chrono::Utc::now())
+            .unwrap();
+        assert_eq!(db.associations(a, 10).unwrap().len(), 1);
+
+        let other = ConchDB {
+            namespace: "other".to_string(),
+            ..db
+        };
+        assert!(other.associations(a, 10).is_err());
+        let c = other.remember_episode("release notes").unwrap().id;
+        other
+            .store()
+            .record_co_recall(&[a, c], "other", chrono::Utc::now())
+            .unwrap();
+        assert!(other.associations(c, 10).unwrap().is_empty());
+    }
+
     // ── Graph traversal tests ───────────────────────────────
 
     /// Helper: create a ConchDB with OrthogonalEmbedder and insert a chain of facts.
@@ -1321,6 +2585,214 @@ mod tests {
         );
     }
 
+    #[test]
+    fn related_and_why_follow_entity_mentions() {
+        let db = setup_graph_db();
+        let episode = db
+            .remember_episode("Had coffee with Alice and Bob near the office")
+            .unwrap();
+        let action = db.remember_action("Sent the Acme contract").unwrap();
+        assert_eq!(
+            db.store().mentions_of(episode.id).unwrap(),
+            vec!["Alice", "Bob"]
+        );
+
+        let nodes = db.related("Alice", 1).unwrap();
+        let via_mention = nodes.iter().find(|n| n.memory.id == episode.id).unwrap();
+        assert_eq!(via_mention.depth, 0);
+        assert_eq!(via_mention.connected_via, "Alice");
+        // The episode's other mention leads on to Bob's facts and the action.
+        let deeper = db.related("Alice", 3).unwrap();
+        assert!(deeper.iter().any(|n| n.memory.id == action.id));
+
+        let why = db.why(episode.id).unwrap().unwrap();
+        assert!(why
+            .related
+            .iter()
+            .any(|n| n.connected_via == "Bob" && n.memory.subject() == Some("Bob")));
+
+        // The same entity in another namespace is a separate node.
+        let other = ConchDB {
+            namespace: "other".to_string(),
+            ..db
+        };
+        let secret = other.remember_fact("Alice", "knows", "Mallory").unwrap();
+        let other_nodes = other.related("Alice", 3).unwrap();
+        assert_eq!(
+            other_nodes.iter().map(|n| n.memory.id).collect::<Vec<_>>(),
+            vec![secret.id]
+        );
+        let db = ConchDB {
+            namespace: "default".to_string(),
+            ..other
+        };
+        assert!(db
+            .related("Alice", 3)
+            .unwrap()
+            .iter()
+            .all(|n| n.memory.namespace == "default"));
+        let why = db.why(episode.id).unwrap().unwrap();
+        assert!(why.related.iter().all(|n| n.memory.id != secret.id));
+    }
+
+    #[test]
+    fn consolidation_summaries_keep_entity_mentions() {
+        let db = ConchDB::open_in_memory_with(Box::new(IdenticalEmbedder)).unwrap();
+        db.remember_fact("Alice", "knows", "Bob").unwrap();
+        db.remember_episode("Had coffee with Alice").unwrap();
+        db.remember_episode("Had coffee with Alice. She brought the Q3 plan.")
+            .unwrap();
+
+        let options = ConsolidateOptions {
+            mode: ConsolidateMode::Summarize,
+            ..Default::default()
+        };
+        let result = db.consolidate(false, &options, |_| {}).unwrap();
+        assert_eq!(result.summaries.len(), 1);
+        let summary = result.summaries[0];
+        assert_eq!(db.store().mentions_of(summary).unwrap(), vec!["Alice"]);
+        assert!(db
+            .related("Alice", 1)
+            .unwrap()
+            .iter()
+            .any(|n| n.memory.id == summary));
+    }
+
+    #[test]
+    fn reindexing_mentions_picks_up_new_fact_entities() {
+        let db = setup_graph_db();
+        let episode = db.remember_episode("booked tickets for ztorm").unwrap();
+        assert!(db.store().mentions_of(episode.id).unwrap().is_empty());
+
+        db.remember_fact("Alice", "likes", "ztorm").unwrap();
+        assert!(db.reindex_mentions().unwrap() >= 1);
+        assert_eq!(db.store().mentions_of(episode.id).unwrap(), vec!["ztorm"]);
+        assert!(db
+            .related("ztorm", 1)
+            .unwrap()
+            .iter()
+            .any(|n| n.memory.id == episode.id));
+        assert_eq!(db.mention_counts(10).unwrap()[0], ("ztorm".to_string(), 1));
+    }
+
+    #[test]
+    fn entity_registry_folds_spellings_into_one_node() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        db.remember_fact("Jared", "likes", "Rust").unwrap();
+        let lower = db.remember_fact("jared", "works_at", "Microsoft").unwrap();
+        assert_eq!(lower.subject(), Some("Jared"));
+        db.remember_fact("Jared Grimes", "lives_in", "Austin")
+            .unwrap();
+        assert_eq!(db.related("JARED", 1).unwrap().len(), 2);
+
+        assert!(matches!(
+            db.alias_entity("Jared Grimes", "Jared"),
+            Err(ConchError::InvalidArgument(_))
+        ));
+        let jared = db.merge_entities("Jared Grimes", "Jared").unwrap();
+        assert_eq!(jared.aliases, vec!["Jared Grimes"]);
+        assert_eq!(jared.facts, 3);
+        assert_eq!(db.related("jared grimes", 1).unwrap().len(), 3);
+        assert!(db.verify().unwrap().corrupted.is_empty());
+
+        // Writes under an alias land on the canonical entity.
+        let via_alias = db
+            .remember_fact("Jared Grimes", "has_pet", "Tortellini")
+            .unwrap();
+        assert_eq!(via_alias.subject(), Some("Jared"));
+
+        db.alias_entity("JG", "Jared").unwrap();
+        assert_eq!(db.forget_by_subject("jg").unwrap(), 4);
+        assert!(db.related("Jared", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn relation_synonyms_turn_rewordings_into_updates() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        let relation = |rec: &MemoryRecord| match &rec.kind {
+            MemoryKind::Fact(f) => f.relation.clone(),
+            _ => panic!("expected a fact"),
+        };
+        let first = db.remember_fact("Jared", "Works At", "Microsoft").unwrap();
+        assert_eq!(relation(&first), "works_at");
+        let (moved, updated) = db.upsert_fact("Jared", "employed by", "Google").unwrap();
+        assert!(updated);
+        assert_eq!(moved.id, first.id);
+
+        let (gig, updated) = db.upsert_fact("Jared", "gig", "Bakery").unwrap();
+        assert!(!updated);
+        assert_eq!(db.add_relation_synonym("Gig", "employer").unwrap(), 1);
+        assert_eq!(
+            relation(&db.store().get_memory(gig.id).unwrap().unwrap()),
+            "works_at"
+        );
+        let (_, updated) = db.upsert_fact("Jared", "gig", "Cafe").unwrap();
+        assert!(updated);
+        assert!(matches!(
+            db.add_relation_synonym("works at", "employed_by"),
+            Err(ConchError::InvalidArgument(_))
+        ));
+
+        let works_at = db
+            .relations()
+            .unwrap()
+            .into_iter()
+            .find(|u| u.relation == "works_at")
+            .unwrap();
+        assert_eq!(works_at.facts, 2);
+        assert!(works_at.synonyms.contains(&"gig".to_string()));
+        assert!(works_at.synonyms.contains(&"employed_by".to_string()));
+        assert!(db.verify().unwrap().corrupted.is_empty());
+    }
+
+    #[test]
+    fn relation_schema_governs_fact_writes() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        db.set_relation_schema("lives in", Cardinality::One, ObjectType::Entity, None)
+            .unwrap();
+        db.set_relation_schema("has_pet", Cardinality::Many, ObjectType::Entity, None)
+            .unwrap();
+        db.set_relation_schema("age", Cardinality::One, ObjectType::Number, None)
+            .unwrap();
+        db.set_relation_schema(
+            "parent_of",
+            Cardinality::Many,
+            ObjectType::Entity,
+            Some("child of"),
+        )
+        .unwrap();
+
+        // Single-valued: the new object supersedes the old one.
+        let first = db.remember_fact("Ana", "lives_in", "Lisbon").unwrap();
+        let moved = db.remember_fact("Ana", "Lives In", "Porto").unwrap();
+        assert_eq!(moved.id, first.id);
+        assert_eq!(moved.object(), Some("Porto"));
+
+        // Multi-valued: upserts add, repeats do not.
+        db.upsert_fact("Ana", "has_pet", "Tortellini").unwrap();
+        let (_, updated) = db.upsert_fact("Ana", "has_pet", "Ravioli").unwrap();
+        assert!(!updated);
+        db.upsert_fact("Ana", "has_pet", "Ravioli").unwrap();
+        assert_eq!(db.facts_about("ana", Some("has_pet")).unwrap().len(), 2);
+
+        // Typed objects are validated and not registered as entities.
+        assert!(matches!(
+            db.remember_fact("Ana", "age", "forty"),
+            Err(ConchError::ValidationError { .. })
+        ));
+        db.remember_fact("Ana", "age", "41").unwrap();
+        assert!(db.entities().unwrap().iter().all(|e| e.name != "41"));
+
+        // Inverse facts read from the object's side.
+        db.remember_fact("Ana", "parent_of", "Bo").unwrap();
+        let views = db.facts_about("Bo", None).unwrap();
+        assert_eq!(views.len(), 1);
+        assert!(views[0].inverted);
+        assert_eq!(views[0].fact.relation, "child_of");
+        assert_eq!(views[0].fact.object, "Ana");
+        assert!(db.facts_about("Lisbon", None).unwrap().is_empty());
+    }
+
     #[test]
     fn related_empty_for_unknown_subject() {
         let db = setup_graph_db();
@@ -1832,4 +3304,54 @@ mod tests {
             "strength must be preserved through export/import"
         );
     }
+
+    #[test]
+    fn extraction_creates_weak_derived_facts_and_links_repeats() {
+        let db = ConchDB::open_in_memory_with(Box::new(OrthogonalEmbedder::new())).unwrap();
+        db.set_policy(&NamespacePolicy {
+            extract_facts: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let episode = db
+            .remember_episode("Jared started working at Microsoft last month")
+            .unwrap();
+        let facts: Vec<_> = db
+            .store()
+            .all_memories()
+            .unwrap()
+            .into_iter()
+            .filter(|m| matches!(&m.kind, MemoryKind::Fact(f) if f.relation == "works_at"))
+            .collect();
+        assert_eq!(facts.len(), 1);
+        let fact = &facts[0];
+        assert!((fact.strength - 0.6).abs() < 1e-9);
+        assert_eq!(fact.source.as_deref(), Some(extract::EXTRACTION_SOURCE));
+        assert!(db
+            .lineage(fact.id)
+            .unwrap()
+            .iter()
+            .any(|l| l.source_id == episode.id && l.relation == LineageRelation::DerivedFrom));
+
+        // A second episode restating the fact links to it instead of duplicating.
+        db.set_policy(&NamespacePolicy::default()).unwrap();
+        let second = db
+            .remember_episode("Jared works at Microsoft on the Azure team")
+            .unwrap();
+        let preview = db.extract_facts(true).unwrap();
+        assert_eq!(preview.scanned, 2);
+        assert!(preview.candidates.iter().any(|c| c.source_id == second.id));
+        assert_eq!((preview.created, preview.linked), (0, 0));
+
+        let report = db.extract_facts(false).unwrap();
+        assert_eq!((report.created, report.linked), (0, 1));
+        assert!(db
+            .lineage(fact.id)
+            .unwrap()
+            .iter()
+            .any(|l| l.source_id == second.id));
+        assert_eq!(db.extract_facts(false).unwrap().linked, 0);
+        assert_eq!(db.stats().unwrap().total_memories, 3);
+    }
 }
@@ -0,0 +1,81 @@
+/// Random-hyperplane locality-sensitive hashing (SimHash) for cosine
+/// similarity.
+///
+/// Each embedding gets `BANDS` band keys of `ROWS_PER_BAND` sign bits each.
+/// Two embeddings at angle θ agree on one bit with probability `1 - θ/π`,
+/// so near-duplicates almost always share at least one band key while
+/// unrelated memories rarely do. Consolidation compares only memories that
+/// share a key instead of every pair.
+pub(crate) const BANDS: usize = 16;
+pub(crate) const ROWS_PER_BAND: usize = 8;
+
+/// Fixed seed so band keys are stable across runs and can be stored.
+const SEED: u64 = 0x636f_6e63_685f_6c73;
+
+pub(crate) struct SimHasher {
+    /// `BANDS * ROWS_PER_BAND` hyperplanes of the embedding dimension.
+    planes: Vec<Vec<f32>>,
+}
+
+impl SimHasher {
+    pub(crate) fn new(dimension: usize) -> Self {
+        let mut state = SEED ^ dimension as u64;
+        let planes = (0..BANDS * ROWS_PER_BAND)
+            .map(|_| {
+                (0..dimension)
+                    .map(|_| gaussian(&mut state) as f32)
+                    .collect()
+            })
+            .collect();
+        Self { planes }
+    }
+
+    /// One key per band: the band's sign bits packed into an integer.
+    pub(crate) fn band_keys(&self, embedding: &[f32]) -> Vec<i64> {
+        self.planes
+            .chunks(ROWS_PER_BAND)
+            .map(|band| {
+                band.iter().fold(0i64, |key, plane| {
+                    let dot: f32 = plane.iter().zip(embedding).map(|(p, e)| p * e).sum();
+                    (key << 1) | i64::from(dot >= 0.0)
+                })
+            })
+            .collect()
+    }
+}
+
+/// SplitMix64: small, fast and good enough to draw hyperplanes.
+pub(crate) fn next_u64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Standard normal sample (Box-Muller), so hyperplane normals are uniform
+/// over directions.
+fn gaussian(state: &mut u64) -> f64 {
+    let uniform = |state: &mut u64| ((next_u64(state) >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+    let (u1, u2) = (uniform(state), uniform(state));
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_deterministic_and_shared_by_near_duplicates() {
+        let hasher = SimHasher::new(3);
+        let a = hasher.band_keys(&[1.0, 0.0, 0.0]);
+        assert_eq!(a.len(), BANDS);
+        assert_eq!(a, SimHasher::new(3).band_keys(&[1.0, 0.0, 0.0]));
+
+        let near = hasher.band_keys(&[0.99, 0.1, 0.0]);
+        assert!(a.iter().zip(&near).any(|(x, y)| x == y));
+
+        let opposite = hasher.band_keys(&[-1.0, 0.0, 0.0]);
+        assert!(a.iter().zip(&opposite).all(|(x, y)| x != y));
+    }
+}
@@ -56,6 +56,9 @@ pub struct MemoryRecord {
     pub checksum: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub temporal: Option<TemporalMetadata>,
+    /// Detected language as an ISO 639-3 code (e.g. `"deu"`), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 fn default_importance() -> f64 {
@@ -141,6 +144,34 @@ pub struct ExportData {
     pub memories: Vec<MemoryRecord>,
 }
 
+// ── Embedding backfill types ────────────────────────────────
+
+/// Progress snapshot emitted after each batch of an embedding backfill.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EmbedProgress {
+    /// Memories attempted so far (embedded + failed).
+    pub processed: usize,
+    /// Memories that were missing an embedding when the pass started.
+    pub total: usize,
+    pub embedded: usize,
+    pub failed: usize,
+}
+
+/// A memory that could not be embedded during a backfill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedFailure {
+    pub id: i64,
+    pub error: String,
+}
+
+/// Outcome of an embedding backfill pass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbedReport {
+    pub embedded: usize,
+    pub batches: usize,
+    pub failures: Vec<EmbedFailure>,
+}
+
 // ── Audit log types ─────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -232,10 +263,197 @@ pub struct GraphNode {
     pub memory: MemoryRecord,
     /// How many hops from the query subject (0 = direct match).
     pub depth: usize,
-    /// The entity (subject or object) that connects this node to the previous hop.
+    /// The entity (fact subject or object, or a mention) that connects this node to the previous hop.
     pub connected_via: String,
 }
 
+/// A memory that tends to be recalled together with another one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Association {
+    pub memory: MemoryRecord,
+    /// Co-recall weight, decayed to the time of the query.
+    pub weight: f64,
+    /// How many recalls returned both memories.
+    pub count: i64,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// A co-recall edge from one memory to a partner, as stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoRecallLink {
+    /// The partner memory.
+    pub memory_id: i64,
+    /// Co-recall weight, decayed to the time of the query.
+    pub weight: f64,
+    /// How many recalls returned both memories.
+    pub count: i64,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Tag added to memories an agent reported as wrong.
+pub const NEEDS_REVIEW_TAG: &str = "needs_review";
+
+/// An agent's verdict on a memory returned by recall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feedback {
+    /// The memory helped: reinforce it as a recall would.
+    Useful,
+    /// The memory did not belong in the results: undo the recall touch boost.
+    Irrelevant,
+    /// The memory is incorrect: demote it and tag it for review.
+    Wrong,
+}
+
+impl Feedback {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Feedback::Useful => "useful",
+            Feedback::Irrelevant => "irrelevant",
+            Feedback::Wrong => "wrong",
+        }
+    }
+}
+
+impl std::fmt::Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Feedback {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "useful" => Ok(Feedback::Useful),
+            "irrelevant" => Ok(Feedback::Irrelevant),
+            "wrong" => Ok(Feedback::Wrong),
+            other => Err(format!(
+                "unknown feedback \"{other}\" (expected useful, irrelevant or wrong)"
+            )),
+        }
+    }
+}
+
+/// How a derived memory relates to one of its sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineageRelation {
+    /// A summary built from a cluster of memories, which were archived.
+    DerivedFrom,
+}
+
+impl LineageRelation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineageRelation::DerivedFrom => "derived_from",
+        }
+    }
+}
+
+impl std::fmt::Display for LineageRelation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LineageRelation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "derived_from" => Ok(LineageRelation::DerivedFrom),
+            other => Err(format!("unknown lineage relation \"{other}\"")),
+        }
+    }
+}
+
+/// A link from a derived memory to a memory it was built from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageLink {
+    /// The derived memory.
+    pub memory_id: i64,
+    /// The source it was built from.
+    pub source_id: i64,
+    pub relation: LineageRelation,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Bookkeeping for consolidation in one namespace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationState {
+    /// Highest memory id covered by the last consolidation pass; incremental
+    /// passes only compare memories added after it.
+    pub consolidated_through: i64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A group of memories about one theme, found by topic clustering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Topic {
+    pub id: i64,
+    pub namespace: String,
+    /// The top keywords, comma separated.
+    pub label: String,
+    /// Keywords best describing the topic, best first.
+    pub keywords: Vec<String>,
+    /// Unarchived memories in the topic.
+    pub size: usize,
+    /// Mean strength of those memories.
+    pub strength: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A canonical entity in the registry. Fact subjects and objects are stored
+/// under the canonical name; aliases resolve to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    pub id: i64,
+    pub namespace: String,
+    /// Canonical spelling.
+    pub name: String,
+    /// Other names that resolve to this entity.
+    pub aliases: Vec<String>,
+    /// Facts naming the entity as subject or object.
+    pub facts: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A fact read from one entity's side, possibly through the inverse of the
+/// stored relation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactView {
+    /// The stored fact this view reads.
+    pub memory_id: i64,
+    pub fact: Fact,
+    /// Whether `fact` is the inverse reading of the stored fact.
+    pub inverted: bool,
+}
+
+/// A canonical relation with how often facts use it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationUsage {
+    pub relation: String,
+    /// Facts using the relation.
+    pub facts: usize,
+    /// Names normalized to this relation on write, built-in and user-defined.
+    pub synonyms: Vec<String>,
+}
+
+/// A logged relevance judgement, kept for evaluation and tuning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    pub id: i64,
+    pub memory_id: i64,
+    pub namespace: String,
+    pub query: String,
+    pub feedback: Feedback,
+    pub strength_before: f64,
+    pub strength_after: f64,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Provenance information for a single memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProvenanceInfo {
@@ -250,8 +468,14 @@ pub struct ProvenanceInfo {
     pub session_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub channel: Option<String>,
-    /// 1-hop related facts for context.
+    /// 1-hop related memories for context.
     pub related: Vec<GraphNode>,
+    /// When the memory was archived by consolidation, if it was.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<String>,
+    /// Lineage links where this memory is the derived memory or a source.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lineage: Vec<LineageLink>,
 }
 
 impl MemoryRecord {
@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::expand::STOPWORDS;
+use crate::extract::{words, Word, MAX_NAME_WORDS};
+use crate::summarize::split_sentences;
+
+/// Names to look for in text, with the canonical name of each alias.
+pub(crate) struct MentionVocabulary {
+    pub(crate) known: Vec<String>,
+    /// Canonical entity name by lowercased alias.
+    pub(crate) canonical: HashMap<String, String>,
+}
+
+impl MentionVocabulary {
+    /// Mentions in `text`, with aliases resolved to their canonical names.
+    pub(crate) fn detect(&self, text: &str) -> Vec<String> {
+        let mut found: Vec<String> = Vec::new();
+        for mention in detect_mentions(text, &self.known) {
+            let mention = self
+                .canonical
+                .get(&mention.to_lowercase())
+                .cloned()
+                .unwrap_or(mention);
+            if !found.iter().any(|f| f.eq_ignore_ascii_case(&mention)) {
+                found.push(mention);
+            }
+        }
+        found
+    }
+}
+
+/// Entities mentioned in `text`, each once, in order of first appearance.
+///
+/// Two kinds of span count as a mention:
+/// - any of the `known` entities (usually fact subjects and objects), matched
+///   case-insensitively on word boundaries and reported in their known
+///   spelling;
+/// - runs of capitalized words such as "Ana Lima" or "Platform Team".
+///
+/// A capitalized word opening a sentence is usually just a capital letter
+/// ("Deployed the fix", "Met Ana Lima"), so it only counts as (part of) a
+/// mention when it also appears capitalized mid-sentence somewhere in `text`.
+pub fn detect_mentions(text: &str, known: &[String]) -> Vec<String> {
+    let known: Vec<(Vec<String>, &str)> = known
+        .iter()
+        .map(|k| {
+            let lowers: Vec<String> = words(k).into_iter().map(|w| w.lower).collect();
+            (lowers, k.as_str())
+        })
+        .filter(|(lowers, _)| lowers.iter().any(|w| !STOPWORDS.contains(&w.as_str())))
+        .collect();
+    let mut by_first: HashMap<&str, Vec<(&[String], &str)>> = HashMap::new();
+    for (lowers, name) in &known {
+        by_first
+            .entry(lowers[0].as_str())
+            .or_default()
+            .push((lowers.as_slice(), name));
+    }
+
+    let sentences: Vec<Vec<Word>> = split_sentences(text).iter().map(|s| words(s)).collect();
+    let mid_sentence_names: HashSet<&str> = sentences
+        .iter()
+        .flat_map(|ws| ws.iter().skip(1))
+        .filter(|w| w.is_name())
+        .map(|w| w.lower.as_str())
+        .collect();
+
+    let mut mentions: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for ws in &sentences {
+        let mut i = 0;
+        while i < ws.len() {
+            // Longest known entity starting here.
+            let known_match = by_first
+                .get(ws[i].lower.as_str())
+                .into_iter()
+                .flatten()
+                .filter(|(lowers, _)| {
+                    ws.len() >= i + lowers.len()
+                        && ws[i..i + lowers.len()]
+                            .iter()
+                            .zip(lowers.iter())
+                            .all(|(w, k)| w.lower == *k)
+                })
+                .max_by_key(|(lowers, _)| lowers.len());
+            if let Some((lowers, name)) = known_match {
+                if seen.insert(name.to_lowercase()) {
+                    mentions.push(name.to_string());
+                }
+                i += lowers.len();
+                continue;
+            }
+
+            if !ws[i].is_name() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < ws.len() && i - start < MAX_NAME_WORDS && ws[i].is_name() {
+                i += 1;
+                if ws[i - 1].ends_clause {
+                    break;
+                }
+            }
+            let mut span = &ws[start..i];
+            if start == 0 && !mid_sentence_names.contains(span[0].lower.as_str()) {
+                span = &span[1..];
+            }
+            if span.is_empty() {
+                continue;
+            }
+            let name = span
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if seen.insert(name.to_lowercase()) {
+                mentions.push(name);
+            }
+        }
+    }
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_capitalized_spans() {
+        assert_eq!(
+            detect_mentions("Met Ana Lima and the Platform Team at lunch.", &[]),
+            vec!["Ana Lima", "Platform Team"]
+        );
+    }
+
+    #[test]
+    fn sentence_initial_capital_needs_support() {
+        assert!(detect_mentions("Deployed the fix to staging.", &[]).is_empty());
+        assert_eq!(
+            detect_mentions("Jared reviewed it. Thanks to Jared it shipped.", &[]),
+            vec!["Jared"]
+        );
+        assert!(detect_mentions("We met today. It went well.", &[]).is_empty());
+    }
+
+    #[test]
+    fn known_entities_match_any_case_in_their_own_spelling() {
+        assert_eq!(
+            detect_mentions(
+                "Jared fixed the espresso machine for tortellini.",
+                &known(&["Jared", "Tortellini", "espresso machine", "the"])
+            ),
+            vec!["Jared", "espresso machine", "Tortellini"]
+        );
+    }
+
+    #[test]
+    fn mentions_are_reported_once() {
+        assert_eq!(
+            detect_mentions("Call Microsoft. Then email Microsoft, and Bob.", &[]),
+            vec!["Microsoft", "Bob"]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::recall::{ContextWeights, FusionStrategy, RecallScoreCoefficients};
+
+/// Recall and write settings persisted per namespace.
+///
+/// Fields left at their defaults keep the built-in behaviour; options passed
+/// to an individual recall call take precedence over the namespace policy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NamespacePolicy {
+    /// Run the cross-encoder rerank stage on every recall in this namespace.
+    #[serde(default)]
+    pub rerank: bool,
+    /// Fusion strategy for this namespace, e.g. keyword-dominant for
+    /// identifier-heavy memories. `None` uses the default weighted RRF.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<FusionStrategy>,
+    /// Language (ISO 639-3) assumed for memories and queries whose language
+    /// could not be detected. `None` uses English.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Score exponents for this namespace, usually fitted by `conch tune`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coefficients: Option<RecallScoreCoefficients>,
+    /// Boost weights for memories sharing the caller's recall context.
+    /// `None` uses the built-in defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_weights: Option<ContextWeights>,
+    /// Extract candidate facts from episodes and actions as they are
+    /// written (see [`crate::extract`]).
+    #[serde(default)]
+    pub extract_facts: bool,
+}
@@ -1,20 +1,148 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use chrono::Utc;
 
 use crate::embed::{cosine_similarity, Embedder};
-use crate::memory::{MemoryKind, MemoryRecord};
+use crate::expand::{expand_query_with_aliases, mentions_name, tokenize, QueryExpansion};
+use crate::language::{bm25_language, normalize_language, DEFAULT_LANGUAGE};
+use crate::memory::{Feedback, MemoryKind, MemoryRecord};
+use crate::rerank::Reranker;
+use crate::session::{priming_keys, SessionState, PRIMING_FACTOR};
 use crate::store::MemoryStore;
 
-/// Minimum cosine similarity threshold for vector search results.
+/// Vector similarity cutoff used when there are too few embedded memories to
+/// estimate the query's score distribution.
 const VECTOR_SIMILARITY_THRESHOLD: f32 = 0.3;
 
+/// Bounds on the adaptive vector similarity cutoff.
+const VECTOR_THRESHOLD_FLOOR: f32 = 0.2;
+const VECTOR_THRESHOLD_CEILING: f32 = 0.5;
+
+/// Standard deviations above the mean similarity a memory must reach to count
+/// as a vector match.
+const VECTOR_THRESHOLD_Z: f32 = 1.0;
+
+/// Fewest embedded memories for which the adaptive cutoff is estimated.
+const VECTOR_THRESHOLD_MIN_SAMPLES: usize = 3;
+
 /// RRF constant k — standard value used by Elasticsearch, Qdrant, etc.
 const RRF_K: f64 = 60.0;
 
+/// How keyword (BM25) and vector rankings are combined into one relevance score.
+///
+/// Expansion probe rankings follow the same strategy, scaled by the expansion
+/// weight. Parses from `rrf[:k=60,keyword=1,vector=1]`, `convex[:keyword=0.5]`,
+/// `vector` and `keyword`.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum FusionStrategy {
+    /// Weighted Reciprocal Rank Fusion: `Σ weight / (k + rank)`.
+    Rrf {
+        k: f64,
+        keyword_weight: f64,
+        vector_weight: f64,
+    },
+    /// Convex combination of min-max normalised raw scores:
+    /// `keyword_weight × bm25 + (1 − keyword_weight) × cosine`.
+    Convex { keyword_weight: f64 },
+    /// Rank by vector similarity alone.
+    VectorOnly,
+    /// Rank by BM25 alone.
+    KeywordOnly,
+}
+
+impl Default for FusionStrategy {
+    fn default() -> Self {
+        FusionStrategy::Rrf {
+            k: RRF_K,
+            keyword_weight: 1.0,
+            vector_weight: 1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for FusionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FusionStrategy::Rrf {
+                k,
+                keyword_weight,
+                vector_weight,
+            } => write!(
+                f,
+                "rrf:k={k},keyword={keyword_weight},vector={vector_weight}"
+            ),
+            FusionStrategy::Convex { keyword_weight } => {
+                write!(f, "convex:keyword={keyword_weight}")
+            }
+            FusionStrategy::VectorOnly => write!(f, "vector"),
+            FusionStrategy::KeywordOnly => write!(f, "keyword"),
+        }
+    }
+}
+
+impl std::str::FromStr for FusionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, params) = match s.trim().split_once(':') {
+            Some((name, params)) => (name, params),
+            None => (s.trim(), ""),
+        };
+        let mut values: HashMap<&str, f64> = HashMap::new();
+        for pair in params.split(',').filter(|p| !p.trim().is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got \"{pair}\""))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number for {}: \"{value}\"", key.trim()))?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be a non-negative number", key.trim()));
+            }
+            values.insert(key.trim(), value);
+        }
+        let allow = |keys: &[&str]| match values.keys().find(|k| !keys.contains(k)) {
+            Some(key) => Err(format!("unknown parameter \"{key}\" for {name} fusion")),
+            None => Ok(()),
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "rrf" => {
+                allow(&["k", "keyword", "vector"])?;
+                Ok(FusionStrategy::Rrf {
+                    k: values.get("k").copied().unwrap_or(RRF_K),
+                    keyword_weight: values.get("keyword").copied().unwrap_or(1.0),
+                    vector_weight: values.get("vector").copied().unwrap_or(1.0),
+                })
+            }
+            "convex" => {
+                allow(&["keyword"])?;
+                let keyword_weight = values.get("keyword").copied().unwrap_or(0.5);
+                if keyword_weight > 1.0 {
+                    return Err("convex keyword weight must be within [0, 1]".to_string());
+                }
+                Ok(FusionStrategy::Convex { keyword_weight })
+            }
+            "vector" | "vector_only" => {
+                allow(&[])?;
+                Ok(FusionStrategy::VectorOnly)
+            }
+            "keyword" | "keyword_only" | "bm25" => {
+                allow(&[])?;
+                Ok(FusionStrategy::KeywordOnly)
+            }
+            other => Err(format!(
+                "unknown fusion strategy \"{other}\" (expected rrf, convex, vector or keyword)"
+            )),
+        }
+    }
+}
+
 /// Explainability metadata for recall ranking.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct RecallScoreExplain {
+    /// Fused relevance score (an RRF score unless `fusion` says otherwise).
     pub rrf_score: f64,
     pub rrf_rank: usize,
     pub rrf_rank_percentile: f64,
@@ -22,18 +150,90 @@ pub struct RecallScoreExplain {
     pub bm25_score: Option<f32>,
     pub vector_rank: Option<usize>,
     pub vector_similarity: Option<f32>,
+    /// Similarity cutoff vector search applied for this query.
+    pub vector_threshold: f32,
     pub modality_agreement: bool,
     pub matched_modalities: usize,
+    /// Fusion strategy that combined the rankings.
+    pub fusion: FusionStrategy,
+    /// Keyword fields the query matched, plus `exact_subject` / `exact_object`
+    /// when the query names the fact's subject or object outright.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keyword_fields: Vec<String>,
+    /// Expansion probe (an entity from the fact graph) that found this memory, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expansion_probe: Option<String>,
+    /// Graph hop at which spreading activation added this memory. Graph-sourced
+    /// memories were not among the fused candidates and have `rrf_rank` 0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_hop: Option<usize>,
+    /// Entity through which spreading activation reached this memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_via: Option<String>,
     pub decayed_strength: f64,
     pub recency_boost: f64,
     pub access_weight: f64,
+    /// Cross-encoder relevance in [0, 1], when the rerank stage scored this memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f64>,
     pub base_score: f64,
     pub spread_boost: f64,
+    /// Boost from Hebbian co-recall associations with the top results.
+    pub co_recall_boost: f64,
+    /// Boost from entities and tags primed by earlier recalls in the session.
+    pub session_boost: f64,
+    /// Boost for sharing the caller's channel, source, session or working
+    /// directory, when a recall context was given and matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_boost: Option<ContextBoost>,
     pub temporal_boost: f64,
+    /// MMR penalty for similarity to higher-ranked results, when diversification ran.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diversity_penalty: Option<f64>,
     pub score_margin_to_next: Option<f64>,
     pub final_score: f64,
 }
 
+/// Per-call recall options. `RecallOptions::default()` behaves like [`recall`].
+#[derive(Debug, Clone, Default)]
+pub struct RecallOptions {
+    /// Only consider memories carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Run the cross-encoder rerank stage. `None` defers to the namespace policy.
+    pub rerank: Option<bool>,
+    /// Skip query expansion through the fact graph.
+    pub disable_expansion: bool,
+    /// Graph hops for spreading activation beyond the candidates
+    /// (default [`DEFAULT_SPREAD_HOPS`]; 0 only re-scores candidates).
+    pub spread_hops: Option<usize>,
+    /// Fixed vector similarity cutoff instead of the adaptive one.
+    pub vector_threshold: Option<f32>,
+    /// Diversify the final list with maximal marginal relevance, using this
+    /// lambda (1.0 = pure relevance, 0.0 = pure novelty). `None` disables it.
+    pub diversity: Option<f64>,
+    /// How keyword and vector rankings are fused (default weighted RRF).
+    pub fusion: Option<FusionStrategy>,
+    /// Language for memories whose language was not detected at write time
+    /// (default [`DEFAULT_LANGUAGE`]).
+    pub language: Option<String>,
+    /// Score without side effects: recalled memories are not reinforced and
+    /// no co-recall associations are learned. Used by evaluation.
+    pub peek: bool,
+    /// Score exponents. `None` defers to the namespace policy, then the
+    /// `CONCH_RECALL_*_EXP` environment variables.
+    pub coefficients: Option<RecallScoreCoefficients>,
+    /// Recall session. Memories already returned in the session are skipped,
+    /// and entities and tags from its recent recalls prime the scores.
+    pub session_id: Option<String>,
+    /// The caller's current channel, source, session and working directory;
+    /// memories learned in the same context are boosted.
+    pub context: Option<RecallContext>,
+    /// Boost per matching context field. `None` defers to the namespace policy.
+    pub context_weights: Option<ContextWeights>,
+    /// Only consider members of this topic (see [`crate::topics`]).
+    pub topic: Option<i64>,
+}
+
 /// A recalled memory with its relevance score.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct RecallResult {
@@ -55,6 +255,9 @@ const FACT_TOUCH_BOOST: f64 = 0.10;
 const EPISODE_TOUCH_BOOST: f64 = 0.20;
 const ACTION_TOUCH_BOOST: f64 = 0.25;
 
+/// Strength multiplier for a memory an agent reported as wrong.
+const WRONG_FEEDBACK_FACTOR: f64 = 0.5;
+
 /// Overfetch multiplier for candidate reranking.
 const CANDIDATE_MULTIPLIER: usize = 10;
 const MIN_CANDIDATES: usize = 50;
@@ -63,7 +266,7 @@ const MIN_CANDIDATES: usize = 50;
 ///
 /// Final base score formula:
 /// rrf^rrf_exp * decayed_strength^decay_exp * recency_boost^recency_exp * access_weight^access_exp
-#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct RecallScoreCoefficients {
     pub rrf_exp: f64,
     pub decay_exp: f64,
@@ -82,9 +285,159 @@ impl Default for RecallScoreCoefficients {
     }
 }
 
+/// Where a recall is made from. Memories remembered with the same channel,
+/// source or session, or about the same project as the working directory,
+/// are favoured.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecallContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+}
+
+impl RecallContext {
+    pub fn is_empty(&self) -> bool {
+        self.channel.is_none()
+            && self.source.is_none()
+            && self.session_id.is_none()
+            && self.cwd.is_none()
+    }
+}
+
+/// Score multiplier bonus for each context field a memory shares with the
+/// recall: a memory from the caller's channel scores `1 + channel` times higher.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ContextWeights {
+    pub channel: f64,
+    pub source: f64,
+    pub session: f64,
+    pub cwd: f64,
+}
+
+impl Default for ContextWeights {
+    fn default() -> Self {
+        Self {
+            channel: 0.3,
+            source: 0.1,
+            session: 0.2,
+            cwd: 0.2,
+        }
+    }
+}
+
+impl std::fmt::Display for ContextWeights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "channel={},source={},session={},cwd={}",
+            self.channel, self.source, self.session, self.cwd
+        )
+    }
+}
+
+impl std::str::FromStr for ContextWeights {
+    type Err = String;
+
+    /// Parses `channel=0.5,cwd=0`; fields left out keep their defaults.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = ContextWeights::default();
+        for pair in s.split(',').filter(|p| !p.trim().is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got \"{pair}\""))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number for {}: \"{value}\"", key.trim()))?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be a non-negative number", key.trim()));
+            }
+            match key.trim() {
+                "channel" => weights.channel = value,
+                "source" => weights.source = value,
+                "session" => weights.session = value,
+                "cwd" => weights.cwd = value,
+                other => {
+                    return Err(format!(
+                    "unknown context weight \"{other}\" (expected channel, source, session or cwd)"
+                ))
+                }
+            }
+        }
+        Ok(weights)
+    }
+}
+
+/// Score added for each context field a recalled memory matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize)]
+pub struct ContextBoost {
+    pub channel: f64,
+    pub source: f64,
+    pub session: f64,
+    pub cwd: f64,
+}
+
+impl ContextBoost {
+    pub fn total(&self) -> f64 {
+        self.channel + self.source + self.session + self.cwd
+    }
+}
+
+/// BM25 weight of each keyword field. Fact subjects and objects name entities
+/// and outweigh the relation; tags are curated and get a boost.
+const KEYWORD_FIELD_WEIGHTS: [(&str, f32); 6] = [
+    ("subject", 1.5),
+    ("object", 1.5),
+    ("relation", 0.5),
+    ("text", 1.0),
+    ("tags", 1.2),
+    ("source", 0.5),
+];
+
+/// Keyword score bonus when the query names a fact's subject or object exactly.
+const EXACT_SUBJECT_BONUS: f32 = 0.3;
+const EXACT_OBJECT_BONUS: f32 = 0.15;
+
+/// RRF weight of the expansion probe rankings relative to the raw query.
+const EXPANSION_WEIGHT: f64 = 0.5;
+
+/// Number of top fused candidates scored by the cross-encoder.
+const RERANK_CANDIDATES: usize = 20;
+
+/// Share of the relevance term taken from the cross-encoder; the rest stays RRF.
+const RERANK_WEIGHT: f64 = 0.7;
+
 /// Spreading activation: fraction of a memory's score given to graph neighbors.
 const SPREAD_FACTOR: f64 = 0.15;
 
+/// Default number of graph hops followed beyond the candidate set.
+pub const DEFAULT_SPREAD_HOPS: usize = 2;
+
+/// Activation kept at each additional hop.
+const SPREAD_HOP_ATTENUATION: f64 = 0.5;
+
+/// Number of top results whose entities seed multi-hop spreading.
+const SPREAD_SEEDS: usize = 5;
+
+/// Default MMR lambda for diversified recall.
+pub const DEFAULT_DIVERSITY_LAMBDA: f64 = 0.7;
+
+/// Upper bound on memories spreading activation may add per recall.
+const MAX_GRAPH_ADDITIONS: usize = 20;
+
+/// Co-activation: fraction of an anchor's score given to memories it is
+/// habitually recalled with, scaled by the saturated association weight.
+const CO_RECALL_FACTOR: f64 = 0.10;
+
+/// Number of top results whose co-recall associations are followed.
+const CO_RECALL_ANCHORS: usize = 5;
+
 /// Recency boost half-life in hours (7 days). Memories newer than this get a
 /// meaningful boost; older ones taper towards a floor.
 const RECENCY_HALF_LIFE_HOURS: f64 = 168.0;
@@ -96,12 +449,17 @@ const RECENCY_FLOOR: f64 = 0.3;
 /// enhanced with brain-inspired scoring heuristics.
 ///
 /// Pipeline:
-/// 1. BM25 search (keyword relevance)
-/// 2. Vector search (semantic relevance, cosine sim > threshold)
-/// 3. RRF fusion of both rankings
-/// 4. Base score = RRF × decayed_strength × recency_boost × access_weight
-/// 5. 1-hop spreading activation through the knowledge graph
-/// 6. Temporal co-occurrence boost for memories created near top results
+/// 1. Field-weighted BM25 search (keyword relevance)
+/// 2. Vector search (semantic relevance, cosine sim ≥ a per-query adaptive cutoff)
+/// 3. Both searches again for entities the query names and their graph neighbours
+/// 4. Fusion of all rankings (weighted RRF by default, see [`FusionStrategy`])
+/// 5. Optional cross-encoder rerank of the top fused candidates
+/// 6. Base score = relevance × decayed_strength × recency_boost × access_weight
+/// 7. Spreading activation through the knowledge graph: candidates sharing an
+///    entity boost each other, and facts up to `spread_hops` away are added
+/// 8. Co-recall boost for memories habitually recalled with the top results
+/// 9. Temporal co-occurrence boost for memories created near top results
+/// 10. Optional maximal marginal relevance diversification of the final list
 ///
 /// Recalled memories are "touched" (decay is applied, then reinforced, and
 /// access count bumped).
@@ -133,21 +491,65 @@ pub fn recall_with_tag_filter_ns(
     limit: usize,
     tag_filter: Option<&str>,
     namespace: &str,
+) -> Result<Vec<RecallResult>, RecallError> {
+    let options = RecallOptions {
+        tag: tag_filter.map(str::to_string),
+        ..Default::default()
+    };
+    recall_with_options(store, query, embedder, None, limit, namespace, &options)
+}
+
+/// The full recall pipeline with per-call options.
+///
+/// When a `reranker` is supplied, the top fused candidates are re-scored by the
+/// cross-encoder and its score is blended into the relevance term of the base
+/// score, so decay, recency and access weighting still apply on top of it.
+pub fn recall_with_options(
+    store: &MemoryStore,
+    query: &str,
+    embedder: &dyn Embedder,
+    reranker: Option<&dyn Reranker>,
+    limit: usize,
+    namespace: &str,
+    options: &RecallOptions,
 ) -> Result<Vec<RecallResult>, RecallError> {
     let mut all_memories = store
         .all_memories_with_text_ns(namespace)
         .map_err(RecallError::Db)?;
 
+    // Expand against the whole namespace graph, before any tag or topic filter
+    // narrows it.
+    let expansion = if options.disable_expansion {
+        QueryExpansion::default()
+    } else {
+        let aliases = store
+            .entity_aliases_ns(namespace)
+            .map_err(RecallError::Db)?;
+        expand_query_with_aliases(query, &all_memories, &aliases)
+    };
+
     // If a tag filter is specified, only keep memories that have the tag.
-    if let Some(tag) = tag_filter {
+    if let Some(tag) = options.tag.as_deref() {
         all_memories.retain(|(mem, _)| mem.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
     }
+    if let Some(topic) = options.topic {
+        let members = store.topic_member_ids(topic).map_err(RecallError::Db)?;
+        all_memories.retain(|(mem, _)| members.contains(&mem.id));
+    }
 
     if all_memories.is_empty() {
         return Ok(vec![]);
     }
 
     let now = Utc::now();
+    let session: Option<SessionState> = match options.session_id.as_deref() {
+        Some(id) => Some(
+            store
+                .recall_session(id, namespace, now)
+                .map_err(RecallError::Db)?,
+        ),
+        None => None,
+    };
 
     // Find the maximum access_count for normalization.
     let max_access = all_memories
@@ -156,7 +558,9 @@ pub fn recall_with_tag_filter_ns(
         .max()
         .unwrap_or(0);
 
-    let coeffs = recall_score_coefficients_from_env();
+    let coeffs = options
+        .coefficients
+        .unwrap_or_else(recall_score_coefficients_from_env);
 
     // Overfetch candidates, then rerank with full score (including decay,
     // recency, and access weighting) to avoid top-K cutoff errors.
@@ -165,27 +569,64 @@ pub fn recall_with_tag_filter_ns(
         .min(all_memories.len());
 
     // BM25
-    let bm25_ranked = bm25_search(query, &all_memories, candidate_count);
+    let default_language = options
+        .language
+        .as_deref()
+        .and_then(normalize_language)
+        .unwrap_or(DEFAULT_LANGUAGE);
+    let keyword_index = KeywordIndex::new(&all_memories, default_language);
+    let bm25_hits = keyword_index.search_fields(query, candidate_count);
+    let bm25_ranked: Vec<(usize, f32)> = bm25_hits.iter().map(|h| (h.idx, h.score)).collect();
     let bm25_meta: HashMap<usize, (usize, f32)> = bm25_ranked
         .iter()
         .enumerate()
         .map(|(rank, (idx, score))| (*idx, (rank + 1, *score)))
         .collect();
+    let mut bm25_fields: HashMap<usize, Vec<String>> = bm25_hits
+        .into_iter()
+        .map(|h| (h.idx, h.fields.into_iter().map(str::to_string).collect()))
+        .collect();
 
     // Vector
     let query_embedding = embedder
         .embed_one(query)
         .map_err(|e| RecallError::Embedding(e.to_string()))?;
-    let vector_ranked = vector_search(&query_embedding, &all_memories, candidate_count);
+    let (vector_ranked, vector_threshold) = vector_search(
+        &query_embedding,
+        &all_memories,
+        candidate_count,
+        options.vector_threshold,
+    );
     let vector_meta: HashMap<usize, (usize, f32)> = vector_ranked
         .iter()
         .enumerate()
         .map(|(rank, (idx, sim))| (*idx, (rank + 1, *sim)))
         .collect();
 
-    // RRF fusion
-    let fused = rrf(&bm25_ranked, &vector_ranked);
-    let candidates = fused.into_iter().take(candidate_count).enumerate();
+    // Expansion probes
+    let probes = search_expansion_probes(
+        &expansion,
+        &keyword_index,
+        embedder,
+        &all_memories,
+        candidate_count,
+        options.vector_threshold,
+    )?;
+
+    // Fusion
+    let fusion = options.fusion.unwrap_or_default();
+    let fused: Vec<(usize, f64)> = fuse(fusion, &bm25_ranked, &vector_ranked, &probes)
+        .into_iter()
+        .take(candidate_count)
+        .collect();
+
+    // Cross-encoder rerank of the top fused candidates
+    let rerank_scores = match reranker {
+        Some(reranker) => rerank_candidates(reranker, query, &all_memories, &fused)?,
+        None => HashMap::new(),
+    };
+    let top_rrf = fused.first().map(|(_, score)| *score).unwrap_or(0.0);
+    let candidates = fused.into_iter().enumerate();
 
     // Score = RRF × decayed_strength × recency_boost × access_weight
     let mut results: Vec<RecallResult> = candidates
@@ -194,8 +635,13 @@ pub fn recall_with_tag_filter_ns(
             let decayed_strength = effective_strength(mem, now);
             let recency = recency_boost(mem, now);
             let access = access_weight(mem, max_access);
+            let rerank_score = rerank_scores.get(&idx).copied();
+            let relevance = match rerank_score {
+                Some(score) => rerank_relevance(rrf_score, top_rrf, score),
+                None => rrf_score,
+            };
             let base_score =
-                compute_base_score(rrf_score, decayed_strength, recency, access, coeffs);
+                compute_base_score(relevance, decayed_strength, recency, access, coeffs);
             let temporal_multiplier = temporal_relevance_multiplier(mem, now);
             let salience_multiplier = operational_salience_multiplier(mem, query, now);
             let scored = base_score * temporal_multiplier * salience_multiplier;
@@ -214,14 +660,30 @@ pub fn recall_with_tag_filter_ns(
                     bm25_score: bm25.map(|(_, score)| score),
                     vector_rank: vector.map(|(rank, _)| rank),
                     vector_similarity: vector.map(|(_, sim)| sim),
+                    vector_threshold,
                     modality_agreement: bm25.is_some() && vector.is_some(),
                     matched_modalities,
+                    fusion,
+                    keyword_fields: bm25_fields.remove(&idx).unwrap_or_default(),
+                    // Only credit the expansion for memories the query itself missed.
+                    expansion_probe: if bm25.is_none() && vector.is_none() {
+                        probes.found_by.get(&idx).cloned()
+                    } else {
+                        None
+                    },
+                    graph_hop: None,
+                    graph_via: None,
                     decayed_strength,
                     recency_boost: recency,
                     access_weight: access,
+                    rerank_score,
                     base_score,
                     spread_boost: 0.0,
+                    co_recall_boost: 0.0,
+                    session_boost: 0.0,
+                    context_boost: None,
                     temporal_boost: (scored - base_score).max(0.0),
+                    diversity_penalty: None,
                     score_margin_to_next: None,
                     final_score: base_score,
                 },
@@ -230,14 +692,77 @@ pub fn recall_with_tag_filter_ns(
         .collect();
 
     // ── Spreading activation ─────────────────────────────────
-    // For each scored Fact, boost other results that share a subject or object.
+    // Boost results that share an entity with another result: a fact's subject
+    // or object, or an entity another memory mentions.
     // This is 1-hop graph traversal inspired by Collins & Loftus (1975).
+    let result_ids: Vec<i64> = results.iter().map(|r| r.memory.id).collect();
+    let mentions = store
+        .mentions_for_ids(&result_ids)
+        .map_err(RecallError::Db)?;
     let before_spread: Vec<f64> = results.iter().map(|r| r.score).collect();
-    spread_activation(&mut results, SPREAD_FACTOR);
+    spread_activation(&mut results, &mentions, SPREAD_FACTOR);
     for (i, r) in results.iter_mut().enumerate() {
         r.explain.spread_boost = (r.score - before_spread[i]).max(0.0);
     }
 
+    // Follow the graph past the candidates: facts linked to the top results (or
+    // to entities the query names) join the results even without word overlap.
+    let hops = options.spread_hops.unwrap_or(DEFAULT_SPREAD_HOPS);
+    let additions = graph_neighbours(
+        store,
+        namespace,
+        &results,
+        &mentions,
+        &expansion.entities,
+        &all_memories,
+        hops,
+        now,
+    )?;
+    results.extend(additions.into_iter().map(|mut r| {
+        r.explain.vector_threshold = vector_threshold;
+        r.explain.fusion = fusion;
+        r
+    }));
+
+    // ── Co-recall (Hebbian) boost ────────────────────────────
+    // Memories that were often recalled together with the current top results
+    // get a boost, so recall learns real usage patterns over time.
+    let candidate_ids: Vec<i64> = results.iter().map(|r| r.memory.id).collect();
+    let co_weights = store
+        .co_recall_weights(&candidate_ids, now)
+        .map_err(RecallError::Db)?;
+    let before_co_recall: Vec<f64> = results.iter().map(|r| r.score).collect();
+    co_recall_boost(&mut results, &co_weights, CO_RECALL_FACTOR);
+    for (i, r) in results.iter_mut().enumerate() {
+        r.explain.co_recall_boost = (r.score - before_co_recall[i]).max(0.0);
+    }
+
+    // ── Session novelty and priming ──────────────────────────
+    // Memories the session has already seen are dropped so the caller gets new
+    // information; entities and tags it recalled recently prime the rest.
+    if let Some(session) = &session {
+        results.retain(|r| !session.seen.contains(&r.memory.id));
+        for r in results.iter_mut() {
+            let boost = r.score * PRIMING_FACTOR * session.activation_of(&r.memory);
+            r.score += boost;
+            r.explain.session_boost = boost;
+        }
+    }
+
+    // ── Context metadata boost ───────────────────────────────
+    // Memories learned in the caller's current channel, source, session or
+    // project are favoured: a question asked in #infra prefers #infra memories.
+    if let Some(context) = options.context.as_ref().filter(|c| !c.is_empty()) {
+        let weights = options.context_weights.unwrap_or_default();
+        for r in results.iter_mut() {
+            let boost = context_boost(&r.memory, context, &weights, r.score);
+            if boost.total() > 0.0 {
+                r.score += boost.total();
+                r.explain.context_boost = Some(boost);
+            }
+        }
+    }
+
     // ── Temporal co-occurrence boost ─────────────────────────
     // Memories created near the same time as high-scoring results get a small
     // boost, implementing Tulving's encoding specificity / contextual
@@ -250,6 +775,9 @@ pub fn recall_with_tag_filter_ns(
     }
 
     sort_recall_results(&mut results);
+    if let Some(lambda) = options.diversity {
+        results = diversify(results, limit, lambda);
+    }
     results.truncate(limit);
     for i in 0..results.len() {
         let margin = if i + 1 < results.len() {
@@ -260,6 +788,10 @@ pub fn recall_with_tag_filter_ns(
         results[i].explain.score_margin_to_next = margin;
     }
 
+    if options.peek {
+        return Ok(results);
+    }
+
     // Touch recalled memories: apply decay first, then reinforce.
     for result in &results {
         let mem = &result.memory;
@@ -283,9 +815,69 @@ pub fn recall_with_tag_filter_ns(
             .map_err(RecallError::Db)?;
     }
 
+    // Memories returned together strengthen their co-recall association.
+    let recalled_ids: Vec<i64> = results.iter().map(|r| r.memory.id).collect();
+    store
+        .record_co_recall(&recalled_ids, namespace, now)
+        .map_err(RecallError::Db)?;
+
+    if let Some(session_id) = options.session_id.as_deref() {
+        let mut primed: Vec<String> = results
+            .iter()
+            .flat_map(|r| priming_keys(&r.memory))
+            .collect();
+        primed.sort();
+        primed.dedup();
+        store
+            .record_recall_session(session_id, namespace, &recalled_ids, &primed, now)
+            .map_err(RecallError::Db)?;
+    }
+
     Ok(results)
 }
 
+/// Per-field boost for a memory scoring `score` that shares parts of the
+/// recall context. Channels compare without a leading `#`; the working
+/// directory matches a memory whose source or channel is that path, or which
+/// is tagged with or mentions the directory's name.
+fn context_boost(
+    mem: &MemoryRecord,
+    context: &RecallContext,
+    weights: &ContextWeights,
+    score: f64,
+) -> ContextBoost {
+    let same = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => {
+            let norm = |s: &str| s.trim().trim_start_matches('#').to_lowercase();
+            !a.trim().is_empty() && norm(a) == norm(b)
+        }
+        _ => false,
+    };
+    let in_cwd = context.cwd.as_deref().is_some_and(|cwd| {
+        let cwd = cwd.trim().trim_end_matches(['/', '\\']);
+        if cwd.is_empty() {
+            return false;
+        }
+        let at_path = |field: &Option<String>| {
+            field
+                .as_deref()
+                .is_some_and(|f| f.trim().trim_end_matches(['/', '\\']) == cwd)
+        };
+        let project = cwd.rsplit(['/', '\\']).next().unwrap_or(cwd);
+        at_path(&mem.source)
+            || at_path(&mem.channel)
+            || mem.tags.iter().any(|t| t.eq_ignore_ascii_case(project))
+            || mentions_name(&tokenize(&mem.text_for_embedding()), project)
+    });
+    let bonus = |matched: bool, weight: f64| if matched { score * weight } else { 0.0 };
+    ContextBoost {
+        channel: bonus(same(&context.channel, &mem.channel), weights.channel),
+        source: bonus(same(&context.source, &mem.source), weights.source),
+        session: bonus(same(&context.session_id, &mem.session_id), weights.session),
+        cwd: bonus(in_cwd, weights.cwd),
+    }
+}
+
 /// Recency boost: gentle sigmoid that favours recent memories without
 /// completely suppressing old ones. Independent of decay (which handles
 /// forgetting); this handles *preference* when scores are close.
@@ -328,29 +920,36 @@ fn access_weight(mem: &MemoryRecord, max_access: i64) -> f64 {
 /// receive a fractional boost proportional to the parent's score. This
 /// implements Collins & Loftus (1975) spreading activation: querying "Max"
 /// will also boost "Jared has_pet Max" and "Max visited vet".
-fn spread_activation(results: &mut Vec<RecallResult>, factor: f64) {
-    // Build index: subject/object → list of result indices.
-    let mut entity_index: HashMap<String, Vec<usize>> = HashMap::new();
-    for (i, r) in results.iter().enumerate() {
-        if let MemoryKind::Fact(f) = &r.memory.kind {
-            let subj = f.subject.to_lowercase();
-            let obj = f.object.to_lowercase();
-            entity_index.entry(subj).or_default().push(i);
-            entity_index.entry(obj).or_default().push(i);
+fn spread_activation(
+    results: &mut Vec<RecallResult>,
+    mentions: &HashMap<i64, Vec<String>>,
+    factor: f64,
+) {
+    // Build index: entity → list of result indices.
+    let entities: Vec<Vec<String>> = results
+        .iter()
+        .map(|r| {
+            linked_entities(&r.memory, mentions)
+                .into_iter()
+                .map(str::to_lowercase)
+                .collect()
+        })
+        .collect();
+    let mut entity_index: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, linked) in entities.iter().enumerate() {
+        for entity in linked {
+            entity_index.entry(entity).or_default().push(i);
         }
     }
 
     // Accumulate boosts (don't mutate while iterating).
     let mut boosts: HashMap<usize, f64> = HashMap::new();
     for (i, r) in results.iter().enumerate() {
-        if let MemoryKind::Fact(f) = &r.memory.kind {
-            let entities = [f.subject.to_lowercase(), f.object.to_lowercase()];
-            for entity in &entities {
-                if let Some(neighbors) = entity_index.get(entity) {
-                    for &ni in neighbors {
-                        if ni != i {
-                            *boosts.entry(ni).or_insert(0.0) += r.score * factor;
-                        }
+        for entity in &entities[i] {
+            if let Some(neighbors) = entity_index.get(entity.as_str()) {
+                for &ni in neighbors {
+                    if ni != i {
+                        *boosts.entry(ni).or_insert(0.0) += r.score * factor;
                     }
                 }
             }
@@ -365,6 +964,241 @@ fn spread_activation(results: &mut Vec<RecallResult>, factor: f64) {
     }
 }
 
+/// Entities a memory links to in the graph: a fact's subject and object, or
+/// the entities any other memory mentions (from `mentions`).
+fn linked_entities<'a>(
+    memory: &'a MemoryRecord,
+    mentions: &'a HashMap<i64, Vec<String>>,
+) -> Vec<&'a str> {
+    match &memory.kind {
+        MemoryKind::Fact(f) => vec![f.subject.as_str(), f.object.as_str()],
+        _ => mentions
+            .get(&memory.id)
+            .map(|m| m.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Multi-hop spreading activation beyond the candidate set.
+///
+/// Entities of the top results (and entities the query names) are activated
+/// with the score of the result they came from. Each hop follows
+/// [`MemoryStore::facts_involving_ns`] and
+/// [`MemoryStore::memories_mentioning_ns`] to new memories, which receive
+/// `activation × SPREAD_FACTOR × decayed_strength`; their other entities are
+/// activated for the next hop at `SPREAD_HOP_ATTENUATION` of the current
+/// level. Only memories in `memories` (i.e. passing the tag filter) that are
+/// not already results are returned.
+#[allow(clippy::too_many_arguments)]
+fn graph_neighbours(
+    store: &MemoryStore,
+    namespace: &str,
+    results: &[RecallResult],
+    mentions: &HashMap<i64, Vec<String>>,
+    query_entities: &[String],
+    memories: &[(MemoryRecord, String)],
+    hops: usize,
+    now: chrono::DateTime<Utc>,
+) -> Result<Vec<RecallResult>, RecallError> {
+    if hops == 0 || results.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let allowed: HashMap<i64, usize> = memories
+        .iter()
+        .enumerate()
+        .map(|(i, (m, _))| (m.id, i))
+        .collect();
+    let mut reached: HashSet<i64> = results.iter().map(|r| r.memory.id).collect();
+
+    let mut seeds: Vec<&RecallResult> = results.iter().collect();
+    seeds.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let top_score = seeds.first().map(|r| r.score).unwrap_or(0.0);
+
+    fn activate(frontier: &mut HashMap<String, f64>, entity: &str, activation: f64) {
+        let slot = frontier.entry(entity.to_string()).or_insert(0.0);
+        *slot = slot.max(activation);
+    }
+
+    let mut frontier: HashMap<String, f64> = HashMap::new();
+    for entity in query_entities {
+        activate(&mut frontier, entity, top_score);
+    }
+    for r in seeds.iter().take(SPREAD_SEEDS) {
+        for entity in linked_entities(&r.memory, mentions) {
+            activate(&mut frontier, entity, r.score);
+        }
+    }
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut added: Vec<RecallResult> = Vec::new();
+    for hop in 1..=hops {
+        let mut entities: Vec<(String, f64)> = frontier
+            .drain()
+            .filter(|(entity, _)| visited.insert(entity.clone()))
+            .collect();
+        if entities.is_empty() {
+            break;
+        }
+        entities.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut next: HashMap<String, f64> = HashMap::new();
+        for (entity, activation) in entities {
+            let mut linked = store
+                .facts_involving_ns(&entity, namespace)
+                .map_err(RecallError::Db)?;
+            linked.extend(
+                store
+                    .memories_mentioning_ns(&entity, namespace)
+                    .map_err(RecallError::Db)?,
+            );
+            let mentioning: Vec<i64> = linked
+                .iter()
+                .filter(|m| !matches!(m.kind, MemoryKind::Fact(_)))
+                .map(|m| m.id)
+                .collect();
+            let linked_mentions = store
+                .mentions_for_ids(&mentioning)
+                .map_err(RecallError::Db)?;
+            for memory in linked {
+                for other in linked_entities(&memory, &linked_mentions) {
+                    if !other.eq_ignore_ascii_case(&entity) {
+                        activate(&mut next, other, activation * SPREAD_HOP_ATTENUATION);
+                    }
+                }
+                let Some(&idx) = allowed.get(&memory.id) else {
+                    continue;
+                };
+                if !reached.insert(memory.id) {
+                    continue;
+                }
+                let mem = &memories[idx].0;
+                let decayed_strength = effective_strength(mem, now);
+                let score = activation * SPREAD_FACTOR * decayed_strength;
+                added.push(RecallResult {
+                    memory: mem.clone(),
+                    score,
+                    explain: RecallScoreExplain {
+                        rrf_score: 0.0,
+                        rrf_rank: 0,
+                        rrf_rank_percentile: 1.0,
+                        bm25_rank: None,
+                        bm25_score: None,
+                        vector_rank: None,
+                        vector_similarity: None,
+                        vector_threshold: 0.0,
+                        modality_agreement: false,
+                        matched_modalities: 0,
+                        fusion: FusionStrategy::default(),
+                        keyword_fields: Vec::new(),
+                        expansion_probe: None,
+                        graph_hop: Some(hop),
+                        graph_via: Some(entity.clone()),
+                        decayed_strength,
+                        recency_boost: 1.0,
+                        access_weight: 1.0,
+                        rerank_score: None,
+                        base_score: 0.0,
+                        spread_boost: score,
+                        co_recall_boost: 0.0,
+                        session_boost: 0.0,
+                        context_boost: None,
+                        temporal_boost: 0.0,
+                        diversity_penalty: None,
+                        score_margin_to_next: None,
+                        final_score: score,
+                    },
+                });
+            }
+        }
+        frontier = next;
+    }
+
+    sort_recall_results(&mut added);
+    added.truncate(MAX_GRAPH_ADDITIONS);
+    Ok(added)
+}
+
+/// Score the top fused candidates with the cross-encoder.
+///
+/// Returns a map from memory index to rerank probability in [0, 1].
+fn rerank_candidates(
+    reranker: &dyn Reranker,
+    query: &str,
+    memories: &[(MemoryRecord, String)],
+    fused: &[(usize, f64)],
+) -> Result<HashMap<usize, f64>, RecallError> {
+    let top: Vec<usize> = fused
+        .iter()
+        .take(RERANK_CANDIDATES)
+        .map(|(idx, _)| *idx)
+        .collect();
+    if top.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let documents: Vec<&str> = top.iter().map(|&idx| memories[idx].1.as_str()).collect();
+    let logits = reranker
+        .rerank(query, &documents)
+        .map_err(|e| RecallError::Embedding(format!("rerank failed: {e}")))?;
+    Ok(top
+        .into_iter()
+        .zip(logits)
+        .map(|(idx, logit)| (idx, 1.0 / (1.0 + (-(logit as f64)).exp())))
+        .collect())
+}
+
+/// Blend a rerank probability into the RRF relevance term.
+///
+/// The rerank share is scaled by the top RRF score so reranked candidates stay
+/// on the same scale as the ones the cross-encoder never saw.
+fn rerank_relevance(rrf_score: f64, top_rrf: f64, rerank_score: f64) -> f64 {
+    (1.0 - RERANK_WEIGHT) * rrf_score + RERANK_WEIGHT * top_rrf * rerank_score
+}
+
+/// Hebbian co-activation (research item 5.3 H).
+///
+/// Each of the top results acts as an anchor: any other result it has been
+/// recalled with receives `anchor_score × factor × w / (1 + w)`, where `w` is
+/// the decayed association weight. The saturation keeps a handful of frequent
+/// pairings from dominating relevance.
+fn co_recall_boost(results: &mut [RecallResult], weights: &HashMap<(i64, i64), f64>, factor: f64) {
+    if results.len() < 2 || weights.is_empty() {
+        return;
+    }
+
+    let mut sorted_indices: Vec<usize> = (0..results.len()).collect();
+    sorted_indices.sort_by(|&a, &b| {
+        results[b]
+            .score
+            .partial_cmp(&results[a].score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| results[a].memory.id.cmp(&results[b].memory.id))
+    });
+    let anchor_count = sorted_indices.len().min(CO_RECALL_ANCHORS);
+
+    let mut boosts: HashMap<usize, f64> = HashMap::new();
+    for &ai in &sorted_indices[..anchor_count] {
+        let (a_id, a_score) = (results[ai].memory.id, results[ai].score);
+        for (j, r) in results.iter().enumerate() {
+            if j == ai {
+                continue;
+            }
+            let key = (a_id.min(r.memory.id), a_id.max(r.memory.id));
+            if let Some(&w) = weights.get(&key) {
+                *boosts.entry(j).or_insert(0.0) += a_score * factor * w / (1.0 + w);
+            }
+        }
+    }
+
+    for (idx, boost) in boosts {
+        results[idx].score += boost;
+    }
+}
+
 fn parse_coeff_env(name: &str, default: f64) -> f64 {
     std::env::var(name)
         .ok()
@@ -373,7 +1207,7 @@ fn parse_coeff_env(name: &str, default: f64) -> f64 {
         .unwrap_or(default)
 }
 
-fn recall_score_coefficients_from_env() -> RecallScoreCoefficients {
+pub(crate) fn recall_score_coefficients_from_env() -> RecallScoreCoefficients {
     let d = RecallScoreCoefficients::default();
     RecallScoreCoefficients {
         rrf_exp: parse_coeff_env("CONCH_RECALL_RRF_EXP", d.rrf_exp),
@@ -396,6 +1230,55 @@ fn compute_base_score(
         * access.powf(coeffs.access_exp)
 }
 
+/// Maximal marginal relevance (Carbonell & Goldstein, 1998).
+///
+/// Greedily picks `limit` results from the ranked list, each maximising
+/// `λ × relevance − (1 − λ) × max cosine similarity to the picks so far`, with
+/// relevance normalised to the top score. Memories without embeddings are
+/// treated as dissimilar to everything. The unpicked tail is dropped.
+fn diversify(results: Vec<RecallResult>, limit: usize, lambda: f64) -> Vec<RecallResult> {
+    let lambda = lambda.clamp(0.0, 1.0);
+    let top_score = results
+        .first()
+        .map(|r| r.score)
+        .filter(|s| *s > 0.0)
+        .unwrap_or(1.0);
+
+    let mut pool: Vec<Option<RecallResult>> = results.into_iter().map(Some).collect();
+    let mut selected: Vec<RecallResult> = Vec::with_capacity(limit.min(pool.len()));
+    while selected.len() < limit {
+        let mut best: Option<(usize, f64, f64)> = None;
+        for (i, candidate) in pool.iter().enumerate() {
+            let Some(candidate) = candidate else {
+                continue;
+            };
+            let redundancy = selected
+                .iter()
+                .map(|s| embedding_similarity(&candidate.memory, &s.memory))
+                .fold(0.0f64, f64::max);
+            let penalty = (1.0 - lambda) * redundancy;
+            let mmr = lambda * candidate.score / top_score - penalty;
+            if best.is_none_or(|(_, best_mmr, _)| mmr > best_mmr) {
+                best = Some((i, mmr, penalty));
+            }
+        }
+        let Some((i, _, penalty)) = best else {
+            break;
+        };
+        let mut picked = pool[i].take().expect("candidate still in pool");
+        picked.explain.diversity_penalty = Some(penalty);
+        selected.push(picked);
+    }
+    selected
+}
+
+fn embedding_similarity(a: &MemoryRecord, b: &MemoryRecord) -> f64 {
+    match (&a.embedding, &b.embedding) {
+        (Some(ea), Some(eb)) => f64::from(cosine_similarity(ea, eb)).max(0.0),
+        _ => 0.0,
+    }
+}
+
 fn sort_recall_results(results: &mut [RecallResult]) {
     results.sort_by(|a, b| {
         b.score
@@ -465,6 +1348,23 @@ fn touch_boost(mem: &MemoryRecord) -> f64 {
     }
 }
 
+/// Strength after relevance feedback. Useful reinforces like a recall touch,
+/// irrelevant restores `pre_touch`, the strength stored before the last recall
+/// touch (and changes nothing if no touch is pending), wrong halves the
+/// strength.
+pub(crate) fn feedback_strength(
+    mem: &MemoryRecord,
+    feedback: Feedback,
+    pre_touch: Option<f64>,
+    now: chrono::DateTime<Utc>,
+) -> f64 {
+    match feedback {
+        Feedback::Useful => (effective_strength(mem, now) + touch_boost(mem)).min(1.0),
+        Feedback::Irrelevant => pre_touch.unwrap_or(mem.strength),
+        Feedback::Wrong => mem.strength * WRONG_FEEDBACK_FACTOR,
+    }
+}
+
 fn effective_strength(mem: &MemoryRecord, now: chrono::DateTime<Utc>) -> f64 {
     if is_expired_pending_temporal(mem, now) {
         return 0.0;
@@ -573,75 +1473,372 @@ fn operational_salience_multiplier(
                 1.15
             }
         }
-        MemoryKind::Action(_) | MemoryKind::Intent(_) => 1.10,
-        _ => 1.0,
+        MemoryKind::Action(_) | MemoryKind::Intent(_) => 1.10,
+        _ => 1.0,
+    }
+}
+
+/// Field-weighted (BM25F-style) index over the recall candidates, built once
+/// per recall so the expansion probes can reuse it: one BM25 engine per field
+/// in [`KEYWORD_FIELD_WEIGHTS`], scores summed with the field weights. Each
+/// field is further split by memory language so documents and the query
+/// are stemmed and stop-worded in that language.
+struct KeywordIndex {
+    fields: Vec<(&'static str, f32, bm25::SearchEngine<usize>)>,
+    /// Language partition of each memory.
+    partitions: Vec<usize>,
+    /// Subject and object of each memory that is a fact.
+    entities: Vec<Option<(String, String)>>,
+    len: usize,
+}
+
+/// A keyword match: the memory index, its weighted score and the fields that matched.
+struct KeywordHit {
+    idx: usize,
+    score: f32,
+    fields: Vec<&'static str>,
+}
+
+impl KeywordIndex {
+    fn new(memories: &[(MemoryRecord, String)], default_language: &str) -> Self {
+        use bm25::{Document, SearchEngineBuilder};
+
+        let languages: Vec<&str> = memories
+            .iter()
+            .map(|(mem, _)| mem.language.as_deref().unwrap_or(default_language))
+            .collect();
+        let partitions = {
+            let mut unique = languages.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            unique
+        };
+        let mut fields: Vec<(&'static str, f32, bm25::SearchEngine<usize>)> = Vec::new();
+        for (field, weight) in KEYWORD_FIELD_WEIGHTS {
+            for &language in &partitions {
+                let documents: Vec<Document<usize>> = memories
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| languages[*i] == language)
+                    .filter_map(|(i, (mem, text))| {
+                        let contents = keyword_field(mem, text, field)?;
+                        Some(Document { id: i, contents })
+                    })
+                    .collect();
+                if documents.is_empty() {
+                    continue;
+                }
+                let engine =
+                    SearchEngineBuilder::with_documents(bm25_language(language), documents)
+                        .b(0.5)
+                        .build();
+                fields.push((field, weight, engine));
+            }
+        }
+        let entities = memories
+            .iter()
+            .map(|(mem, _)| match &mem.kind {
+                MemoryKind::Fact(f) => Some((f.subject.clone(), f.object.clone())),
+                _ => None,
+            })
+            .collect();
+        let partitions = languages
+            .iter()
+            .map(|language| {
+                partitions
+                    .binary_search(language)
+                    .expect("every language has a partition")
+            })
+            .collect();
+        Self {
+            fields,
+            partitions,
+            entities,
+            len: memories.len(),
+        }
+    }
+
+    fn search(&self, query: &str, search_limit: usize) -> Vec<(usize, f32)> {
+        self.search_fields(query, search_limit)
+            .into_iter()
+            .map(|hit| (hit.idx, hit.score))
+            .collect()
+    }
+
+    fn search_fields(&self, query: &str, search_limit: usize) -> Vec<KeywordHit> {
+        let mut hits: HashMap<usize, KeywordHit> = HashMap::new();
+        for (field, weight, engine) in &self.fields {
+            for result in engine.search(query, self.len) {
+                let idx = result.document.id;
+                let hit = hits.entry(idx).or_insert_with(|| KeywordHit {
+                    idx,
+                    score: 0.0,
+                    fields: Vec::new(),
+                });
+                hit.score += *weight * result.score;
+                hit.fields.push(*field);
+            }
+        }
+
+        // Each partition has its own corpus statistics, so raw BM25 scores
+        // are not comparable across partitions: scale each by its top hit.
+        let mut top: HashMap<usize, f32> = HashMap::new();
+        for hit in hits.values() {
+            let best = top.entry(self.partitions[hit.idx]).or_insert(0.0);
+            *best = best.max(hit.score);
+        }
+        for hit in hits.values_mut() {
+            let best = top[&self.partitions[hit.idx]];
+            if best > 0.0 {
+                hit.score /= best;
+            }
+        }
+
+        let query_tokens = tokenize(query);
+        for hit in hits.values_mut() {
+            if let Some((subject, object)) = &self.entities[hit.idx] {
+                if mentions_name(&query_tokens, subject) {
+                    hit.score *= 1.0 + EXACT_SUBJECT_BONUS;
+                    hit.fields.push("exact_subject");
+                }
+                if mentions_name(&query_tokens, object) {
+                    hit.score *= 1.0 + EXACT_OBJECT_BONUS;
+                    hit.fields.push("exact_object");
+                }
+            }
+        }
+
+        let mut ranked: Vec<KeywordHit> = hits.into_values().collect();
+        ranked.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.idx.cmp(&b.idx))
+        });
+        ranked.truncate(search_limit.max(1));
+        ranked
+    }
+}
+
+/// The searchable text of one keyword field, or `None` when the memory has none.
+fn keyword_field(mem: &MemoryRecord, text: &str, field: &str) -> Option<String> {
+    let contents = match (field, &mem.kind) {
+        ("subject", MemoryKind::Fact(f)) => f.subject.clone(),
+        ("object", MemoryKind::Fact(f)) => f.object.clone(),
+        ("relation", MemoryKind::Fact(f)) => f.relation.replace(['_', '-'], " "),
+        ("text", MemoryKind::Fact(_)) => return None,
+        ("text", _) => text.to_string(),
+        ("tags", _) => mem.tags.join(" ").replace(['_', '-'], " "),
+        ("source", _) => [mem.source.as_deref(), mem.channel.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => return None,
+    };
+    (!contents.trim().is_empty()).then_some(contents)
+}
+
+/// Rank memories by cosine similarity to the query embedding.
+///
+/// Returns the matches and the cutoff they had to reach: `threshold` when
+/// given, otherwise [`adaptive_vector_threshold`] over this query's similarities.
+fn vector_search(
+    query_emb: &[f32],
+    memories: &[(MemoryRecord, String)],
+    search_limit: usize,
+    threshold: Option<f32>,
+) -> (Vec<(usize, f32)>, f32) {
+    let similarities: Vec<(usize, f32)> = memories
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (mem, _))| {
+            let emb = mem.embedding.as_ref()?;
+            Some((i, cosine_similarity(query_emb, emb)))
+        })
+        .collect();
+    let cutoff = threshold.unwrap_or_else(|| {
+        let sims: Vec<f32> = similarities.iter().map(|(_, sim)| *sim).collect();
+        adaptive_vector_threshold(&sims)
+    });
+
+    let mut scored: Vec<(usize, f32)> = similarities
+        .into_iter()
+        .filter(|(_, sim)| *sim >= cutoff)
+        .collect();
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(search_limit.max(1).min(scored.len()));
+    (scored, cutoff)
+}
+
+/// Adaptive vector cutoff (research item 5.1 C).
+///
+/// A memory counts as a vector match when its similarity stands out from this
+/// query's distribution: at least `mean + VECTOR_THRESHOLD_Z × stddev`, clamped
+/// to [`VECTOR_THRESHOLD_FLOOR`, `VECTOR_THRESHOLD_CEILING`]. Short queries that
+/// score everything alike get a higher cutoff; strong matches always pass the
+/// ceiling. With too few samples the fixed default applies.
+fn adaptive_vector_threshold(similarities: &[f32]) -> f32 {
+    if similarities.len() < VECTOR_THRESHOLD_MIN_SAMPLES {
+        return VECTOR_SIMILARITY_THRESHOLD;
+    }
+    let n = similarities.len() as f32;
+    let mean = similarities.iter().sum::<f32>() / n;
+    let variance = similarities.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / n;
+    let cutoff = mean + VECTOR_THRESHOLD_Z * variance.sqrt();
+    if cutoff.is_finite() {
+        cutoff.clamp(VECTOR_THRESHOLD_FLOOR, VECTOR_THRESHOLD_CEILING)
+    } else {
+        VECTOR_SIMILARITY_THRESHOLD
+    }
+}
+
+/// Rankings produced by the query-expansion probes, merged across probes.
+#[derive(Default)]
+struct ProbeHits {
+    keyword: Vec<(usize, f32)>,
+    vector: Vec<(usize, f32)>,
+    /// The probe that found each memory (vector hits win over keyword hits).
+    found_by: HashMap<usize, String>,
+}
+
+/// Run every expansion probe through BM25 and vector search, keeping each
+/// memory's best score per modality.
+fn search_expansion_probes(
+    expansion: &QueryExpansion,
+    keyword_index: &KeywordIndex,
+    embedder: &dyn Embedder,
+    memories: &[(MemoryRecord, String)],
+    search_limit: usize,
+    vector_threshold: Option<f32>,
+) -> Result<ProbeHits, RecallError> {
+    if expansion.is_empty() {
+        return Ok(ProbeHits::default());
+    }
+    let probe_refs: Vec<&str> = expansion.probes.iter().map(|p| p.as_str()).collect();
+    let probe_embeddings = embedder
+        .embed(&probe_refs)
+        .map_err(|e| RecallError::Embedding(e.to_string()))?;
+
+    let mut keyword: HashMap<usize, (f32, usize)> = HashMap::new();
+    let mut vector: HashMap<usize, (f32, usize)> = HashMap::new();
+    for (p, probe) in probe_refs.iter().enumerate() {
+        for (idx, score) in keyword_index.search(probe, search_limit) {
+            let best = keyword.entry(idx).or_insert((score, p));
+            if score > best.0 {
+                *best = (score, p);
+            }
+        }
+        if let Some(emb) = probe_embeddings.get(p) {
+            let (hits, _) = vector_search(emb, memories, search_limit, vector_threshold);
+            for (idx, sim) in hits {
+                let best = vector.entry(idx).or_insert((sim, p));
+                if sim > best.0 {
+                    *best = (sim, p);
+                }
+            }
+        }
     }
-}
 
-fn bm25_search(
-    query: &str,
-    memories: &[(MemoryRecord, String)],
-    search_limit: usize,
-) -> Vec<(usize, f32)> {
-    use bm25::{Document, Language, SearchEngineBuilder};
+    let mut found_by: HashMap<usize, String> = HashMap::new();
+    for (idx, (_, p)) in keyword.iter().chain(vector.iter()) {
+        found_by.insert(*idx, expansion.probes[*p].clone());
+    }
+    Ok(ProbeHits {
+        keyword: ranked_by_score(keyword),
+        vector: ranked_by_score(vector),
+        found_by,
+    })
+}
 
-    let documents: Vec<Document<usize>> = memories
-        .iter()
-        .enumerate()
-        .map(|(i, (_, text))| Document {
-            id: i,
-            contents: text.clone(),
-        })
+fn ranked_by_score(hits: HashMap<usize, (f32, usize)>) -> Vec<(usize, f32)> {
+    let mut ranked: Vec<(usize, f32)> = hits
+        .into_iter()
+        .map(|(idx, (score, _))| (idx, score))
         .collect();
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    ranked
+}
 
-    let engine: bm25::SearchEngine<usize> =
-        SearchEngineBuilder::with_documents(Language::English, documents)
-            .b(0.5)
-            .build();
-
-    engine
-        .search(query, search_limit.max(1).min(memories.len()))
-        .into_iter()
-        .map(|r| (r.document.id, r.score))
-        .collect()
+/// Combine the query and probe rankings according to `strategy`.
+fn fuse(
+    strategy: FusionStrategy,
+    bm25: &[(usize, f32)],
+    vector: &[(usize, f32)],
+    probes: &ProbeHits,
+) -> Vec<(usize, f64)> {
+    let lists = |keyword_weight: f64, vector_weight: f64| {
+        [
+            (bm25, keyword_weight),
+            (vector, vector_weight),
+            (probes.keyword.as_slice(), keyword_weight * EXPANSION_WEIGHT),
+            (probes.vector.as_slice(), vector_weight * EXPANSION_WEIGHT),
+        ]
+    };
+    match strategy {
+        FusionStrategy::Rrf {
+            k,
+            keyword_weight,
+            vector_weight,
+        } => rrf(&lists(keyword_weight, vector_weight), k),
+        FusionStrategy::Convex { keyword_weight } => {
+            let keyword_weight = keyword_weight.clamp(0.0, 1.0);
+            convex(&lists(keyword_weight, 1.0 - keyword_weight))
+        }
+        FusionStrategy::VectorOnly => rrf(&lists(0.0, 1.0), RRF_K),
+        FusionStrategy::KeywordOnly => rrf(&lists(1.0, 0.0), RRF_K),
+    }
 }
 
-fn vector_search(
-    query_emb: &[f32],
-    memories: &[(MemoryRecord, String)],
-    search_limit: usize,
-) -> Vec<(usize, f32)> {
-    let mut scored: Vec<(usize, f32)> = memories
-        .iter()
-        .enumerate()
-        .filter_map(|(i, (mem, _))| {
-            let emb = mem.embedding.as_ref()?;
-            let sim = cosine_similarity(query_emb, emb);
-            if sim > VECTOR_SIMILARITY_THRESHOLD {
-                Some((i, sim))
-            } else {
-                None
-            }
-        })
-        .collect();
+/// Weighted Reciprocal Rank Fusion over any number of rankings.
+/// Rankings with a non-positive weight are ignored.
+fn rrf(lists: &[(&[(usize, f32)], f64)], k: f64) -> Vec<(usize, f64)> {
+    let mut scores: HashMap<usize, f64> = HashMap::new();
 
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored.truncate(search_limit.max(1).min(scored.len()));
-    scored
+    for (list, weight) in lists.iter().filter(|(_, weight)| *weight > 0.0) {
+        for (rank, &(idx, _)) in list.iter().enumerate() {
+            *scores.entry(idx).or_insert(0.0) += weight / (k + rank as f64 + 1.0);
+        }
+    }
+
+    ranked_fused(scores)
 }
 
-fn rrf(list_a: &[(usize, f32)], list_b: &[(usize, f32)]) -> Vec<(usize, f64)> {
+/// Weighted sum of min-max normalised raw scores over any number of rankings.
+/// Rankings with a non-positive weight are ignored.
+fn convex(lists: &[(&[(usize, f32)], f64)]) -> Vec<(usize, f64)> {
     let mut scores: HashMap<usize, f64> = HashMap::new();
 
-    for (rank, &(idx, _)) in list_a.iter().enumerate() {
-        *scores.entry(idx).or_insert(0.0) += 1.0 / (RRF_K + rank as f64 + 1.0);
-    }
-    for (rank, &(idx, _)) in list_b.iter().enumerate() {
-        *scores.entry(idx).or_insert(0.0) += 1.0 / (RRF_K + rank as f64 + 1.0);
+    for (list, weight) in lists.iter().filter(|(_, weight)| *weight > 0.0) {
+        let (min, max) = list
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, s)| {
+                (lo.min(f64::from(s)), hi.max(f64::from(s)))
+            });
+        for &(idx, score) in list.iter() {
+            let normalized = if max > min {
+                (f64::from(score) - min) / (max - min)
+            } else {
+                1.0
+            };
+            *scores.entry(idx).or_insert(0.0) += weight * normalized;
+        }
     }
 
+    ranked_fused(scores)
+}
+
+fn ranked_fused(scores: HashMap<usize, f64>) -> Vec<(usize, f64)> {
     let mut results: Vec<(usize, f64)> = scores.into_iter().collect();
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    results.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
     results
 }
 
@@ -694,14 +1891,25 @@ mod tests {
             bm25_score: None,
             vector_rank: None,
             vector_similarity: None,
+            vector_threshold: VECTOR_SIMILARITY_THRESHOLD,
             modality_agreement: false,
             matched_modalities: 0,
+            fusion: FusionStrategy::default(),
+            keyword_fields: Vec::new(),
+            expansion_probe: None,
+            graph_hop: None,
+            graph_via: None,
             decayed_strength: 1.0,
             recency_boost: 1.0,
             access_weight: 1.0,
+            rerank_score: None,
             base_score: score,
             spread_boost: 0.0,
+            co_recall_boost: 0.0,
+            session_boost: 0.0,
+            context_boost: None,
             temporal_boost: 0.0,
+            diversity_penalty: None,
             score_margin_to_next: None,
             final_score: score,
         }
@@ -918,7 +2126,7 @@ mod tests {
         let original_related = results[1].score;
         let original_unrelated = results[2].score;
 
-        spread_activation(&mut results, SPREAD_FACTOR);
+        spread_activation(&mut results, &HashMap::new(), SPREAD_FACTOR);
 
         assert!(
             results[1].score > original_related,
@@ -951,7 +2159,7 @@ mod tests {
         let score_a_before = results[0].score;
         let score_b_before = results[1].score;
 
-        spread_activation(&mut results, SPREAD_FACTOR);
+        spread_activation(&mut results, &HashMap::new(), SPREAD_FACTOR);
 
         // A boosted B via shared "Microsoft"
         assert!(results[1].score > score_b_before);
@@ -967,7 +2175,7 @@ mod tests {
             explain: test_explain(1.0),
         }];
 
-        spread_activation(&mut results, SPREAD_FACTOR);
+        spread_activation(&mut results, &HashMap::new(), SPREAD_FACTOR);
         // Single result — no self-boost possible
         assert!((results[0].score - 1.0).abs() < f64::EPSILON);
     }
@@ -1223,6 +2431,7 @@ mod tests {
             namespace: "default".to_string(),
             checksum: None,
             temporal: None,
+            language: None,
         }
     }
 
@@ -1245,6 +2454,7 @@ mod tests {
             namespace: "default".to_string(),
             checksum: None,
             temporal: None,
+            language: None,
         }
     }
 
@@ -1360,4 +2570,658 @@ mod tests {
         let results = recall_with_tag_filter(&store, "alpha", &MockEmbedder, 10, None).unwrap();
         assert_eq!(results.len(), 2);
     }
+
+    #[test]
+    fn recall_within_topic_only_returns_its_members() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        store
+            .remember_episode("alpha deploy finished", Some(&[1.0, 0.0]))
+            .unwrap();
+        let lunch = store
+            .remember_episode("alpha team lunch", Some(&[0.0, 1.0]))
+            .unwrap();
+        let topics = crate::topics::build_topics(&store, "default", Some(2)).unwrap();
+        let lunch_topic = topics
+            .iter()
+            .find(|t| t.keywords.contains(&"lunch".to_string()))
+            .unwrap();
+
+        let options = RecallOptions {
+            topic: Some(lunch_topic.id),
+            ..Default::default()
+        };
+        let results = recall_with_options(
+            &store,
+            "alpha",
+            &MockEmbedder,
+            None,
+            10,
+            "default",
+            &options,
+        )
+        .unwrap();
+        let ids: Vec<i64> = results.iter().map(|r| r.memory.id).collect();
+        assert_eq!(ids, vec![lunch]);
+    }
+
+    // ── Query expansion tests ─────────────────────────────────
+
+    #[test]
+    fn expansion_finds_neighbour_memories_without_word_overlap() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        // Stored embeddings are orthogonal to every MockEmbedder query, so only
+        // keyword search can find anything here.
+        let fact = store
+            .remember_fact("Jared", "has_pet", "Tortellini", Some(&[1.0, 0.0]))
+            .unwrap();
+        let vet = store
+            .remember_episode("Jared took the dog to the vet", Some(&[1.0, 0.0]))
+            .unwrap();
+
+        let results = recall(&store, "How is Tortellini doing?", &MockEmbedder, 5).unwrap();
+        let fact_result = results
+            .iter()
+            .find(|r| r.memory.id == fact)
+            .expect("the query names the fact's object");
+        assert!(fact_result.explain.bm25_rank.is_some());
+        assert_eq!(fact_result.explain.expansion_probe, None);
+        let vet_result = results
+            .iter()
+            .find(|r| r.memory.id == vet)
+            .expect("neighbour probe should surface the vet episode");
+        assert_eq!(vet_result.explain.expansion_probe.as_deref(), Some("Jared"));
+        assert!(vet_result.explain.bm25_rank.is_none());
+        assert!(vet_result.explain.vector_rank.is_none());
+
+        let options = RecallOptions {
+            disable_expansion: true,
+            ..Default::default()
+        };
+        let plain = recall_with_options(
+            &store,
+            "How is Tortellini doing?",
+            &MockEmbedder,
+            None,
+            5,
+            "default",
+            &options,
+        )
+        .unwrap();
+        assert!(plain.iter().all(|r| r.memory.id != vet));
+    }
+
+    #[test]
+    fn co_recall_boosts_habitual_partners_of_top_results() {
+        let mut results = vec![
+            RecallResult {
+                memory: make_fact_record(1, "Deploy", "needs", "checklist"),
+                score: 1.0,
+                explain: test_explain(1.0),
+            },
+            RecallResult {
+                memory: make_fact_record(2, "Rollback", "uses", "snapshots"),
+                score: 0.2,
+                explain: test_explain(0.2),
+            },
+            RecallResult {
+                memory: make_fact_record(3, "Lunch", "is", "pizza"),
+                score: 0.2,
+                explain: test_explain(0.2),
+            },
+        ];
+        let weights = HashMap::from([((1, 2), 3.0)]);
+        co_recall_boost(&mut results, &weights, CO_RECALL_FACTOR);
+
+        let expected = 0.2 + 1.0 * CO_RECALL_FACTOR * 3.0 / 4.0;
+        assert!((results[1].score - expected).abs() < 1e-9);
+        assert!((results[2].score - 0.2).abs() < 1e-9);
+        // The weaker partner still feeds the anchor a small boost back.
+        assert!(results[0].score > 1.0);
+    }
+
+    #[test]
+    fn recall_records_co_recall_between_returned_memories() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let a = store
+            .remember_episode("alpha deploy checklist", Some(&[1.0, 0.0]))
+            .unwrap();
+        let b = store
+            .remember_episode("alpha rollback plan", Some(&[1.0, 0.0]))
+            .unwrap();
+
+        recall(&store, "alpha", &MockEmbedder, 5).unwrap();
+        recall(&store, "alpha", &MockEmbedder, 5).unwrap();
+
+        let assoc = store
+            .co_recall_associations(a, "default", 5, Utc::now())
+            .unwrap();
+        assert_eq!(assoc.len(), 1);
+        assert_eq!(assoc[0].memory_id, b);
+        assert_eq!(assoc[0].count, 2);
+
+        let results = recall(&store, "alpha", &MockEmbedder, 5).unwrap();
+        assert!(results.iter().any(|r| r.explain.co_recall_boost > 0.0));
+    }
+
+    #[test]
+    fn session_skips_seen_memories_and_primes_shared_entities() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        store
+            .remember_fact("Billing", "uses", "Postgres", Some(&[1.0, 0.0]))
+            .unwrap();
+        store
+            .remember_fact("Postgres", "runs_on", "db01", Some(&[1.0, 0.0]))
+            .unwrap();
+        let options = RecallOptions {
+            disable_expansion: true,
+            spread_hops: Some(0),
+            session_id: Some("chat-1".to_string()),
+            ..Default::default()
+        };
+        let recall_in_session = |query: &str, limit: usize| {
+            recall_with_options(
+                &store,
+                query,
+                &MockEmbedder,
+                None,
+                limit,
+                "default",
+                &options,
+            )
+            .unwrap()
+        };
+
+        let first = recall_in_session("Billing Postgres", 1);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].explain.session_boost, 0.0);
+
+        let second = recall_in_session("Postgres", 5);
+        assert!(second.iter().all(|r| r.memory.id != first[0].memory.id));
+        assert_eq!(second.len(), 1);
+        assert!(second[0].explain.session_boost > 0.0);
+
+        // Without the session, the first memory comes back.
+        let fresh = recall(&store, "Postgres", &MockEmbedder, 5).unwrap();
+        assert!(fresh.iter().any(|r| r.memory.id == first[0].memory.id));
+    }
+
+    #[test]
+    fn spreading_adds_graph_neighbours_across_hops() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let pet = store
+            .remember_fact("Jared", "has_pet", "Tortellini", Some(&[1.0, 0.0]))
+            .unwrap();
+        let job = store
+            .remember_fact("Jared", "works_at", "Microsoft", Some(&[1.0, 0.0]))
+            .unwrap();
+        let office = store
+            .remember_fact("Microsoft", "located_in", "Redmond", Some(&[1.0, 0.0]))
+            .unwrap();
+        store
+            .remember_fact("Abby", "likes", "cats", Some(&[1.0, 0.0]))
+            .unwrap();
+
+        let recall_hops = |hops: usize| {
+            let options = RecallOptions {
+                disable_expansion: true,
+                spread_hops: Some(hops),
+                ..Default::default()
+            };
+            recall_with_options(
+                &store,
+                "Tortellini",
+                &MockEmbedder,
+                None,
+                10,
+                "default",
+                &options,
+            )
+            .unwrap()
+        };
+
+        let results = recall_hops(2);
+        assert_eq!(results[0].memory.id, pet);
+        assert!(results[0].explain.graph_hop.is_none());
+        let job_result = results.iter().find(|r| r.memory.id == job).unwrap();
+        assert_eq!(job_result.explain.graph_hop, Some(1));
+        assert_eq!(job_result.explain.graph_via.as_deref(), Some("Jared"));
+        assert_eq!(job_result.explain.rrf_rank, 0);
+        let office_result = results.iter().find(|r| r.memory.id == office).unwrap();
+        assert_eq!(office_result.explain.graph_hop, Some(2));
+        assert!(office_result.score < job_result.score);
+        assert_eq!(results.len(), 3);
+
+        let one_hop: Vec<i64> = recall_hops(1).iter().map(|r| r.memory.id).collect();
+        assert_eq!(one_hop, vec![pet, job]);
+        let no_hops: Vec<i64> = recall_hops(0).iter().map(|r| r.memory.id).collect();
+        assert_eq!(no_hops, vec![pet]);
+    }
+
+    #[test]
+    fn spreading_follows_entity_mentions() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let pet = store
+            .remember_fact("Jared", "has_pet", "Tortellini", Some(&[1.0, 0.0]))
+            .unwrap();
+        let walk = store
+            .remember_episode("Long walk along the river", Some(&[1.0, 0.0]))
+            .unwrap();
+        store
+            .set_mentions(walk, "default", &["Jared".to_string()])
+            .unwrap();
+
+        let recall_hops = |hops: usize| {
+            let options = RecallOptions {
+                disable_expansion: true,
+                spread_hops: Some(hops),
+                ..Default::default()
+            };
+            recall_with_options(
+                &store,
+                "Tortellini",
+                &MockEmbedder,
+                None,
+                10,
+                "default",
+                &options,
+            )
+            .unwrap()
+        };
+
+        let results = recall_hops(1);
+        assert_eq!(results[0].memory.id, pet);
+        let walk_result = results.iter().find(|r| r.memory.id == walk).unwrap();
+        assert_eq!(walk_result.explain.graph_hop, Some(1));
+        assert_eq!(walk_result.explain.graph_via.as_deref(), Some("Jared"));
+        assert!(recall_hops(0).iter().all(|r| r.memory.id != walk));
+    }
+
+    #[test]
+    fn spreading_activation_links_mentions_to_facts() {
+        let mut results = vec![
+            RecallResult {
+                memory: make_fact_record(1, "Jared", "works_at", "Microsoft"),
+                score: 0.8,
+                explain: test_explain(0.8),
+            },
+            RecallResult {
+                memory: make_fact_record(2, "Abby", "likes", "cats"),
+                score: 0.3,
+                explain: test_explain(0.3),
+            },
+        ];
+        results.push(RecallResult {
+            memory: make_timed_episode(3, "Lunch with jared", Utc::now()),
+            score: 0.3,
+            explain: test_explain(0.3),
+        });
+        let mentions = HashMap::from([(3, vec!["Jared".to_string()])]);
+
+        spread_activation(&mut results, &mentions, SPREAD_FACTOR);
+        assert!(results[0].score > 0.8);
+        assert_eq!(results[1].score, 0.3);
+        assert!(results[2].score > 0.3);
+    }
+
+    #[test]
+    fn adaptive_threshold_tracks_the_similarity_distribution() {
+        // Too few samples: fixed default.
+        assert_eq!(
+            adaptive_vector_threshold(&[0.9, 0.1]),
+            VECTOR_SIMILARITY_THRESHOLD
+        );
+        // Uniformly weak similarities: the floor applies.
+        assert_eq!(
+            adaptive_vector_threshold(&[0.05, 0.05, 0.05, 0.05]),
+            VECTOR_THRESHOLD_FLOOR
+        );
+        // Noise around 0.35 with one strong match: cutoff rises above the noise.
+        let sims = [0.9, 0.36, 0.35, 0.34, 0.35, 0.36, 0.34, 0.35];
+        let cutoff = adaptive_vector_threshold(&sims);
+        assert!(cutoff > 0.36 && cutoff <= VECTOR_THRESHOLD_CEILING);
+        // Everything similar: capped at the ceiling so strong matches survive.
+        assert_eq!(
+            adaptive_vector_threshold(&[0.95, 0.9, 0.92, 0.93]),
+            VECTOR_THRESHOLD_CEILING
+        );
+    }
+
+    #[test]
+    fn vector_search_drops_noise_and_reports_cutoff() {
+        let memory = |id: i64, emb: Vec<f32>| {
+            let mut mem = make_fact_record(id, "s", "r", "o");
+            mem.embedding = Some(emb);
+            (mem, String::new())
+        };
+        let memories = vec![
+            memory(1, vec![1.0, 0.0]),
+            memory(2, vec![0.35, 0.94]),
+            memory(3, vec![0.36, 0.93]),
+            memory(4, vec![0.34, 0.94]),
+        ];
+        let (adaptive, cutoff) = vector_search(&[1.0, 0.0], &memories, 10, None);
+        assert!(cutoff > 0.36);
+        assert_eq!(
+            adaptive.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![0]
+        );
+
+        let (fixed, cutoff) = vector_search(&[1.0, 0.0], &memories, 10, Some(0.3));
+        assert_eq!(cutoff, 0.3);
+        assert_eq!(fixed.len(), 4);
+    }
+
+    #[test]
+    fn diversify_prefers_distinct_results_over_near_duplicates() {
+        let result = |id: i64, score: f64, emb: Vec<f32>| {
+            let mut memory = make_timed_episode(id, "deploy", Utc::now());
+            memory.embedding = Some(emb);
+            RecallResult {
+                memory,
+                score,
+                explain: test_explain(score),
+            }
+        };
+        let ranked = vec![
+            result(1, 1.0, vec![1.0, 0.0]),
+            result(2, 0.95, vec![0.99, 0.1]),
+            result(3, 0.9, vec![0.98, 0.2]),
+            result(4, 0.6, vec![0.0, 1.0]),
+        ];
+
+        let plain: Vec<i64> = diversify(ranked.clone(), 2, 1.0)
+            .iter()
+            .map(|r| r.memory.id)
+            .collect();
+        assert_eq!(plain, vec![1, 2]);
+
+        let diverse = diversify(ranked, 2, DEFAULT_DIVERSITY_LAMBDA);
+        let ids: Vec<i64> = diverse.iter().map(|r| r.memory.id).collect();
+        assert_eq!(ids, vec![1, 4]);
+        assert_eq!(diverse[0].explain.diversity_penalty, Some(0.0));
+        assert!(diverse[1].explain.diversity_penalty.unwrap() < 1e-6);
+    }
+
+    #[test]
+    fn context_boost_matches_channel_session_and_project() {
+        let mut mem = make_fact_record(1, "Grafana", "runs_on", "db01");
+        mem.channel = Some("#infra".to_string());
+        mem.session_id = Some("s-7".to_string());
+        mem.tags = vec!["conch".to_string()];
+        let weights = ContextWeights::default();
+
+        let context = RecallContext {
+            channel: Some("Infra".to_string()),
+            source: Some("discord".to_string()),
+            session_id: Some("s-7".to_string()),
+            cwd: Some("/home/me/src/conch/".to_string()),
+        };
+        let boost = context_boost(&mem, &context, &weights, 2.0);
+        assert!((boost.channel - 2.0 * weights.channel).abs() < 1e-9);
+        assert_eq!(boost.source, 0.0);
+        assert!((boost.session - 2.0 * weights.session).abs() < 1e-9);
+        assert!((boost.cwd - 2.0 * weights.cwd).abs() < 1e-9);
+
+        let elsewhere = RecallContext {
+            channel: Some("#general".to_string()),
+            cwd: Some("/home/me/src/other".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(context_boost(&mem, &elsewhere, &weights, 2.0).total(), 0.0);
+    }
+
+    #[test]
+    fn context_weights_parse_with_defaults() {
+        let weights: ContextWeights = "channel=0.5, cwd=0".parse().unwrap();
+        assert_eq!(weights.channel, 0.5);
+        assert_eq!(weights.cwd, 0.0);
+        assert_eq!(weights.session, ContextWeights::default().session);
+        assert_eq!(
+            weights.to_string().parse::<ContextWeights>().unwrap(),
+            weights
+        );
+        assert!("room=1".parse::<ContextWeights>().is_err());
+        assert!("channel=-1".parse::<ContextWeights>().is_err());
+    }
+
+    #[test]
+    fn fusion_strategy_parses_and_displays() {
+        let rrf: FusionStrategy = "rrf:k=40,keyword=2".parse().unwrap();
+        assert_eq!(
+            rrf,
+            FusionStrategy::Rrf {
+                k: 40.0,
+                keyword_weight: 2.0,
+                vector_weight: 1.0,
+            }
+        );
+        assert_eq!(rrf.to_string().parse::<FusionStrategy>().unwrap(), rrf);
+        assert_eq!(
+            "convex:keyword=0.8".parse::<FusionStrategy>().unwrap(),
+            FusionStrategy::Convex {
+                keyword_weight: 0.8
+            }
+        );
+        assert_eq!(
+            "vector".parse::<FusionStrategy>().unwrap(),
+            FusionStrategy::VectorOnly
+        );
+        assert_eq!(
+            "rrf".parse::<FusionStrategy>().unwrap(),
+            FusionStrategy::default()
+        );
+        assert!("convex:keyword=2".parse::<FusionStrategy>().is_err());
+        assert!("rrf:x=1".parse::<FusionStrategy>().is_err());
+        assert!("vector:k=1".parse::<FusionStrategy>().is_err());
+        assert!("linear".parse::<FusionStrategy>().is_err());
+    }
+
+    #[test]
+    fn fusion_strategies_weight_modalities() {
+        let bm25 = [(0usize, 5.0f32), (1, 1.0)];
+        let vector = [(2usize, 0.9f32), (0, 0.5)];
+        let probes = ProbeHits::default();
+        let order = |strategy| -> Vec<usize> {
+            fuse(strategy, &bm25, &vector, &probes)
+                .into_iter()
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+
+        assert_eq!(order(FusionStrategy::KeywordOnly), vec![0, 1]);
+        assert_eq!(order(FusionStrategy::VectorOnly), vec![2, 0]);
+        assert_eq!(
+            order(FusionStrategy::Convex {
+                keyword_weight: 0.8
+            }),
+            vec![0, 2, 1]
+        );
+        assert_eq!(
+            order(FusionStrategy::Convex {
+                keyword_weight: 0.2
+            }),
+            vec![2, 0, 1]
+        );
+        assert_eq!(order(FusionStrategy::default()), vec![0, 2, 1]);
+        let keyword_heavy = FusionStrategy::Rrf {
+            k: RRF_K,
+            keyword_weight: 3.0,
+            vector_weight: 0.1,
+        };
+        assert_eq!(order(keyword_heavy), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn recall_records_fusion_strategy_in_explain() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        store
+            .remember_fact("deploy", "uses", "PLAT-4412", Some(&[1.0, 0.0]))
+            .unwrap();
+        let options = RecallOptions {
+            fusion: Some(FusionStrategy::KeywordOnly),
+            ..Default::default()
+        };
+        let results = recall_with_options(
+            &store,
+            "PLAT-4412",
+            &MockEmbedder,
+            None,
+            5,
+            "default",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(results[0].explain.fusion, FusionStrategy::KeywordOnly);
+    }
+
+    fn with_text(mem: MemoryRecord) -> (MemoryRecord, String) {
+        let text = mem.text_for_embedding();
+        (mem, text)
+    }
+
+    #[test]
+    fn keyword_search_prefers_facts_with_the_name_as_subject() {
+        let memories = vec![
+            with_text(make_fact_record(1, "Abby", "manages", "Jared")),
+            with_text(make_fact_record(2, "Jared", "works_at", "Microsoft")),
+            with_text(make_timed_episode(3, "lunch with Jared", Utc::now())),
+        ];
+        let index = KeywordIndex::new(&memories, DEFAULT_LANGUAGE);
+        let hits = index.search_fields("Jared", 10);
+
+        assert_eq!(hits[0].idx, 1);
+        assert!(hits[0].fields.contains(&"subject"));
+        assert!(hits[0].fields.contains(&"exact_subject"));
+        let object_hit = hits.iter().find(|h| h.idx == 0).unwrap();
+        assert!(object_hit.fields.contains(&"exact_object"));
+        assert!(object_hit.score < hits[0].score);
+    }
+
+    #[test]
+    fn keyword_search_covers_tags_and_source() {
+        let mut tagged = make_timed_episode(1, "rolled the cluster nodes", Utc::now());
+        tagged.tags = vec!["kubernetes".to_string()];
+        let mut sourced = make_timed_episode(2, "paged about disk usage", Utc::now());
+        sourced.channel = Some("#oncall".to_string());
+        let memories = vec![with_text(tagged), with_text(sourced)];
+        let index = KeywordIndex::new(&memories, DEFAULT_LANGUAGE);
+
+        let hits = index.search_fields("kubernetes", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].fields, vec!["tags"]);
+
+        let hits = index.search_fields("oncall", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].fields, vec!["source"]);
+    }
+
+    #[test]
+    fn keyword_search_stems_each_memory_in_its_language() {
+        let mut german = make_timed_episode(1, "Die Katzen schlafen in den Häusern", Utc::now());
+        german.language = Some("deu".to_string());
+        let undetected = make_timed_episode(2, "Die Kunden warten auf Rechnungen", Utc::now());
+        let memories = vec![with_text(german), with_text(undetected)];
+
+        let index = KeywordIndex::new(&memories, DEFAULT_LANGUAGE);
+        let hits = index.search_fields("Katze im Haus", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].idx, 0);
+        assert!(index.search_fields("Kunde Rechnung", 10).is_empty());
+
+        let index = KeywordIndex::new(&memories, "deu");
+        assert_eq!(index.search_fields("Kunde Rechnung", 10)[0].idx, 1);
+    }
+
+    #[test]
+    fn keyword_scores_are_comparable_across_language_partitions() {
+        // A large English partition makes "deploy" common there, which
+        // lowers its raw BM25 score next to a tiny German partition.
+        let mut memories: Vec<(MemoryRecord, String)> = (0..40)
+            .map(|i| {
+                let mut mem =
+                    make_timed_episode(i, &format!("deploy service number {i}"), Utc::now());
+                mem.language = Some("eng".to_string());
+                with_text(mem)
+            })
+            .collect();
+        for i in 40..42 {
+            let mut mem = make_timed_episode(i, "deploy am Freitag erledigt", Utc::now());
+            mem.language = Some("deu".to_string());
+            memories.push(with_text(mem));
+        }
+        let mut german = make_timed_episode(42, "Kunden warten auf Rechnungen", Utc::now());
+        german.language = Some("deu".to_string());
+        memories.push(with_text(german));
+
+        let index = KeywordIndex::new(&memories, DEFAULT_LANGUAGE);
+        let hits = index.search_fields("deploy", 50);
+        assert_eq!(hits.len(), 42);
+        let top = hits[0].score;
+        assert!((top - 1.0).abs() < 1e-6);
+        let english_top = hits.iter().find(|h| h.idx < 40).unwrap().score;
+        let german_top = hits.iter().find(|h| h.idx >= 40).unwrap().score;
+        assert!((english_top - german_top).abs() < 1e-6);
+    }
+
+    #[test]
+    fn weighted_rrf_scales_each_ranking() {
+        let a = [(0usize, 1.0f32)];
+        let b = [(1usize, 1.0f32)];
+        let fused = rrf(&[(&a[..], 1.0), (&b[..], 0.5)], RRF_K);
+        assert_eq!(fused[0].0, 0);
+        assert!((fused[0].1 - 2.0 * fused[1].1).abs() < 1e-12);
+    }
+
+    // ── Rerank tests ──────────────────────────────────────────
+
+    /// Mock cross-encoder that only considers documents about bananas relevant.
+    struct BananaReranker;
+
+    impl Reranker for BananaReranker {
+        fn rerank(&self, _query: &str, documents: &[&str]) -> Result<Vec<f32>, EmbedError> {
+            Ok(documents
+                .iter()
+                .map(|d| if d.contains("bananas") { 5.0 } else { -5.0 })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn rerank_stage_reorders_candidates_and_is_explained() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let apples = store
+            .remember_episode("alpha apples", Some(&[1.0, 0.0]))
+            .unwrap();
+        let bananas = store
+            .remember_episode("alpha bananas", Some(&[1.0, 0.0]))
+            .unwrap();
+
+        let plain = recall(&store, "alpha apples", &MockEmbedder, 2).unwrap();
+        assert_eq!(plain[0].memory.id, apples);
+        assert!(plain.iter().all(|r| r.explain.rerank_score.is_none()));
+
+        let reranked = recall_with_options(
+            &store,
+            "alpha apples",
+            &MockEmbedder,
+            Some(&BananaReranker),
+            2,
+            "default",
+            &RecallOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(reranked[0].memory.id, bananas);
+        let top = reranked[0].explain.rerank_score.unwrap();
+        let bottom = reranked[1].explain.rerank_score.unwrap();
+        assert!(top > 0.9 && bottom < 0.1, "top={top} bottom={bottom}");
+    }
+
+    #[test]
+    fn rerank_relevance_keeps_rrf_share() {
+        // A candidate the cross-encoder rejects still keeps part of its RRF score.
+        let rejected = rerank_relevance(0.03, 0.03, 0.0);
+        assert!((rejected - 0.03 * (1.0 - RERANK_WEIGHT)).abs() < 1e-12);
+        // A perfect rerank score on the top candidate reproduces the top RRF score.
+        assert!((rerank_relevance(0.03, 0.03, 1.0) - 0.03).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+/// Synonyms every namespace understands, as `(synonym, canonical relation)`.
+/// Synonyms added to a namespace take precedence.
+pub const BUILTIN_SYNONYMS: &[(&str, &str)] = &[
+    ("employed_by", "works_at"),
+    ("works_for", "works_at"),
+    ("employer", "works_at"),
+    ("job", "works_at"),
+    ("resides_in", "lives_in"),
+    ("lives_at", "lives_in"),
+    ("is_located_in", "located_in"),
+    ("located_at", "located_in"),
+    ("was_born_in", "born_in"),
+    ("birthplace", "born_in"),
+    ("is_married_to", "married_to"),
+    ("spouse", "married_to"),
+    ("also_known_as", "aka"),
+    ("known_as", "aka"),
+];
+
+/// Normal form of a relation: lowercase, with runs of whitespace, hyphens and
+/// underscores folded into one underscore ("Works  At" → "works_at").
+pub fn normalize_relation(relation: &str) -> String {
+    relation
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Canonical form of `relation`: normalized, then mapped through the
+/// namespace's `synonyms` (keyed by normalized synonym) or, failing that,
+/// [`BUILTIN_SYNONYMS`].
+pub fn canonical_relation(relation: &str, synonyms: &HashMap<String, String>) -> String {
+    let normalized = normalize_relation(relation);
+    if let Some(canonical) = synonyms.get(&normalized) {
+        return canonical.clone();
+    }
+    BUILTIN_SYNONYMS
+        .iter()
+        .find(|(synonym, _)| *synonym == normalized)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_folds_case_whitespace_and_separators() {
+        assert_eq!(normalize_relation("works_at"), "works_at");
+        assert_eq!(normalize_relation("  Works   At "), "works_at");
+        assert_eq!(normalize_relation("works-at"), "works_at");
+        assert_eq!(normalize_relation("WORKS__AT"), "works_at");
+    }
+
+    #[test]
+    fn synonyms_map_to_the_canonical_relation() {
+        let none = HashMap::new();
+        assert_eq!(canonical_relation("employed by", &none), "works_at");
+        assert_eq!(canonical_relation("Job", &none), "works_at");
+        assert_eq!(canonical_relation("likes", &none), "likes");
+
+        let synonyms = HashMap::from([
+            ("job".to_string(), "role".to_string()),
+            ("gig".to_string(), "works_at".to_string()),
+        ]);
+        assert_eq!(canonical_relation("job", &synonyms), "role");
+        assert_eq!(canonical_relation("GIG", &synonyms), "works_at");
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+use crate::embed::EmbedError;
+
+/// A cross-encoder that scores each (query, document) pair jointly.
+///
+/// Unlike an [`Embedder`](crate::embed::Embedder), which encodes query and
+/// document independently, a reranker reads both at once. It is far more
+/// precise on ambiguous queries but too slow to run over a whole namespace,
+/// so recall only applies it to the top fused candidates.
+pub trait Reranker: Send + Sync {
+    /// Score every document against `query`. Returns one raw relevance score
+    /// (a logit; higher is more relevant) per document, in input order.
+    fn rerank(&self, query: &str, documents: &[&str]) -> Result<Vec<f32>, EmbedError>;
+}
+
+/// Local cross-encoder backed by fastembed (BAAI bge-reranker-base).
+pub struct FastReranker {
+    model: Mutex<fastembed::TextRerank>,
+}
+
+impl FastReranker {
+    pub fn new() -> Result<Self, EmbedError> {
+        let options = fastembed::RerankInitOptions::new(fastembed::RerankerModel::BGERerankerBase);
+        let model = fastembed::TextRerank::try_new(options)
+            .map_err(|e| EmbedError::Model(e.to_string()))?;
+        Ok(Self {
+            model: Mutex::new(model),
+        })
+    }
+}
+
+impl Reranker for FastReranker {
+    fn rerank(&self, query: &str, documents: &[&str]) -> Result<Vec<f32>, EmbedError> {
+        // fastembed 5.1 takes the documents by value; later 5.x also accept a slice.
+        let owned = documents.to_vec();
+        let ranked = self
+            .model
+            .lock()
+            .map_err(|e| EmbedError::Other(e.to_string()))?
+            .rerank(query, owned, false, None)
+            .map_err(|e| EmbedError::Model(e.to_string()))?;
+        // fastembed returns results sorted by score; put them back in input order.
+        let mut scores = vec![f32::NEG_INFINITY; documents.len()];
+        for r in ranked {
+            if let Some(slot) = scores.get_mut(r.index) {
+                *slot = r.score;
+            }
+        }
+        Ok(scores)
+    }
+}
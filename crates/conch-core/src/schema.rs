@@ -0,0 +1,171 @@
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// How many objects a subject may have for a relation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cardinality {
+    /// A new object supersedes the old one (lives_in, email).
+    One,
+    /// Objects accumulate (has_pet, likes).
+    #[default]
+    Many,
+}
+
+impl Cardinality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cardinality::One => "one",
+            Cardinality::Many => "many",
+        }
+    }
+}
+
+impl std::fmt::Display for Cardinality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Cardinality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "one" | "single" | "1" => Ok(Cardinality::One),
+            "many" | "multi" | "n" => Ok(Cardinality::Many),
+            other => Err(format!(
+                "unknown cardinality \"{other}\" (expected one or many)"
+            )),
+        }
+    }
+}
+
+/// What a relation's object has to be.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectType {
+    /// Another entity, registered like fact subjects are.
+    #[default]
+    Entity,
+    /// Free text, not registered as an entity.
+    String,
+    Number,
+    /// A calendar date (`2024-03-01`) or RFC 3339 timestamp.
+    Date,
+    /// An `http` or `https` URL.
+    Url,
+}
+
+impl ObjectType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectType::Entity => "entity",
+            ObjectType::String => "string",
+            ObjectType::Number => "number",
+            ObjectType::Date => "date",
+            ObjectType::Url => "url",
+        }
+    }
+
+    /// Whether `object` is a valid value of this type.
+    pub fn accepts(&self, object: &str) -> bool {
+        let object = object.trim();
+        if object.is_empty() {
+            return false;
+        }
+        match self {
+            ObjectType::Entity | ObjectType::String => true,
+            ObjectType::Number => object.replace('_', "").parse::<f64>().is_ok(),
+            ObjectType::Date => {
+                NaiveDate::parse_from_str(object, "%Y-%m-%d").is_ok()
+                    || DateTime::parse_from_rfc3339(object).is_ok()
+            }
+            ObjectType::Url => ["https://", "http://"].iter().any(|scheme| {
+                object.len() > scheme.len()
+                    && object
+                        .get(..scheme.len())
+                        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+                    && !object.contains(char::is_whitespace)
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ObjectType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "entity" => Ok(ObjectType::Entity),
+            "string" | "text" => Ok(ObjectType::String),
+            "number" => Ok(ObjectType::Number),
+            "date" => Ok(ObjectType::Date),
+            "url" => Ok(ObjectType::Url),
+            other => Err(format!(
+                "unknown object type \"{other}\" (expected entity, string, number, date or url)"
+            )),
+        }
+    }
+}
+
+/// Declared shape of a canonical relation in a namespace. Relations without
+/// a schema behave as before: any object, and upserts replace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationSchema {
+    pub relation: String,
+    #[serde(default)]
+    pub cardinality: Cardinality,
+    #[serde(default)]
+    pub object_type: ObjectType,
+    /// Relation reading the same fact from the object's side
+    /// (`parent_of` ↔ `child_of`).
+    #[serde(default)]
+    pub inverse: Option<String>,
+}
+
+impl RelationSchema {
+    /// Check `object` against the declared object type.
+    pub fn check_object(&self, object: &str) -> Result<(), String> {
+        if self.object_type.accepts(object) {
+            Ok(())
+        } else {
+            Err(format!(
+                "\"{object}\" is not a {} (relation \"{}\")",
+                self.object_type, self.relation
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_types_accept_their_values() {
+        assert!(ObjectType::Number.accepts("42"));
+        assert!(ObjectType::Number.accepts("-3.5"));
+        assert!(!ObjectType::Number.accepts("forty"));
+        assert!(ObjectType::Date.accepts("2024-03-01"));
+        assert!(ObjectType::Date.accepts("2024-03-01T09:30:00Z"));
+        assert!(!ObjectType::Date.accepts("next tuesday"));
+        assert!(ObjectType::Url.accepts("https://example.com/a"));
+        assert!(!ObjectType::Url.accepts("example.com"));
+        assert!(!ObjectType::Url.accepts("https://"));
+        assert!(!ObjectType::String.accepts("  "));
+    }
+
+    #[test]
+    fn parses_cardinality_and_types() {
+        assert_eq!("One".parse::<Cardinality>().unwrap(), Cardinality::One);
+        assert_eq!("text".parse::<ObjectType>().unwrap(), ObjectType::String);
+        assert!("several".parse::<Cardinality>().is_err());
+    }
+}
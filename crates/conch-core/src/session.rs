@@ -0,0 +1,140 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::memory::{MemoryKind, MemoryRecord};
+
+/// A recall session with no recall for this long is forgotten: its seen set
+/// and activation are dropped and the next recall starts fresh.
+pub const SESSION_IDLE_TIMEOUT_MINUTES: i64 = 30;
+
+/// Priming activation half-life: an entity or tag not recalled again for this
+/// long primes half as strongly.
+pub(crate) const PRIMING_HALF_LIFE_MINUTES: f64 = 10.0;
+
+/// Activation below this is treated as gone.
+pub(crate) const MIN_ACTIVATION: f64 = 0.05;
+
+/// Maximum score multiplier bonus from priming (at full activation).
+pub(crate) const PRIMING_FACTOR: f64 = 0.25;
+
+/// What a recall session remembers between calls.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionState {
+    pub session_id: String,
+    pub namespace: String,
+    /// Memories already returned in this session; later recalls skip them.
+    pub seen: HashSet<i64>,
+    /// Priming keys (`entity:<name>` / `tag:<name>`) with their activation in
+    /// (0, 1], decayed to the time the state was loaded.
+    pub activation: HashMap<String, f64>,
+    pub last_active: Option<DateTime<Utc>>,
+}
+
+impl SessionState {
+    /// Activation priming `mem`: the strongest activation among its keys.
+    pub fn activation_of(&self, mem: &MemoryRecord) -> f64 {
+        priming_keys(mem)
+            .iter()
+            .filter_map(|key| self.activation.get(key))
+            .fold(0.0, |acc: f64, &a| acc.max(a))
+            .min(1.0)
+    }
+}
+
+/// Entities (fact subject and object) and tags a memory primes, lowercased.
+pub(crate) fn priming_keys(mem: &MemoryRecord) -> Vec<String> {
+    let mut keys = Vec::new();
+    if let MemoryKind::Fact(f) = &mem.kind {
+        keys.push(format!("entity:{}", f.subject.trim().to_lowercase()));
+        keys.push(format!("entity:{}", f.object.trim().to_lowercase()));
+    }
+    for tag in &mem.tags {
+        let tag = tag.trim();
+        if !tag.is_empty() {
+            keys.push(format!("tag:{}", tag.to_lowercase()));
+        }
+    }
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Activation decayed from `updated_at` to `now`.
+pub(crate) fn decay_activation(
+    activation: f64,
+    updated_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let minutes = (now - updated_at).num_seconds().max(0) as f64 / 60.0;
+    activation * 0.5f64.powf(minutes / PRIMING_HALF_LIFE_MINUTES)
+}
+
+/// Whether a session last active at `last_active` has expired by `now`.
+pub(crate) fn is_idle(last_active: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - last_active > chrono::Duration::minutes(SESSION_IDLE_TIMEOUT_MINUTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Fact;
+    use chrono::Duration;
+
+    fn fact(subject: &str, object: &str, tags: &[&str]) -> MemoryRecord {
+        MemoryRecord {
+            id: 1,
+            kind: MemoryKind::Fact(Fact {
+                subject: subject.to_string(),
+                relation: "uses".to_string(),
+                object: object.to_string(),
+            }),
+            strength: 1.0,
+            embedding: None,
+            created_at: Utc::now(),
+            last_accessed_at: Utc::now(),
+            access_count: 0,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            source: None,
+            session_id: None,
+            channel: None,
+            importance: 0.5,
+            namespace: "default".to_string(),
+            checksum: None,
+            temporal: None,
+            language: None,
+        }
+    }
+
+    #[test]
+    fn priming_keys_cover_entities_and_tags() {
+        let keys = priming_keys(&fact("Billing", "Postgres", &["Ops", "ops"]));
+        assert_eq!(keys, vec!["entity:billing", "entity:postgres", "tag:ops"]);
+    }
+
+    #[test]
+    fn activation_halves_every_half_life() {
+        let now = Utc::now();
+        let then = now - Duration::minutes(PRIMING_HALF_LIFE_MINUTES as i64);
+        assert!((decay_activation(1.0, then, now) - 0.5).abs() < 1e-9);
+        assert!(!is_idle(now - Duration::minutes(5), now));
+        assert!(is_idle(
+            now - Duration::minutes(SESSION_IDLE_TIMEOUT_MINUTES + 1),
+            now
+        ));
+    }
+
+    #[test]
+    fn activation_of_takes_the_strongest_key() {
+        let state = SessionState {
+            activation: HashMap::from([
+                ("entity:postgres".to_string(), 0.4),
+                ("tag:ops".to_string(), 0.9),
+            ]),
+            ..Default::default()
+        };
+        assert!((state.activation_of(&fact("Billing", "Postgres", &["ops"])) - 0.9).abs() < 1e-9);
+        assert_eq!(state.activation_of(&fact("Lunch", "pizza", &[])), 0.0);
+    }
+}
@@ -1,15 +1,22 @@
 use chrono::{DateTime, Duration, FixedOffset, Local, Offset, Utc};
 use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::thread::sleep;
 use std::time::{Duration as StdDuration, Instant};
 
+use crate::language::detect_language;
 use crate::memory::{
-    Action, AuditEntry, AuditIntegrityResult, CorruptedMemory, Episode, Fact, Intent, MemoryKind,
-    MemoryRecord, MemoryStats, OperationWriteRetryStats, TamperedAuditEntry, TemporalMetadata,
-    VerifyResult, WriteRetryStats,
+    Action, AuditEntry, AuditIntegrityResult, ConsolidationState, CorruptedMemory, Entity, Episode,
+    Fact, Feedback, FeedbackEntry, Intent, LineageLink, LineageRelation, MemoryKind, MemoryRecord,
+    MemoryStats, OperationWriteRetryStats, TamperedAuditEntry, TemporalMetadata, Topic,
+    VerifyResult, WriteRetryStats, NEEDS_REVIEW_TAG,
 };
+use crate::policy::NamespacePolicy;
+use crate::relations::canonical_relation;
+use crate::schema::RelationSchema;
+use crate::session::{self, SessionState};
 use crate::temporal::extract_temporal_metadata;
 
 pub struct MemoryStore {
@@ -21,6 +28,10 @@ const WRITE_RETRY_BACKOFF_MS: u64 = 25;
 const WRITE_RETRY_MAX_BACKOFF_MS: u64 = 250;
 const WRITE_RETRY_JITTER_PCT: f64 = 0.20;
 
+/// Co-recall association half-life: an association not reinforced for this
+/// long counts half as much.
+const CO_RECALL_HALF_LIFE_DAYS: f64 = 14.0;
+
 impl MemoryStore {
     pub fn conn(&self) -> &Connection {
         &self.conn
@@ -190,9 +201,280 @@ impl MemoryStore {
             self.conn
                 .execute_batch("ALTER TABLE audit_log ADD COLUMN entry_hash TEXT;")?;
         }
+
+        // Per-namespace recall policy
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS namespace_policies (
+                namespace   TEXT PRIMARY KEY,
+                policy_json TEXT NOT NULL,
+                updated_at  TEXT NOT NULL
+            );",
+        )?;
+
+        // Hebbian co-recall associations (mem_a < mem_b)
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS co_recall (
+                mem_a       INTEGER NOT NULL,
+                mem_b       INTEGER NOT NULL,
+                namespace   TEXT NOT NULL DEFAULT 'default',
+                weight      REAL NOT NULL,
+                count       INTEGER NOT NULL DEFAULT 0,
+                last_seen   TEXT NOT NULL,
+                PRIMARY KEY (mem_a, mem_b)
+            );
+            CREATE INDEX IF NOT EXISTS idx_co_recall_mem_b ON co_recall(mem_b);",
+        )?;
+
+        // Migration: add detected language column and backfill existing rows.
+        let has_language: bool = self
+            .conn
+            .prepare("SELECT language FROM memories LIMIT 0")
+            .is_ok();
+        if !has_language {
+            self.conn
+                .execute_batch("ALTER TABLE memories ADD COLUMN language TEXT;")?;
+            self.backfill_languages()?;
+        }
+
+        // Relevance feedback on recalled memories
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS recall_feedback (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                memory_id       INTEGER NOT NULL,
+                namespace       TEXT NOT NULL DEFAULT 'default',
+                query           TEXT NOT NULL,
+                feedback        TEXT NOT NULL CHECK(feedback IN ('useful', 'irrelevant', 'wrong')),
+                strength_before REAL NOT NULL,
+                strength_after  REAL NOT NULL,
+                created_at      TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_recall_feedback_ns ON recall_feedback(namespace, created_at);",
+        )?;
+
+        // Recall sessions: memories already returned and primed entities/tags
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS recall_sessions (
+                session_id  TEXT NOT NULL,
+                namespace   TEXT NOT NULL DEFAULT 'default',
+                last_active TEXT NOT NULL,
+                PRIMARY KEY (session_id, namespace)
+            );
+            CREATE TABLE IF NOT EXISTS recall_session_seen (
+                session_id  TEXT NOT NULL,
+                namespace   TEXT NOT NULL DEFAULT 'default',
+                memory_id   INTEGER NOT NULL,
+                seen_at     TEXT NOT NULL,
+                PRIMARY KEY (session_id, namespace, memory_id)
+            );
+            CREATE TABLE IF NOT EXISTS recall_session_activation (
+                session_id  TEXT NOT NULL,
+                namespace   TEXT NOT NULL DEFAULT 'default',
+                key         TEXT NOT NULL,
+                activation  REAL NOT NULL,
+                updated_at  TEXT NOT NULL,
+                PRIMARY KEY (session_id, namespace, key)
+            );
+            CREATE INDEX IF NOT EXISTS idx_recall_sessions_active ON recall_sessions(last_active);",
+        )?;
+
+        // Migration: archived memories are kept for lineage but no longer recalled.
+        let has_archived_at: bool = self
+            .conn
+            .prepare("SELECT archived_at FROM memories LIMIT 0")
+            .is_ok();
+        if !has_archived_at {
+            self.conn
+                .execute_batch("ALTER TABLE memories ADD COLUMN archived_at TEXT;")?;
+        }
+
+        // Lineage of derived memories (e.g. consolidation summaries)
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS memory_lineage (
+                memory_id   INTEGER NOT NULL,
+                source_id   INTEGER NOT NULL,
+                relation    TEXT NOT NULL,
+                created_at  TEXT NOT NULL,
+                PRIMARY KEY (memory_id, source_id, relation)
+            );
+            CREATE INDEX IF NOT EXISTS idx_memory_lineage_source ON memory_lineage(source_id);",
+        )?;
+
+        // Consolidation watermark and LSH neighbour index
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS consolidation_state (
+                namespace             TEXT PRIMARY KEY,
+                consolidated_through  INTEGER NOT NULL DEFAULT 0,
+                updated_at            TEXT
+            );
+            CREATE TABLE IF NOT EXISTS consolidation_lsh (
+                memory_id  INTEGER NOT NULL,
+                namespace  TEXT NOT NULL,
+                dimension  INTEGER NOT NULL,
+                band       INTEGER NOT NULL,
+                bucket     INTEGER NOT NULL,
+                PRIMARY KEY (memory_id, band)
+            );
+            CREATE INDEX IF NOT EXISTS idx_consolidation_lsh_bucket
+                ON consolidation_lsh(namespace, band, bucket);",
+        )?;
+
+        // Topic clusters and their members
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS topics (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                namespace   TEXT NOT NULL,
+                label       TEXT NOT NULL,
+                keywords    TEXT NOT NULL DEFAULT '[]',
+                created_at  TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_topics_namespace ON topics(namespace);
+            CREATE TABLE IF NOT EXISTS memory_topics (
+                memory_id  INTEGER PRIMARY KEY,
+                topic_id   INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_memory_topics_topic ON memory_topics(topic_id);",
+        )?;
+
+        // Entities mentioned by non-fact memories, for graph traversal
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entity_mentions (
+                memory_id  INTEGER NOT NULL,
+                namespace  TEXT NOT NULL,
+                entity     TEXT NOT NULL COLLATE NOCASE,
+                PRIMARY KEY (memory_id, entity)
+            );
+            CREATE INDEX IF NOT EXISTS idx_entity_mentions_entity
+                ON entity_mentions(namespace, entity);",
+        )?;
+
+        // Canonical entities and the aliases that resolve to them
+        let has_entities = self.conn.prepare("SELECT id FROM entities LIMIT 0").is_ok();
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entities (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                namespace   TEXT NOT NULL,
+                name        TEXT NOT NULL COLLATE NOCASE,
+                created_at  TEXT NOT NULL,
+                UNIQUE (namespace, name)
+            );
+            CREATE TABLE IF NOT EXISTS entity_aliases (
+                namespace  TEXT NOT NULL,
+                alias      TEXT NOT NULL COLLATE NOCASE,
+                entity_id  INTEGER NOT NULL,
+                PRIMARY KEY (namespace, alias)
+            );
+            CREATE INDEX IF NOT EXISTS idx_entity_aliases_entity ON entity_aliases(entity_id);",
+        )?;
+
+        // Namespace relation synonyms (normalized synonym → canonical relation)
+        let has_relation_synonyms = self
+            .conn
+            .prepare("SELECT synonym FROM relation_synonyms LIMIT 0")
+            .is_ok();
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS relation_synonyms (
+                namespace   TEXT NOT NULL,
+                synonym     TEXT NOT NULL,
+                relation    TEXT NOT NULL,
+                created_at  TEXT NOT NULL,
+                PRIMARY KEY (namespace, synonym)
+            );",
+        )?;
+
+        // Declared cardinality, object type and inverse of canonical relations
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS relation_schema (
+                namespace    TEXT NOT NULL,
+                relation     TEXT NOT NULL,
+                cardinality  TEXT NOT NULL,
+                object_type  TEXT NOT NULL,
+                inverse      TEXT,
+                updated_at   TEXT NOT NULL,
+                PRIMARY KEY (namespace, relation)
+            );",
+        )?;
+        if !has_entities {
+            // Register the entities of existing facts; the first spelling
+            // written becomes the canonical name.
+            self.conn.execute_batch(
+                "INSERT OR IGNORE INTO entities (namespace, name, created_at)
+                 SELECT namespace, name, created_at FROM (
+                     SELECT namespace, subject AS name, created_at, id, 0 AS side
+                     FROM memories WHERE kind = 'fact'
+                     UNION ALL
+                     SELECT namespace, object, created_at, id, 1
+                     FROM memories WHERE kind = 'fact'
+                 )
+                 ORDER BY id, side;",
+            )?;
+        }
+        if !has_relation_synonyms {
+            // Facts written before normalization keep their free-text
+            // relations; bring them to the built-in canonical forms so
+            // upserts find them.
+            let namespaces: Vec<String> = {
+                let mut stmt = self
+                    .conn
+                    .prepare("SELECT DISTINCT namespace FROM memories WHERE kind = 'fact'")?;
+                let rows = stmt.query_map([], |row| row.get(0))?;
+                rows.collect::<SqlResult<_>>()?
+            };
+            let builtin = HashMap::new();
+            for namespace in &namespaces {
+                self.rewrite_relations_ns(namespace, |relation| {
+                    canonical_relation(relation, &builtin)
+                })?;
+            }
+        }
+
+        // Migration: remember the state before the last recall touch so
+        // irrelevant feedback can undo it exactly.
+        let has_touch_before: bool = self
+            .conn
+            .prepare("SELECT touch_strength_before FROM memories LIMIT 0")
+            .is_ok();
+        if !has_touch_before {
+            self.conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN touch_strength_before REAL;
+                 ALTER TABLE memories ADD COLUMN touch_accessed_before TEXT;",
+            )?;
+        }
         Ok(())
     }
 
+    fn backfill_languages(&self) -> SqlResult<()> {
+        let rows: Vec<(i64, String)> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT id, kind, subject, relation, object, episode_text FROM memories",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let kind: String = row.get(1)?;
+                let text = if kind == "fact" {
+                    format!(
+                        "{} {} {}",
+                        row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                        row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                        row.get::<_, Option<String>>(4)?.unwrap_or_default()
+                    )
+                } else {
+                    row.get::<_, Option<String>>(5)?.unwrap_or_default()
+                };
+                Ok((row.get(0)?, text))
+            })?;
+            rows.collect::<SqlResult<_>>()?
+        };
+        let tx = self.conn.unchecked_transaction()?;
+        for (id, text) in rows {
+            if let Some(language) = detect_language(&text) {
+                tx.execute(
+                    "UPDATE memories SET language = ?1 WHERE id = ?2",
+                    params![language, id],
+                )?;
+            }
+        }
+        tx.commit()
+    }
+
     fn is_retryable_write_error(err: &rusqlite::Error) -> bool {
         match err {
             rusqlite::Error::SqliteFailure(code, _) => {
@@ -366,11 +648,12 @@ impl MemoryStore {
 
         let temporal_json = extract_temporal_metadata(&content, temporal_anchor_time())
             .and_then(|m| serde_json::to_string(&m).ok());
+        let language = detect_language(&content);
         self.with_write_retry("remember_fact", || {
             self.conn.execute(
-                "INSERT INTO memories (kind, subject, relation, object, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json)
-                 VALUES ('fact', ?1, ?2, ?3, ?4, ?5, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                params![subject, relation, object, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+                "INSERT INTO memories (kind, subject, relation, object, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+                 VALUES ('fact', ?1, ?2, ?3, ?4, ?5, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![subject, relation, object, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
             )
         })?;
         let id = self.conn.last_insert_rowid();
@@ -429,14 +712,15 @@ impl MemoryStore {
             let checksum = compute_checksum(&content);
             let temporal_json = extract_temporal_metadata(&content, temporal_anchor_time())
                 .and_then(|m| serde_json::to_string(&m).ok());
+            let language = detect_language(&content);
             self.with_write_retry("upsert_fact", || {
                 self.conn.execute(
                     "UPDATE memories SET object = ?1, embedding = COALESCE(?2, embedding), \
                      last_accessed_at = ?3, access_count = access_count + 1, \
                      tags = ?4, source = COALESCE(?5, source), \
                      session_id = COALESCE(?6, session_id), channel = COALESCE(?7, channel), \
-                     checksum = ?8, temporal_json = ?9 \
-                     WHERE id = ?10",
+                     checksum = ?8, temporal_json = ?9, language = ?10 \
+                     WHERE id = ?11",
                     params![
                         object,
                         emb_blob,
@@ -447,6 +731,7 @@ impl MemoryStore {
                         channel,
                         checksum,
                         temporal_json,
+                        language,
                         id
                     ],
                 )
@@ -528,11 +813,12 @@ impl MemoryStore {
 
         let temporal_json = extract_temporal_metadata(text, temporal_anchor_time())
             .and_then(|m| serde_json::to_string(&m).ok());
+        let language = detect_language(text);
         self.with_write_retry("remember_episode", || {
             self.conn.execute(
-                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json)
-                 VALUES ('episode', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+                 VALUES ('episode', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
             )
         })?;
         let id = self.conn.last_insert_rowid();
@@ -592,11 +878,12 @@ impl MemoryStore {
         let tags_str = tags.join(",");
         let checksum = compute_checksum(text);
 
+        let language = detect_language(text);
         self.with_write_retry("remember_action", || {
             self.conn.execute(
-                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json)
-                 VALUES ('action', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, Option::<String>::None],
+                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+                 VALUES ('action', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, Option::<String>::None, language],
             )
         })?;
         let id = self.conn.last_insert_rowid();
@@ -656,11 +943,12 @@ impl MemoryStore {
         let tags_str = tags.join(",");
         let checksum = compute_checksum(text);
 
+        let language = detect_language(text);
         self.with_write_retry("remember_intent", || {
             self.conn.execute(
-                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json)
-                 VALUES ('intent', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, Option::<String>::None],
+                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+                 VALUES ('intent', ?1, ?2, ?3, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![text, emb_blob, now, tags_str, source, session_id, channel, namespace, checksum, Option::<String>::None, language],
             )
         })?;
         let id = self.conn.last_insert_rowid();
@@ -689,8 +977,8 @@ impl MemoryStore {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
-             FROM memories WHERE strength > 0.01 AND namespace = ?1",
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories WHERE strength > 0.01 AND namespace = ?1 AND archived_at IS NULL",
         )?;
         let rows = stmt.query_map(params![namespace], |row| {
             let mem = row_to_memory(row)?;
@@ -709,8 +997,8 @@ impl MemoryStore {
             .query_row(
                 "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
-             FROM memories WHERE checksum = ?1 AND namespace = ?2
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories WHERE checksum = ?1 AND namespace = ?2 AND archived_at IS NULL
              ORDER BY id ASC LIMIT 1",
                 params![checksum, namespace],
                 row_to_memory,
@@ -727,9 +1015,9 @@ impl MemoryStore {
             .query_row(
                 "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
              FROM memories
-             WHERE checksum = ?1 AND namespace = ?2 AND kind = 'fact'
+             WHERE checksum = ?1 AND namespace = ?2 AND kind = 'fact' AND archived_at IS NULL
              ORDER BY id ASC LIMIT 1",
                 params![checksum, namespace],
                 row_to_memory,
@@ -753,8 +1041,9 @@ impl MemoryStore {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
-             FROM memories WHERE strength > 0.01 AND tags LIKE ?1 AND namespace = ?2",
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories
+             WHERE strength > 0.01 AND tags LIKE ?1 AND namespace = ?2 AND archived_at IS NULL",
         )?;
         let rows = stmt.query_map(params![pattern, namespace], |row| {
             let mem = row_to_memory(row)?;
@@ -787,7 +1076,8 @@ impl MemoryStore {
     ) -> SqlResult<()> {
         self.with_write_retry("touch_memory_with_strength", || {
             self.conn.execute(
-                "UPDATE memories SET last_accessed_at = ?1, access_count = access_count + 1, strength = ?2 WHERE id = ?3",
+                "UPDATE memories SET touch_strength_before = strength, touch_accessed_before = last_accessed_at,
+                        last_accessed_at = ?1, access_count = access_count + 1, strength = ?2 WHERE id = ?3",
                 params![now.to_rfc3339(), strength.clamp(0.0, 1.0), id],
             )
         })?;
@@ -800,11 +1090,25 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Stored strength of a memory before its last recall touch, if it was
+    /// touched since the last feedback on it.
+    pub fn pre_touch_strength(&self, id: i64) -> SqlResult<Option<f64>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT touch_strength_before FROM memories WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten())
+    }
+
     pub fn get_memory(&self, id: i64) -> SqlResult<Option<MemoryRecord>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
              FROM memories WHERE id = ?1",
         )?;
         let mut rows = stmt.query_map(params![id], row_to_memory)?;
@@ -828,7 +1132,8 @@ impl MemoryStore {
     ) -> SqlResult<usize> {
         let now = Utc::now();
         let mut stmt = self.conn.prepare(
-            "SELECT id, last_accessed_at, strength, importance FROM memories WHERE strength > 0.01 AND namespace = ?1",
+            "SELECT id, last_accessed_at, strength, importance FROM memories
+             WHERE strength > 0.01 AND namespace = ?1 AND archived_at IS NULL",
         )?;
         let rows: Vec<(i64, String, f64, f64)> = stmt
             .query_map(params![namespace], |row| {
@@ -882,7 +1187,7 @@ impl MemoryStore {
     pub fn forget_by_subject_ns(&self, subject: &str, namespace: &str) -> SqlResult<usize> {
         let count = self.with_write_retry("forget_by_subject", || {
             self.conn.execute(
-                "DELETE FROM memories WHERE subject = ?1 AND namespace = ?2",
+                "DELETE FROM memories WHERE subject = ?1 COLLATE NOCASE AND namespace = ?2",
                 params![subject, namespace],
             )
         })?;
@@ -954,13 +1259,45 @@ impl MemoryStore {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
              FROM memories WHERE embedding IS NULL",
         )?;
         let rows = stmt.query_map([], row_to_memory)?;
         rows.collect()
     }
 
+    /// Fetch up to `limit` memories in `namespace` that still lack an embedding,
+    /// ordered by id and starting after `after_id`.
+    ///
+    /// Rows embedded by an earlier (possibly interrupted) pass no longer match,
+    /// so a new pass started from `after_id = 0` resumes where the last one stopped.
+    pub fn memories_missing_embeddings_page_ns(
+        &self,
+        namespace: &str,
+        after_id: i64,
+        limit: usize,
+    ) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, subject, relation, object, episode_text,
+                    strength, embedding, created_at, last_accessed_at, access_count,
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories
+             WHERE embedding IS NULL AND namespace = ?1 AND id > ?2
+             ORDER BY id LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![namespace, after_id, limit as i64], row_to_memory)?;
+        rows.collect()
+    }
+
+    pub fn count_missing_embeddings_ns(&self, namespace: &str) -> SqlResult<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM memories WHERE embedding IS NULL AND namespace = ?1",
+            params![namespace],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     pub fn update_embedding(&self, id: i64, embedding: &[f32]) -> SqlResult<()> {
         self.conn.execute(
             "UPDATE memories SET embedding = ?1 WHERE id = ?2",
@@ -969,13 +1306,31 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Write a batch of embeddings atomically: either every row in the batch is
+    /// updated or none are.
+    pub fn update_embeddings_batch(&self, embeddings: &[(i64, Vec<f32>)]) -> SqlResult<()> {
+        if embeddings.is_empty() {
+            return Ok(());
+        }
+        self.with_write_retry("update_embeddings_batch", || {
+            let tx = self.conn.unchecked_transaction()?;
+            {
+                let mut stmt = tx.prepare("UPDATE memories SET embedding = ?1 WHERE id = ?2")?;
+                for (id, embedding) in embeddings {
+                    stmt.execute(params![embedding_to_blob(embedding), id])?;
+                }
+            }
+            tx.commit()
+        })
+    }
+
     // ── Export / Import ──────────────────────────────────────
 
     pub fn all_memories(&self) -> SqlResult<Vec<MemoryRecord>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
              FROM memories",
         )?;
         let rows = stmt.query_map([], row_to_memory)?;
@@ -986,13 +1341,25 @@ impl MemoryStore {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, subject, relation, object, episode_text,
                     strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
              FROM memories WHERE namespace = ?1",
         )?;
         let rows = stmt.query_map(params![namespace], row_to_memory)?;
         rows.collect()
     }
 
+    /// Memories in a namespace that have not been archived.
+    pub fn active_memories_ns(&self, namespace: &str) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, subject, relation, object, episode_text,
+                    strength, embedding, created_at, last_accessed_at, access_count,
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories WHERE namespace = ?1 AND archived_at IS NULL",
+        )?;
+        let rows = stmt.query_map(params![namespace], row_to_memory)?;
+        rows.collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn import_fact(
         &self,
@@ -1048,10 +1415,11 @@ impl MemoryStore {
         let content = format!("{subject} {relation} {object}");
         let checksum = compute_checksum(&content);
         let temporal_json: Option<String> = None;
+        let language = detect_language(&content);
         self.conn.execute(
-            "INSERT INTO memories (kind, subject, relation, object, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json)
-             VALUES ('fact', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-            params![subject, relation, object, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+            "INSERT INTO memories (kind, subject, relation, object, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+             VALUES ('fact', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            params![subject, relation, object, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -1104,10 +1472,11 @@ impl MemoryStore {
         let tags_str = tags.join(",");
         let checksum = compute_checksum(text);
         let temporal_json: Option<String> = None;
+        let language = detect_language(text);
         self.conn.execute(
-            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json)
-             VALUES ('episode', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+             VALUES ('episode', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -1131,10 +1500,11 @@ impl MemoryStore {
         let tags_str = tags.join(",");
         let checksum = compute_checksum(text);
         let temporal_json: Option<String> = None;
+        let language = detect_language(text);
         self.conn.execute(
-            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json)
-             VALUES ('action', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+             VALUES ('action', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -1158,10 +1528,11 @@ impl MemoryStore {
         let tags_str = tags.join(",");
         let checksum = compute_checksum(text);
         let temporal_json: Option<String> = None;
+        let language = detect_language(text);
         self.conn.execute(
-            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json)
-             VALUES ('intent', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json],
+            "INSERT INTO memories (kind, episode_text, strength, embedding, created_at, last_accessed_at, access_count, tags, source, session_id, channel, namespace, checksum, temporal_json, language)
+             VALUES ('intent', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![text, strength, emb_blob, created_at, last_accessed_at, access_count, tags_str, source, session_id, channel, namespace, checksum, temporal_json, language],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -1189,75 +1560,1449 @@ impl MemoryStore {
         Ok(())
     }
 
-    /// Set strength to zero (archive) for a memory.
-    pub fn archive_memory(&self, id: i64) -> SqlResult<()> {
-        self.with_write_retry("archive_memory", || {
-            self.conn.execute(
-                "UPDATE memories SET strength = 0.0 WHERE id = ?1",
-                params![id],
-            )
-        })?;
-        Ok(())
+    /// Set the strength of a memory.
+    pub fn set_strength(&self, id: i64, strength: f64) -> SqlResult<()> {
+        self.with_write_retry("set_strength", || {
+            self.conn.execute(
+                "UPDATE memories SET strength = ?1 WHERE id = ?2",
+                params![strength.clamp(0.0, 1.0), id],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Set strength to zero (archive) for a memory.
+    pub fn archive_memory(&self, id: i64) -> SqlResult<()> {
+        self.with_write_retry("archive_memory", || {
+            self.conn.execute(
+                "UPDATE memories SET strength = 0.0 WHERE id = ?1",
+                params![id],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Delete a memory by numeric ID.
+    pub fn delete_memory(&self, id: i64) -> SqlResult<()> {
+        self.with_write_retry("delete_memory", || {
+            self.conn
+                .execute("DELETE FROM memories WHERE id = ?1", params![id])
+        })?;
+        Ok(())
+    }
+
+    // ── Dedup ─────────────────────────────────────────────────
+
+    /// Fetch all memory IDs with their embeddings for dedup similarity checks.
+    pub fn all_embeddings(&self) -> SqlResult<Vec<(i64, Vec<f32>)>> {
+        self.all_embeddings_ns("default")
+    }
+
+    pub fn all_embeddings_ns(&self, namespace: &str) -> SqlResult<Vec<(i64, Vec<f32>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL AND namespace = ?1",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            let id: i64 = row.get(0)?;
+            let blob: Vec<u8> = row.get(1)?;
+            Ok((id, blob_to_embedding(&blob)))
+        })?;
+        rows.collect()
+    }
+
+    /// Reinforce an existing memory's strength (clamped to 1.0) and bump access count.
+    pub fn reinforce_memory(&self, id: i64, boost: f64) -> SqlResult<()> {
+        let now = Utc::now().to_rfc3339();
+        self.with_write_retry("reinforce_memory", || {
+            self.conn.execute(
+                "UPDATE memories SET strength = MIN(strength + ?1, 1.0), \
+             last_accessed_at = ?2, access_count = access_count + 1 WHERE id = ?3",
+                params![boost, now, id],
+            )
+        })?;
+        self.log_audit(
+            "reinforce",
+            Some(id),
+            "system",
+            Some(&format!("{{\"boost\":{boost}}}")),
+        )
+    }
+
+    // ── Graph traversal ────────────────────────────────────────
+
+    /// Find all facts where the given entity appears as subject OR object,
+    /// ignoring case.
+    pub fn facts_involving(&self, entity: &str) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, subject, relation, object, episode_text,
+                    strength, embedding, created_at, last_accessed_at, access_count,
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories
+             WHERE kind = 'fact' AND (subject = ?1 COLLATE NOCASE OR object = ?1 COLLATE NOCASE)",
+        )?;
+        let rows = stmt.query_map(params![entity], row_to_memory)?;
+        rows.collect()
+    }
+
+    /// Like [`facts_involving`](Self::facts_involving), limited to one namespace.
+    pub fn facts_involving_ns(
+        &self,
+        entity: &str,
+        namespace: &str,
+    ) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, subject, relation, object, episode_text,
+                    strength, embedding, created_at, last_accessed_at, access_count,
+                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+             FROM memories
+             WHERE kind = 'fact' AND namespace = ?2
+               AND (subject = ?1 COLLATE NOCASE OR object = ?1 COLLATE NOCASE)
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![entity, namespace], row_to_memory)?;
+        rows.collect()
+    }
+
+    // ── Recall feedback ─────────────────────────────────────
+
+    /// Apply a relevance judgement to a memory and log it.
+    ///
+    /// Sets the memory's strength to `strength`. Useful feedback also counts as
+    /// an access; irrelevant feedback takes back the access the recall added;
+    /// wrong feedback tags the memory [`NEEDS_REVIEW_TAG`].
+    pub fn record_feedback(
+        &self,
+        memory: &MemoryRecord,
+        query: &str,
+        feedback: Feedback,
+        strength: f64,
+        now: DateTime<Utc>,
+    ) -> SqlResult<FeedbackEntry> {
+        let strength = strength.clamp(0.0, 1.0);
+        let now_str = now.to_rfc3339();
+        let id = self.with_write_retry("record_feedback", || {
+            let tx = self.conn.unchecked_transaction()?;
+            match feedback {
+                Feedback::Useful => tx.execute(
+                    "UPDATE memories SET strength = ?1, last_accessed_at = ?2, access_count = access_count + 1 WHERE id = ?3",
+                    params![strength, now_str, memory.id],
+                )?,
+                Feedback::Irrelevant => tx.execute(
+                    "UPDATE memories SET strength = ?1,
+                            access_count = CASE WHEN touch_accessed_before IS NOT NULL
+                                THEN MAX(access_count - 1, 0) ELSE access_count END,
+                            last_accessed_at = COALESCE(touch_accessed_before, last_accessed_at)
+                     WHERE id = ?2",
+                    params![strength, memory.id],
+                )?,
+                Feedback::Wrong => {
+                    let mut tags = memory.tags.clone();
+                    if !tags.iter().any(|t| t == NEEDS_REVIEW_TAG) {
+                        tags.push(NEEDS_REVIEW_TAG.to_string());
+                    }
+                    tx.execute(
+                        "UPDATE memories SET strength = ?1, tags = ?2 WHERE id = ?3",
+                        params![strength, tags.join(","), memory.id],
+                    )?
+                }
+            };
+            // A judged touch cannot be undone again.
+            tx.execute(
+                "UPDATE memories SET touch_strength_before = NULL, touch_accessed_before = NULL WHERE id = ?1",
+                params![memory.id],
+            )?;
+            tx.execute(
+                "INSERT INTO recall_feedback (memory_id, namespace, query, feedback, strength_before, strength_after, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![memory.id, memory.namespace, query, feedback.as_str(), memory.strength, strength, now_str],
+            )?;
+            let id = tx.last_insert_rowid();
+            tx.commit()?;
+            Ok(id)
+        })?;
+        let details = serde_json::json!({
+            "query": query,
+            "feedback": feedback.as_str(),
+            "strength_before": memory.strength,
+            "strength_after": strength,
+        });
+        self.log_audit(
+            "feedback",
+            Some(memory.id),
+            "system",
+            Some(&details.to_string()),
+        )?;
+        Ok(FeedbackEntry {
+            id,
+            memory_id: memory.id,
+            namespace: memory.namespace.clone(),
+            query: query.to_string(),
+            feedback,
+            strength_before: memory.strength,
+            strength_after: strength,
+            created_at: now,
+        })
+    }
+
+    /// Logged feedback for a namespace, oldest first.
+    pub fn feedback_log(&self, namespace: &str) -> SqlResult<Vec<FeedbackEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, memory_id, namespace, query, feedback, strength_before, strength_after, created_at
+             FROM recall_feedback WHERE namespace = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            let feedback: String = row.get(4)?;
+            Ok(FeedbackEntry {
+                id: row.get(0)?,
+                memory_id: row.get(1)?,
+                namespace: row.get(2)?,
+                query: row.get(3)?,
+                feedback: feedback.parse().unwrap_or(Feedback::Irrelevant),
+                strength_before: row.get(5)?,
+                strength_after: row.get(6)?,
+                created_at: parse_datetime(&row.get::<_, String>(7)?),
+            })
+        })?;
+        rows.collect()
+    }
+
+    // ── Lineage and archiving ───────────────────────────────────
+
+    /// Link `memory_id` to the memories it was built from.
+    pub fn add_lineage(
+        &self,
+        memory_id: i64,
+        source_ids: &[i64],
+        relation: LineageRelation,
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        let now_str = now.to_rfc3339();
+        self.with_write_retry("add_lineage", || {
+            let tx = self.conn.unchecked_transaction()?;
+            for source_id in source_ids {
+                tx.execute(
+                    "INSERT OR IGNORE INTO memory_lineage (memory_id, source_id, relation, created_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![memory_id, source_id, relation.as_str(), now_str],
+                )?;
+            }
+            tx.commit()
+        })?;
+        let details = serde_json::json!({
+            "relation": relation.as_str(),
+            "sources": source_ids,
+        });
+        self.log_audit(
+            "lineage",
+            Some(memory_id),
+            "system",
+            Some(&details.to_string()),
+        )
+    }
+
+    /// Lineage links in which `id` is either the derived memory or a source.
+    pub fn lineage(&self, id: i64) -> SqlResult<Vec<LineageLink>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT memory_id, source_id, relation, created_at FROM memory_lineage
+             WHERE memory_id = ?1 OR source_id = ?1 ORDER BY memory_id, source_id",
+        )?;
+        let rows = stmt.query_map(params![id], |row| {
+            let relation: String = row.get(2)?;
+            Ok(LineageLink {
+                memory_id: row.get(0)?,
+                source_id: row.get(1)?,
+                relation: relation.parse().unwrap_or(LineageRelation::DerivedFrom),
+                created_at: parse_datetime(&row.get::<_, String>(3)?),
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Archive memories: they stay in the database (and in lineage) but are
+    /// no longer recalled, decayed or consolidated. Returns how many were archived.
+    pub fn archive_memories(&self, ids: &[i64], now: DateTime<Utc>) -> SqlResult<usize> {
+        let now_str = now.to_rfc3339();
+        let archived = self.with_write_retry("archive_memories", || {
+            let tx = self.conn.unchecked_transaction()?;
+            let mut archived = 0;
+            for id in ids {
+                archived += tx.execute(
+                    "UPDATE memories SET archived_at = ?1 WHERE id = ?2 AND archived_at IS NULL",
+                    params![now_str, id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(archived)
+        })?;
+        for id in ids {
+            self.log_audit("archive", Some(*id), "system", None)?;
+        }
+        Ok(archived)
+    }
+
+    /// Store a consolidation summary and retire its sources in one
+    /// transaction: insert the summary as an `action` (if `action`) or an
+    /// episode, link it to `source_ids` and archive them. Returns the summary
+    /// id and how many sources were archived.
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_summary_ns(
+        &self,
+        text: &str,
+        action: bool,
+        embedding: &[f32],
+        tags: &[String],
+        source: &str,
+        source_ids: &[i64],
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<(i64, usize)> {
+        let now_str = now.to_rfc3339();
+        let kind = if action { "action" } else { "episode" };
+        let emb_blob = embedding_to_blob(embedding);
+        let tags_str = tags.join(",");
+        let checksum = compute_checksum(text);
+        let temporal_json = if action {
+            None
+        } else {
+            extract_temporal_metadata(text, temporal_anchor_time())
+                .and_then(|m| serde_json::to_string(&m).ok())
+        };
+        let language = detect_language(text);
+        let relation = LineageRelation::DerivedFrom;
+        let (id, archived) = self.with_write_retry("insert_summary", || {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "INSERT INTO memories (kind, episode_text, embedding, created_at, last_accessed_at, tags, source, namespace, checksum, temporal_json, language)
+                 VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![kind, text, emb_blob, now_str, tags_str, source, namespace, checksum, temporal_json, language],
+            )?;
+            let id = tx.last_insert_rowid();
+            let mut archived = 0;
+            for source_id in source_ids {
+                tx.execute(
+                    "INSERT OR IGNORE INTO memory_lineage (memory_id, source_id, relation, created_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![id, source_id, relation.as_str(), now_str],
+                )?;
+                archived += tx.execute(
+                    "UPDATE memories SET archived_at = ?1 WHERE id = ?2 AND archived_at IS NULL",
+                    params![now_str, source_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok((id, archived))
+        })?;
+
+        let remember = serde_json::json!({ "kind": kind, "namespace": namespace });
+        self.log_audit("remember", Some(id), "system", Some(&remember.to_string()))?;
+        let lineage = serde_json::json!({
+            "relation": relation.as_str(),
+            "sources": source_ids,
+        });
+        self.log_audit("lineage", Some(id), "system", Some(&lineage.to_string()))?;
+        for source_id in source_ids {
+            self.log_audit("archive", Some(*source_id), "system", None)?;
+        }
+        Ok((id, archived))
+    }
+
+    /// When a memory was archived, if it was.
+    pub fn archived_at(&self, id: i64) -> SqlResult<Option<DateTime<Utc>>> {
+        let archived_at: Option<Option<String>> = self
+            .conn
+            .query_row(
+                "SELECT archived_at FROM memories WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(archived_at.flatten().map(|s| parse_datetime(&s)))
+    }
+
+    // ── Consolidation index ─────────────────────────────────────
+
+    /// Consolidation bookkeeping for `namespace` (defaults if never consolidated).
+    pub fn consolidation_state(&self, namespace: &str) -> SqlResult<ConsolidationState> {
+        let state = self
+            .conn
+            .query_row(
+                "SELECT consolidated_through, updated_at
+                 FROM consolidation_state WHERE namespace = ?1",
+                params![namespace],
+                |row| {
+                    Ok(ConsolidationState {
+                        consolidated_through: row.get(0)?,
+                        updated_at: row.get::<_, Option<String>>(1)?.map(|s| parse_datetime(&s)),
+                    })
+                },
+            )
+            .optional()?;
+        Ok(state.unwrap_or_default())
+    }
+
+    /// Record that consolidation has covered every memory up to `through`.
+    pub fn set_consolidated_through(
+        &self,
+        namespace: &str,
+        through: i64,
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        self.with_write_retry("set_consolidated_through", || {
+            self.conn.execute(
+                "INSERT INTO consolidation_state (namespace, consolidated_through, updated_at)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(namespace) DO UPDATE SET
+                    consolidated_through = excluded.consolidated_through,
+                    updated_at = excluded.updated_at",
+                params![namespace, through, now.to_rfc3339()],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Remove index entries for memories that were deleted or archived, or
+    /// whose embedding was dropped or replaced by one of another dimension
+    /// (a new embedding model). Returns how many entries were removed.
+    pub fn prune_consolidation_index(&self, namespace: &str) -> SqlResult<usize> {
+        self.with_write_retry("prune_consolidation_index", || {
+            self.conn.execute(
+                "DELETE FROM consolidation_lsh
+                 WHERE namespace = ?1 AND NOT EXISTS (
+                    SELECT 1 FROM memories m
+                    WHERE m.id = consolidation_lsh.memory_id AND m.archived_at IS NULL
+                      AND length(m.embedding) = 4 * consolidation_lsh.dimension
+                 )",
+                params![namespace],
+            )
+        })
+    }
+
+    /// Active, embedded memories of `namespace` not yet in the neighbour
+    /// index, up to `limit` ordered by id and starting after `after_id`.
+    pub fn unindexed_embeddings_page_ns(
+        &self,
+        namespace: &str,
+        after_id: i64,
+        limit: usize,
+    ) -> SqlResult<Vec<(i64, Vec<f32>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, embedding FROM memories m
+             WHERE namespace = ?1 AND id > ?2 AND archived_at IS NULL AND embedding IS NOT NULL
+               AND NOT EXISTS (SELECT 1 FROM consolidation_lsh l WHERE l.memory_id = m.id)
+             ORDER BY id LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![namespace, after_id, limit as i64], |row| {
+            let blob: Vec<u8> = row.get(1)?;
+            Ok((row.get(0)?, blob_to_embedding(&blob)))
+        })?;
+        rows.collect()
+    }
+
+    pub fn count_unindexed_embeddings_ns(&self, namespace: &str) -> SqlResult<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM memories m
+             WHERE namespace = ?1 AND archived_at IS NULL AND embedding IS NOT NULL
+               AND NOT EXISTS (SELECT 1 FROM consolidation_lsh l WHERE l.memory_id = m.id)",
+            params![namespace],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Active, embedded memories of `namespace`.
+    pub fn count_embedded_ns(&self, namespace: &str) -> SqlResult<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM memories
+             WHERE namespace = ?1 AND archived_at IS NULL AND embedding IS NOT NULL",
+            params![namespace],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Highest memory id in `namespace`, or 0 if it is empty.
+    pub fn max_memory_id_ns(&self, namespace: &str) -> SqlResult<i64> {
+        self.conn.query_row(
+            "SELECT COALESCE(MAX(id), 0) FROM memories WHERE namespace = ?1",
+            params![namespace],
+            |row| row.get(0),
+        )
+    }
+
+    /// Store the LSH band keys of a page of memories in one transaction. Each
+    /// entry is a memory id, its embedding dimension and one key per band.
+    pub fn add_consolidation_keys(
+        &self,
+        namespace: &str,
+        keys: &[(i64, usize, Vec<i64>)],
+    ) -> SqlResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.with_write_retry("add_consolidation_keys", || {
+            let tx = self.conn.unchecked_transaction()?;
+            {
+                let mut stmt = tx.prepare(
+                    "INSERT OR REPLACE INTO consolidation_lsh
+                        (memory_id, namespace, dimension, band, bucket)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )?;
+                for (id, dimension, buckets) in keys {
+                    for (band, bucket) in buckets.iter().enumerate() {
+                        stmt.execute(params![
+                            id,
+                            namespace,
+                            *dimension as i64,
+                            band as i64,
+                            bucket
+                        ])?;
+                    }
+                }
+            }
+            tx.commit()
+        })
+    }
+
+    /// Ids in the neighbour index of `namespace`, up to `limit` ordered by id
+    /// and starting after `after_id`.
+    pub fn indexed_ids_page_ns(
+        &self,
+        namespace: &str,
+        after_id: i64,
+        limit: usize,
+    ) -> SqlResult<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT memory_id FROM consolidation_lsh
+             WHERE namespace = ?1 AND memory_id > ?2
+             ORDER BY memory_id LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![namespace, after_id, limit as i64], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    /// `(band, bucket)` keys shared by more than `max_size` memories. Such
+    /// buckets hold generic content and would produce a quadratic number of
+    /// candidates, so consolidation skips them.
+    pub fn oversized_consolidation_buckets(
+        &self,
+        namespace: &str,
+        max_size: usize,
+    ) -> SqlResult<HashSet<(i64, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT band, bucket FROM consolidation_lsh WHERE namespace = ?1
+             GROUP BY band, bucket HAVING COUNT(*) > ?2",
+        )?;
+        let rows = stmt.query_map(params![namespace, max_size as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.collect()
+    }
+
+    /// Candidate pairs `(a, b)` with `a < b` sharing an LSH bucket, where `a`
+    /// is in `(from_id, to_id]` and `b` is in `(since, through]`. Pairs are
+    /// distinct and in `(a, b)` order.
+    pub fn consolidation_candidates(
+        &self,
+        namespace: &str,
+        from_id: i64,
+        to_id: i64,
+        since: i64,
+        through: i64,
+        skip_buckets: &HashSet<(i64, i64)>,
+    ) -> SqlResult<Vec<(i64, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.memory_id, b.memory_id, a.band, a.bucket
+             FROM consolidation_lsh a
+             JOIN consolidation_lsh b
+               ON b.namespace = a.namespace AND b.band = a.band AND b.bucket = a.bucket
+              AND b.memory_id > a.memory_id AND b.dimension = a.dimension
+             WHERE a.namespace = ?1 AND a.memory_id > ?2 AND a.memory_id <= ?3
+               AND b.memory_id > ?4 AND b.memory_id <= ?5",
+        )?;
+        let rows = stmt.query_map(params![namespace, from_id, to_id, since, through], |row| {
+            Ok((
+                (row.get::<_, i64>(0)?, row.get::<_, i64>(1)?),
+                (row.get::<_, i64>(2)?, row.get::<_, i64>(3)?),
+            ))
+        })?;
+        let mut pairs = Vec::new();
+        for row in rows {
+            let (pair, bucket) = row?;
+            if !skip_buckets.contains(&bucket) {
+                pairs.push(pair);
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        Ok(pairs)
+    }
+
+    /// Memories with the given ids, in id order. Missing ids are skipped.
+    pub fn memories_by_ids(&self, ids: &[i64]) -> SqlResult<Vec<MemoryRecord>> {
+        let mut memories = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT id, kind, subject, relation, object, episode_text,
+                        strength, embedding, created_at, last_accessed_at, access_count,
+                        tags, source, session_id, channel, importance, namespace, checksum, temporal_json, language
+                 FROM memories WHERE id IN ({placeholders})"
+            ))?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), row_to_memory)?;
+            for row in rows {
+                memories.push(row?);
+            }
+        }
+        memories.sort_by_key(|m| m.id);
+        Ok(memories)
+    }
+
+    // ── Topics ──────────────────────────────────────────────────
+
+    /// Replace the topics of `namespace`. Each entry is a label, its keywords
+    /// and the member ids. Returns the new topic ids.
+    pub fn replace_topics(
+        &self,
+        namespace: &str,
+        topics: &[(String, Vec<String>, Vec<i64>)],
+        now: DateTime<Utc>,
+    ) -> SqlResult<Vec<i64>> {
+        let now_str = now.to_rfc3339();
+        let ids = self.with_write_retry("replace_topics", || {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM memory_topics
+                 WHERE topic_id IN (SELECT id FROM topics WHERE namespace = ?1)",
+                params![namespace],
+            )?;
+            tx.execute("DELETE FROM topics WHERE namespace = ?1", params![namespace])?;
+            let mut ids = Vec::with_capacity(topics.len());
+            for (label, keywords, members) in topics {
+                tx.execute(
+                    "INSERT INTO topics (namespace, label, keywords, created_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        namespace,
+                        label,
+                        serde_json::to_string(keywords).unwrap_or_else(|_| "[]".to_string()),
+                        now_str
+                    ],
+                )?;
+                let topic_id = tx.last_insert_rowid();
+                for memory_id in members {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO memory_topics (memory_id, topic_id) VALUES (?1, ?2)",
+                        params![memory_id, topic_id],
+                    )?;
+                }
+                ids.push(topic_id);
+            }
+            tx.commit()?;
+            Ok(ids)
+        })?;
+        let details = serde_json::json!({ "namespace": namespace, "topics": ids.len() });
+        self.log_audit("topics", None, "system", Some(&details.to_string()))?;
+        Ok(ids)
+    }
+
+    /// Topics of `namespace` with their current size and mean strength,
+    /// largest first. Archived or deleted members are not counted.
+    pub fn topics_ns(&self, namespace: &str) -> SqlResult<Vec<Topic>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.namespace, t.label, t.keywords, t.created_at,
+                    COUNT(m.id), COALESCE(AVG(m.strength), 0.0)
+             FROM topics t
+             LEFT JOIN memory_topics mt ON mt.topic_id = t.id
+             LEFT JOIN memories m ON m.id = mt.memory_id AND m.archived_at IS NULL
+             WHERE t.namespace = ?1
+             GROUP BY t.id
+             ORDER BY COUNT(m.id) DESC, t.id",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            let keywords: String = row.get(3)?;
+            Ok(Topic {
+                id: row.get(0)?,
+                namespace: row.get(1)?,
+                label: row.get(2)?,
+                keywords: serde_json::from_str(&keywords).unwrap_or_default(),
+                created_at: parse_datetime(&row.get::<_, String>(4)?),
+                size: row.get::<_, i64>(5)? as usize,
+                strength: row.get(6)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Unarchived members of a topic, strongest first.
+    pub fn topic_memories(&self, topic_id: i64) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT m.id, m.kind, m.subject, m.relation, m.object, m.episode_text,
+                    m.strength, m.embedding, m.created_at, m.last_accessed_at, m.access_count,
+                    m.tags, m.source, m.session_id, m.channel, m.importance, m.namespace, m.checksum, m.temporal_json, m.language
+             FROM memory_topics mt JOIN memories m ON m.id = mt.memory_id
+             WHERE mt.topic_id = ?1 AND m.archived_at IS NULL
+             ORDER BY m.strength DESC, m.id",
+        )?;
+        let rows = stmt.query_map(params![topic_id], row_to_memory)?;
+        rows.collect()
+    }
+
+    /// Ids of the memories assigned to a topic.
+    pub fn topic_member_ids(&self, topic_id: i64) -> SqlResult<HashSet<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT memory_id FROM memory_topics WHERE topic_id = ?1")?;
+        let rows = stmt.query_map(params![topic_id], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    // ── Entity mentions ─────────────────────────────────────────
+
+    /// Replace the entities a memory mentions.
+    pub fn set_mentions(
+        &self,
+        memory_id: i64,
+        namespace: &str,
+        entities: &[String],
+    ) -> SqlResult<()> {
+        self.with_write_retry("set_mentions", || {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM entity_mentions WHERE memory_id = ?1",
+                params![memory_id],
+            )?;
+            for entity in entities {
+                tx.execute(
+                    "INSERT OR IGNORE INTO entity_mentions (memory_id, namespace, entity)
+                     VALUES (?1, ?2, ?3)",
+                    params![memory_id, namespace, entity],
+                )?;
+            }
+            tx.commit()
+        })
+    }
+
+    /// Replace every mention in `namespace` with `mentions` (memory id and the
+    /// entities it mentions). Returns the number of mentions stored.
+    pub fn replace_mentions_ns(
+        &self,
+        namespace: &str,
+        mentions: &[(i64, Vec<String>)],
+    ) -> SqlResult<usize> {
+        self.with_write_retry("replace_mentions", || {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM entity_mentions WHERE namespace = ?1",
+                params![namespace],
+            )?;
+            let mut stored = 0;
+            for (memory_id, entities) in mentions {
+                for entity in entities {
+                    stored += tx.execute(
+                        "INSERT OR IGNORE INTO entity_mentions (memory_id, namespace, entity)
+                         VALUES (?1, ?2, ?3)",
+                        params![memory_id, namespace, entity],
+                    )?;
+                }
+            }
+            tx.commit()?;
+            Ok(stored)
+        })
+    }
+
+    /// Entities a memory mentions, in the order they were found.
+    pub fn mentions_of(&self, memory_id: i64) -> SqlResult<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT entity FROM entity_mentions WHERE memory_id = ?1 ORDER BY rowid")?;
+        let rows = stmt.query_map(params![memory_id], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    /// Mentioned entities of each of `ids` that mentions any.
+    pub fn mentions_for_ids(&self, ids: &[i64]) -> SqlResult<HashMap<i64, Vec<String>>> {
+        let mut mentions: HashMap<i64, Vec<String>> = HashMap::new();
+        for chunk in ids.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT memory_id, entity FROM entity_mentions
+                 WHERE memory_id IN ({placeholders}) ORDER BY rowid"
+            ))?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                let (id, entity) = row?;
+                mentions.entry(id).or_default().push(entity);
+            }
+        }
+        Ok(mentions)
+    }
+
+    /// Memories in `namespace` that mention `entity` (case-insensitive).
+    pub fn memories_mentioning_ns(
+        &self,
+        entity: &str,
+        namespace: &str,
+    ) -> SqlResult<Vec<MemoryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT m.id, m.kind, m.subject, m.relation, m.object, m.episode_text,
+                    m.strength, m.embedding, m.created_at, m.last_accessed_at, m.access_count,
+                    m.tags, m.source, m.session_id, m.channel, m.importance, m.namespace, m.checksum, m.temporal_json, m.language
+             FROM entity_mentions em JOIN memories m ON m.id = em.memory_id
+             WHERE em.entity = ?1 AND em.namespace = ?2
+             ORDER BY m.id",
+        )?;
+        let rows = stmt.query_map(params![entity, namespace], row_to_memory)?;
+        rows.collect()
+    }
+
+    /// Distinct subjects and objects of the unarchived facts in `namespace`.
+    pub fn fact_entities_ns(&self, namespace: &str) -> SqlResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT subject FROM memories
+             WHERE kind = 'fact' AND namespace = ?1 AND archived_at IS NULL
+             UNION
+             SELECT object FROM memories
+             WHERE kind = 'fact' AND namespace = ?1 AND archived_at IS NULL",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    /// Entities mentioned in `namespace` with the number of memories that
+    /// mention each, most mentioned first.
+    pub fn mention_counts_ns(
+        &self,
+        namespace: &str,
+        limit: usize,
+    ) -> SqlResult<Vec<(String, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT em.entity, COUNT(*) AS n
+             FROM entity_mentions em JOIN memories m ON m.id = em.memory_id
+             WHERE em.namespace = ?1
+             GROUP BY em.entity
+             ORDER BY n DESC, em.entity
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![namespace, limit as i64], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+        rows.collect()
+    }
+
+    // ── Entities ────────────────────────────────────────────────
+
+    /// Canonical name of the entity `name` refers to in `namespace`. Entity
+    /// names and aliases match case-insensitively.
+    pub fn canonical_entity_ns(&self, name: &str, namespace: &str) -> SqlResult<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT name FROM entities WHERE name = ?1 AND namespace = ?2
+                 UNION ALL
+                 SELECT e.name FROM entity_aliases a JOIN entities e ON e.id = a.entity_id
+                 WHERE a.alias = ?1 AND a.namespace = ?2
+                 LIMIT 1",
+                params![name, namespace],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Resolve `name` to its canonical entity, registering it as a new entity
+    /// if nothing in the registry matches. Returns the canonical name.
+    pub fn register_entity(
+        &self,
+        name: &str,
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<String> {
+        if let Some(canonical) = self.canonical_entity_ns(name, namespace)? {
+            return Ok(canonical);
+        }
+        let now_str = now.to_rfc3339();
+        self.with_write_retry("register_entity", || {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO entities (namespace, name, created_at) VALUES (?1, ?2, ?3)",
+                params![namespace, name, now_str],
+            )
+        })?;
+        Ok(name.to_string())
+    }
+
+    /// Registered entities of `namespace` with their aliases and the number of
+    /// facts naming them, by name.
+    pub fn entities_ns(&self, namespace: &str) -> SqlResult<Vec<Entity>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT e.id, e.name, e.created_at,
+                    (SELECT COUNT(*) FROM memories m
+                     WHERE m.kind = 'fact' AND m.namespace = e.namespace
+                       AND (m.subject = e.name COLLATE NOCASE OR m.object = e.name COLLATE NOCASE))
+             FROM entities e WHERE e.namespace = ?1
+             ORDER BY e.name",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            Ok(Entity {
+                id: row.get(0)?,
+                namespace: namespace.to_string(),
+                name: row.get(1)?,
+                aliases: Vec::new(),
+                facts: row.get::<_, i64>(3)? as usize,
+                created_at: parse_datetime(&row.get::<_, String>(2)?),
+            })
+        })?;
+        let mut entities = rows.collect::<SqlResult<Vec<_>>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT entity_id, alias FROM entity_aliases WHERE namespace = ?1 ORDER BY alias",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut aliases: HashMap<i64, Vec<String>> = HashMap::new();
+        for row in rows {
+            let (entity_id, alias) = row?;
+            aliases.entry(entity_id).or_default().push(alias);
+        }
+        for entity in &mut entities {
+            entity.aliases = aliases.remove(&entity.id).unwrap_or_default();
+        }
+        Ok(entities)
+    }
+
+    /// Every alias in `namespace` with the canonical name it resolves to.
+    pub fn entity_aliases_ns(&self, namespace: &str) -> SqlResult<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.alias, e.name FROM entity_aliases a JOIN entities e ON e.id = a.entity_id
+             WHERE a.namespace = ?1 ORDER BY a.alias",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+
+    /// Fold the name `from` into the registered entity `into`: `from` becomes
+    /// an alias of `into` (taking its own aliases along if it was an entity),
+    /// and facts and mentions spelled `from` are rewritten to `into`, with
+    /// fresh checksums. Returns the number of facts rewritten.
+    pub fn merge_entity_ns(&self, from: &str, into: &str, namespace: &str) -> SqlResult<usize> {
+        let rewritten = self.with_write_retry("merge_entity", || {
+            let tx = self.conn.unchecked_transaction()?;
+            let into_id: i64 = tx.query_row(
+                "SELECT id FROM entities WHERE name = ?1 AND namespace = ?2",
+                params![into, namespace],
+                |row| row.get(0),
+            )?;
+            let from_id: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM entities WHERE name = ?1 AND namespace = ?2",
+                    params![from, namespace],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(from_id) = from_id {
+                tx.execute(
+                    "UPDATE entity_aliases SET entity_id = ?1 WHERE entity_id = ?2",
+                    params![into_id, from_id],
+                )?;
+                tx.execute("DELETE FROM entities WHERE id = ?1", params![from_id])?;
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO entity_aliases (namespace, alias, entity_id)
+                 VALUES (?1, ?2, ?3)",
+                params![namespace, from, into_id],
+            )?;
+
+            let facts: Vec<(i64, String, String, String)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT id, subject, relation, object FROM memories
+                     WHERE kind = 'fact' AND namespace = ?2
+                       AND (subject = ?1 COLLATE NOCASE OR object = ?1 COLLATE NOCASE)",
+                )?;
+                let rows = stmt.query_map(params![from, namespace], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?;
+                rows.collect::<SqlResult<_>>()?
+            };
+            for (id, subject, relation, object) in &facts {
+                let rename = |name: &String| {
+                    if name.eq_ignore_ascii_case(from) {
+                        into.to_string()
+                    } else {
+                        name.clone()
+                    }
+                };
+                let (subject, object) = (rename(subject), rename(object));
+                tx.execute(
+                    "UPDATE memories SET subject = ?1, object = ?2, checksum = ?3 WHERE id = ?4",
+                    params![
+                        subject,
+                        object,
+                        compute_checksum(&format!("{subject} {relation} {object}")),
+                        id
+                    ],
+                )?;
+            }
+
+            tx.execute(
+                "UPDATE OR IGNORE entity_mentions SET entity = ?1 WHERE entity = ?2 AND namespace = ?3",
+                params![into, from, namespace],
+            )?;
+            tx.execute(
+                "DELETE FROM entity_mentions WHERE entity = ?1 AND namespace = ?2",
+                params![from, namespace],
+            )?;
+            tx.commit()?;
+            Ok(facts.len())
+        })?;
+        let details = serde_json::json!({
+            "namespace": namespace,
+            "from": from,
+            "into": into,
+            "facts": rewritten,
+        });
+        self.log_audit("entity_merge", None, "system", Some(&details.to_string()))?;
+        Ok(rewritten)
+    }
+
+    // ── Relations ───────────────────────────────────────────────
+
+    /// Relation synonyms of `namespace`, keyed by normalized synonym.
+    pub fn relation_synonyms_ns(&self, namespace: &str) -> SqlResult<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT synonym, relation FROM relation_synonyms WHERE namespace = ?1")?;
+        let rows = stmt.query_map(params![namespace], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+
+    /// Map `synonym` to `relation` in `namespace`. Synonyms that pointed at
+    /// `synonym` are repointed at `relation`, so lookups stay one step.
+    pub fn add_relation_synonym(
+        &self,
+        synonym: &str,
+        relation: &str,
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        let now_str = now.to_rfc3339();
+        self.with_write_retry("add_relation_synonym", || {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "INSERT OR REPLACE INTO relation_synonyms (namespace, synonym, relation, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![namespace, synonym, relation, now_str],
+            )?;
+            tx.execute(
+                "UPDATE relation_synonyms SET relation = ?1 WHERE relation = ?2 AND namespace = ?3",
+                params![relation, synonym, namespace],
+            )?;
+            tx.commit()
+        })?;
+        let details = serde_json::json!({
+            "namespace": namespace,
+            "synonym": synonym,
+            "relation": relation,
+        });
+        self.log_audit(
+            "relation_synonym",
+            None,
+            "system",
+            Some(&details.to_string()),
+        )?;
+        Ok(())
+    }
+
+    /// Rewrite the relation of every fact in `namespace` to `canonical(relation)`
+    /// where that differs, with fresh checksums. Returns the number of facts
+    /// rewritten.
+    pub fn rewrite_relations_ns(
+        &self,
+        namespace: &str,
+        canonical: impl Fn(&str) -> String,
+    ) -> SqlResult<usize> {
+        let rewritten = self.with_write_retry("rewrite_relations", || {
+            let tx = self.conn.unchecked_transaction()?;
+            let facts: Vec<(i64, String, String, String)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT id, subject, relation, object FROM memories
+                     WHERE kind = 'fact' AND namespace = ?1",
+                )?;
+                let rows = stmt.query_map(params![namespace], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?;
+                rows.collect::<SqlResult<_>>()?
+            };
+            let mut rewritten = 0;
+            for (id, subject, relation, object) in &facts {
+                let relation_canonical = canonical(relation);
+                if relation_canonical == *relation {
+                    continue;
+                }
+                tx.execute(
+                    "UPDATE memories SET relation = ?1, checksum = ?2 WHERE id = ?3",
+                    params![
+                        relation_canonical,
+                        compute_checksum(&format!("{subject} {relation_canonical} {object}")),
+                        id
+                    ],
+                )?;
+                rewritten += 1;
+            }
+            tx.commit()?;
+            Ok(rewritten)
+        })?;
+        if rewritten > 0 {
+            let details = serde_json::json!({ "namespace": namespace, "facts": rewritten });
+            self.log_audit(
+                "relation_normalize",
+                None,
+                "system",
+                Some(&details.to_string()),
+            )?;
+        }
+        Ok(rewritten)
+    }
+
+    /// Relations used by facts in `namespace` with their fact counts, most
+    /// used first.
+    pub fn relation_counts_ns(&self, namespace: &str) -> SqlResult<Vec<(String, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT relation, COUNT(*) AS n FROM memories
+             WHERE kind = 'fact' AND namespace = ?1
+             GROUP BY relation
+             ORDER BY n DESC, relation",
+        )?;
+        let rows = stmt.query_map(params![namespace], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+        rows.collect()
+    }
+
+    // ── Relation schema ─────────────────────────────────────────
+
+    fn row_to_relation_schema(row: &rusqlite::Row) -> SqlResult<RelationSchema> {
+        let parse_err = |i: usize, e: String| {
+            rusqlite::Error::FromSqlConversionFailure(i, rusqlite::types::Type::Text, e.into())
+        };
+        let cardinality: String = row.get(1)?;
+        let object_type: String = row.get(2)?;
+        Ok(RelationSchema {
+            relation: row.get(0)?,
+            cardinality: cardinality.parse().map_err(|e| parse_err(1, e))?,
+            object_type: object_type.parse().map_err(|e| parse_err(2, e))?,
+            inverse: row.get(3)?,
+        })
+    }
+
+    /// Schema of the canonical `relation` in `namespace`, if one was declared.
+    pub fn relation_schema_ns(
+        &self,
+        relation: &str,
+        namespace: &str,
+    ) -> SqlResult<Option<RelationSchema>> {
+        self.conn
+            .query_row(
+                "SELECT relation, cardinality, object_type, inverse FROM relation_schema
+                 WHERE relation = ?1 AND namespace = ?2",
+                params![relation, namespace],
+                Self::row_to_relation_schema,
+            )
+            .optional()
+    }
+
+    /// Every relation schema declared in `namespace`, by relation.
+    pub fn relation_schemas_ns(&self, namespace: &str) -> SqlResult<Vec<RelationSchema>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT relation, cardinality, object_type, inverse FROM relation_schema
+             WHERE namespace = ?1 ORDER BY relation",
+        )?;
+        let rows = stmt.query_map(params![namespace], Self::row_to_relation_schema)?;
+        rows.collect()
+    }
+
+    /// Declare (or replace) the schema of a relation in `namespace`.
+    pub fn set_relation_schema(
+        &self,
+        schema: &RelationSchema,
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        let now_str = now.to_rfc3339();
+        self.with_write_retry("set_relation_schema", || {
+            self.conn.execute(
+                "INSERT INTO relation_schema
+                     (namespace, relation, cardinality, object_type, inverse, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(namespace, relation) DO UPDATE SET
+                     cardinality = ?3, object_type = ?4, inverse = ?5, updated_at = ?6",
+                params![
+                    namespace,
+                    schema.relation,
+                    schema.cardinality.as_str(),
+                    schema.object_type.as_str(),
+                    schema.inverse,
+                    now_str
+                ],
+            )
+        })?;
+        let details = serde_json::json!({ "namespace": namespace, "schema": schema });
+        self.log_audit(
+            "relation_schema",
+            None,
+            "system",
+            Some(&details.to_string()),
+        )?;
+        Ok(())
+    }
+
+    // ── Recall sessions ─────────────────────────────────────────
+
+    /// State of a recall session as of `now`. An unknown or idle session
+    /// (see [`SESSION_IDLE_TIMEOUT_MINUTES`](crate::session::SESSION_IDLE_TIMEOUT_MINUTES))
+    /// comes back empty.
+    pub fn recall_session(
+        &self,
+        session_id: &str,
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<SessionState> {
+        let mut state = SessionState {
+            session_id: session_id.to_string(),
+            namespace: namespace.to_string(),
+            ..Default::default()
+        };
+        let last_active: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT last_active FROM recall_sessions WHERE session_id = ?1 AND namespace = ?2",
+                params![session_id, namespace],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(last_active) = last_active.map(|s| parse_datetime(&s)) else {
+            return Ok(state);
+        };
+        if session::is_idle(last_active, now) {
+            return Ok(state);
+        }
+        state.last_active = Some(last_active);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT memory_id FROM recall_session_seen WHERE session_id = ?1 AND namespace = ?2",
+        )?;
+        state.seen = stmt
+            .query_map(params![session_id, namespace], |row| row.get(0))?
+            .collect::<SqlResult<_>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT key, activation, updated_at FROM recall_session_activation
+             WHERE session_id = ?1 AND namespace = ?2",
+        )?;
+        let rows = stmt.query_map(params![session_id, namespace], |row| {
+            let updated_at = parse_datetime(&row.get::<_, String>(2)?);
+            Ok((
+                row.get::<_, String>(0)?,
+                session::decay_activation(row.get(1)?, updated_at, now),
+            ))
+        })?;
+        for row in rows {
+            let (key, activation) = row?;
+            if activation >= session::MIN_ACTIVATION {
+                state.activation.insert(key, activation);
+            }
+        }
+        Ok(state)
+    }
+
+    /// Record one recall in a session: `seen` memories are suppressed from
+    /// later recalls and each `primed` key is refreshed to full activation.
+    /// Idle sessions (including this one, if it had gone idle) are dropped first.
+    pub fn record_recall_session(
+        &self,
+        session_id: &str,
+        namespace: &str,
+        seen: &[i64],
+        primed: &[String],
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        let now_str = now.to_rfc3339();
+        let cutoff = (now - Duration::minutes(session::SESSION_IDLE_TIMEOUT_MINUTES)).to_rfc3339();
+        self.with_write_retry("record_recall_session", || {
+            let tx = self.conn.unchecked_transaction()?;
+            for table in ["recall_session_seen", "recall_session_activation"] {
+                tx.execute(
+                    &format!(
+                        "DELETE FROM {table} WHERE (session_id, namespace) IN
+                         (SELECT session_id, namespace FROM recall_sessions WHERE last_active < ?1)"
+                    ),
+                    params![cutoff],
+                )?;
+            }
+            tx.execute(
+                "DELETE FROM recall_sessions WHERE last_active < ?1",
+                params![cutoff],
+            )?;
+            tx.execute(
+                "INSERT INTO recall_sessions (session_id, namespace, last_active)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(session_id, namespace) DO UPDATE SET last_active = ?3",
+                params![session_id, namespace, now_str],
+            )?;
+            for id in seen {
+                tx.execute(
+                    "INSERT OR IGNORE INTO recall_session_seen (session_id, namespace, memory_id, seen_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![session_id, namespace, id, now_str],
+                )?;
+            }
+            for key in primed {
+                tx.execute(
+                    "INSERT INTO recall_session_activation (session_id, namespace, key, activation, updated_at)
+                     VALUES (?1, ?2, ?3, 1.0, ?4)
+                     ON CONFLICT(session_id, namespace, key) DO UPDATE SET activation = 1.0, updated_at = ?4",
+                    params![session_id, namespace, key, now_str],
+                )?;
+            }
+            tx.commit()
+        })
     }
 
-    /// Delete a memory by numeric ID.
-    pub fn delete_memory(&self, id: i64) -> SqlResult<()> {
-        self.with_write_retry("delete_memory", || {
-            self.conn
-                .execute("DELETE FROM memories WHERE id = ?1", params![id])
-        })?;
-        Ok(())
+    /// Forget a recall session. Returns whether it existed.
+    pub fn end_recall_session(&self, session_id: &str, namespace: &str) -> SqlResult<bool> {
+        self.with_write_retry("end_recall_session", || {
+            let tx = self.conn.unchecked_transaction()?;
+            for table in ["recall_session_seen", "recall_session_activation"] {
+                tx.execute(
+                    &format!("DELETE FROM {table} WHERE session_id = ?1 AND namespace = ?2"),
+                    params![session_id, namespace],
+                )?;
+            }
+            let removed = tx.execute(
+                "DELETE FROM recall_sessions WHERE session_id = ?1 AND namespace = ?2",
+                params![session_id, namespace],
+            )?;
+            tx.commit()?;
+            Ok(removed > 0)
+        })
     }
 
-    // ── Dedup ─────────────────────────────────────────────────
+    // ── Co-recall (Hebbian associations) ───────────────────────
 
-    /// Fetch all memory IDs with their embeddings for dedup similarity checks.
-    pub fn all_embeddings(&self) -> SqlResult<Vec<(i64, Vec<f32>)>> {
-        self.all_embeddings_ns("default")
+    /// Strengthen the association between every pair of memories recalled together.
+    ///
+    /// Each pair's weight is first decayed to `now`, then incremented by 1.
+    pub fn record_co_recall(
+        &self,
+        ids: &[i64],
+        namespace: &str,
+        now: DateTime<Utc>,
+    ) -> SqlResult<()> {
+        if ids.len() < 2 {
+            return Ok(());
+        }
+        let mut pairs = Vec::new();
+        for (i, &a) in ids.iter().enumerate() {
+            for &b in &ids[i + 1..] {
+                if a != b {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+        let now_str = now.to_rfc3339();
+        self.with_write_retry("record_co_recall", || {
+            let tx = self.conn.unchecked_transaction()?;
+            for &(a, b) in &pairs {
+                let existing: Option<(f64, String)> = tx
+                    .query_row(
+                        "SELECT weight, last_seen FROM co_recall WHERE mem_a = ?1 AND mem_b = ?2",
+                        params![a, b],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                let decayed = existing
+                    .map(|(weight, last_seen)| {
+                        decay_co_recall_weight(weight, parse_datetime(&last_seen), now)
+                    })
+                    .unwrap_or(0.0);
+                tx.execute(
+                    "INSERT INTO co_recall (mem_a, mem_b, namespace, weight, count, last_seen)
+                     VALUES (?1, ?2, ?3, ?4, 1, ?5)
+                     ON CONFLICT(mem_a, mem_b) DO UPDATE
+                     SET weight = ?4, count = count + 1, last_seen = ?5",
+                    params![a, b, namespace, decayed + 1.0, now_str],
+                )?;
+            }
+            tx.commit()
+        })
     }
 
-    pub fn all_embeddings_ns(&self, namespace: &str) -> SqlResult<Vec<(i64, Vec<f32>)>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL AND namespace = ?1",
-        )?;
-        let rows = stmt.query_map(params![namespace], |row| {
-            let id: i64 = row.get(0)?;
-            let blob: Vec<u8> = row.get(1)?;
-            Ok((id, blob_to_embedding(&blob)))
+    /// Current (decayed) association weights for every pair within `ids`.
+    /// Keys are `(smaller_id, larger_id)`.
+    pub fn co_recall_weights(
+        &self,
+        ids: &[i64],
+        now: DateTime<Utc>,
+    ) -> SqlResult<HashMap<(i64, i64), f64>> {
+        if ids.len() < 2 {
+            return Ok(HashMap::new());
+        }
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "SELECT mem_a, mem_b, weight, last_seen FROM co_recall
+             WHERE mem_a IN ({placeholders}) AND mem_b IN ({placeholders})"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params: Vec<&dyn rusqlite::ToSql> = ids
+            .iter()
+            .chain(ids.iter())
+            .map(|id| id as &dyn rusqlite::ToSql)
+            .collect();
+        let rows = stmt.query_map(params.as_slice(), |row| {
+            let last_seen: String = row.get(3)?;
+            Ok((
+                (row.get::<_, i64>(0)?, row.get::<_, i64>(1)?),
+                decay_co_recall_weight(row.get(2)?, parse_datetime(&last_seen), now),
+            ))
         })?;
         rows.collect()
     }
 
-    /// Reinforce an existing memory's strength (clamped to 1.0) and bump access count.
-    pub fn reinforce_memory(&self, id: i64, boost: f64) -> SqlResult<()> {
-        let now = Utc::now().to_rfc3339();
-        self.with_write_retry("reinforce_memory", || {
-            self.conn.execute(
-                "UPDATE memories SET strength = MIN(strength + ?1, 1.0), \
-             last_accessed_at = ?2, access_count = access_count + 1 WHERE id = ?3",
-                params![boost, now, id],
-            )
-        })?;
-        self.log_audit(
-            "reinforce",
-            Some(id),
-            "system",
-            Some(&format!("{{\"boost\":{boost}}}")),
-        )
-    }
-
-    // ── Graph traversal ────────────────────────────────────────
-
-    /// Find all facts where the given entity appears as subject OR object.
-    pub fn facts_involving(&self, entity: &str) -> SqlResult<Vec<MemoryRecord>> {
+    /// Strongest co-recall associations of one memory with partners in
+    /// `namespace`, skipping partners that no longer exist. Weights are decayed
+    /// to `now`.
+    pub fn co_recall_associations(
+        &self,
+        id: i64,
+        namespace: &str,
+        limit: usize,
+        now: DateTime<Utc>,
+    ) -> SqlResult<Vec<CoRecallLink>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, kind, subject, relation, object, episode_text,
-                    strength, embedding, created_at, last_accessed_at, access_count,
-                    tags, source, session_id, channel, importance, namespace, checksum, temporal_json
-             FROM memories WHERE kind = 'fact' AND (subject = ?1 OR object = ?1)",
+            "SELECT CASE WHEN c.mem_a = ?1 THEN c.mem_b ELSE c.mem_a END AS other,
+                    c.weight, c.count, c.last_seen
+             FROM co_recall c
+             JOIN memories m ON m.id = (CASE WHEN c.mem_a = ?1 THEN c.mem_b ELSE c.mem_a END)
+             WHERE (c.mem_a = ?1 OR c.mem_b = ?1) AND m.namespace = ?2",
         )?;
-        let rows = stmt.query_map(params![entity], row_to_memory)?;
-        rows.collect()
+        let mut rows: Vec<CoRecallLink> = stmt
+            .query_map(params![id, namespace], |row| {
+                let last_seen = parse_datetime(&row.get::<_, String>(3)?);
+                Ok(CoRecallLink {
+                    memory_id: row.get(0)?,
+                    weight: decay_co_recall_weight(row.get(1)?, last_seen, now),
+                    count: row.get(2)?,
+                    last_seen,
+                })
+            })?
+            .collect::<SqlResult<Vec<_>>>()?;
+        rows.sort_by(|a, b| {
+            b.weight
+                .partial_cmp(&a.weight)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.memory_id.cmp(&b.memory_id))
+        });
+        rows.truncate(limit);
+        Ok(rows)
     }
 
     // ── Stats ────────────────────────────────────────────────
@@ -1601,6 +3346,54 @@ impl MemoryStore {
             missing_checksum,
         })
     }
+
+    // ── Namespace policy ────────────────────────────────────
+
+    /// Load the recall policy for a namespace, or the default policy if none was set.
+    pub fn namespace_policy(&self, namespace: &str) -> SqlResult<NamespacePolicy> {
+        let json: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT policy_json FROM namespace_policies WHERE namespace = ?1",
+                params![namespace],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match json {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            }),
+            None => Ok(NamespacePolicy::default()),
+        }
+    }
+
+    pub fn set_namespace_policy(&self, namespace: &str, policy: &NamespacePolicy) -> SqlResult<()> {
+        let json = serde_json::to_string(policy)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let now = Utc::now().to_rfc3339();
+        self.with_write_retry("set_namespace_policy", || {
+            self.conn.execute(
+                "INSERT INTO namespace_policies (namespace, policy_json, updated_at)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(namespace) DO UPDATE SET policy_json = ?2, updated_at = ?3",
+                params![namespace, json, now],
+            )
+        })?;
+        self.log_audit(
+            "policy_update",
+            None,
+            "system",
+            Some(&format!(
+                "{{\"namespace\":{},\"policy\":{}}}",
+                serde_json::json!(namespace),
+                json
+            )),
+        )
+    }
 }
 
 // ── Helpers ──────────────────────────────────────────────────
@@ -1615,6 +3408,11 @@ fn percentile_u64(samples: &mut [u64], percentile: f64) -> Option<u64> {
     samples.get(idx).copied()
 }
 
+fn decay_co_recall_weight(weight: f64, last_seen: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let days = (now - last_seen).num_seconds().max(0) as f64 / 86_400.0;
+    weight * 0.5f64.powf(days / CO_RECALL_HALF_LIFE_DAYS)
+}
+
 fn compute_audit_hash(input: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
@@ -1730,6 +3528,7 @@ fn parse_datetime(s: &str) -> DateTime<Utc> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::recall::FusionStrategy;
 
     #[test]
     fn remember_fact_with_tags_stores_and_retrieves() {
@@ -3015,6 +4814,285 @@ mod tests {
         assert!(matches!(mem.kind, MemoryKind::Intent(_)));
         let _ = std::fs::remove_file(&path);
     }
+
+    // ── Namespace policy tests ──────────────────────────────
+
+    #[test]
+    fn namespace_policy_defaults_when_unset() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        assert_eq!(
+            store.namespace_policy("default").unwrap(),
+            NamespacePolicy::default()
+        );
+    }
+
+    #[test]
+    fn namespace_policy_roundtrips_and_is_isolated() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let policy = NamespacePolicy {
+            rerank: true,
+            fusion: Some(FusionStrategy::KeywordOnly),
+            language: Some("deu".to_string()),
+            coefficients: None,
+            context_weights: "channel=0.5".parse().ok(),
+            extract_facts: true,
+        };
+        store.set_namespace_policy("team", &policy).unwrap();
+
+        assert_eq!(store.namespace_policy("team").unwrap(), policy);
+        assert!(!store.namespace_policy("default").unwrap().rerank);
+
+        let log = store.get_audit_log(10, None, None).unwrap();
+        assert!(log.iter().any(|e| e.action == "policy_update"));
+    }
+
+    #[test]
+    fn merging_an_entity_rewrites_facts_and_mentions() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let now = Utc::now();
+        assert_eq!(
+            store.register_entity("Jared", "default", now).unwrap(),
+            "Jared"
+        );
+        assert_eq!(
+            store.register_entity("JARED", "default", now).unwrap(),
+            "Jared"
+        );
+        store.register_entity("JG", "default", now).unwrap();
+        let fact = store.remember_fact("JG", "likes", "Rust", None).unwrap();
+        let note = store.remember_episode("lunch with JG", None).unwrap();
+        store
+            .set_mentions(note, "default", &["JG".to_string()])
+            .unwrap();
+
+        assert_eq!(store.merge_entity_ns("JG", "Jared", "default").unwrap(), 1);
+        assert_eq!(
+            store
+                .canonical_entity_ns("jg", "default")
+                .unwrap()
+                .as_deref(),
+            Some("Jared")
+        );
+        let merged = store.get_memory(fact).unwrap().unwrap();
+        assert_eq!(merged.subject(), Some("Jared"));
+        assert!(store.verify_integrity().unwrap().corrupted.is_empty());
+        assert_eq!(store.mentions_of(note).unwrap(), vec!["Jared"]);
+
+        let entities = store.entities_ns("default").unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].aliases, vec!["JG"]);
+        assert_eq!(entities[0].facts, 1);
+        let log = store.get_audit_log(10, None, None).unwrap();
+        assert!(log.iter().any(|e| e.action == "entity_merge"));
+    }
+
+    #[test]
+    fn co_recall_accumulates_and_decays() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let a = store.remember_episode("deploy checklist", None).unwrap();
+        let b = store.remember_episode("rollback plan", None).unwrap();
+        let c = store.remember_episode("lunch order", None).unwrap();
+        let now = Utc::now();
+
+        store.record_co_recall(&[a, b], "default", now).unwrap();
+        store.record_co_recall(&[b, a], "default", now).unwrap();
+
+        let weights = store.co_recall_weights(&[a, b, c], now).unwrap();
+        assert_eq!(weights.len(), 1);
+        assert!((weights[&(a.min(b), a.max(b))] - 2.0).abs() < 1e-9);
+
+        let later = now + Duration::days(CO_RECALL_HALF_LIFE_DAYS as i64);
+        let decayed = store.co_recall_weights(&[a, b], later).unwrap();
+        assert!((decayed[&(a.min(b), a.max(b))] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn co_recall_associations_skip_deleted_memories() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let a = store.remember_episode("deploy checklist", None).unwrap();
+        let b = store.remember_episode("rollback plan", None).unwrap();
+        let c = store.remember_episode("on-call rota", None).unwrap();
+        let now = Utc::now();
+        store.record_co_recall(&[a, b, c], "default", now).unwrap();
+        store.record_co_recall(&[a, b], "default", now).unwrap();
+
+        let assoc = store.co_recall_associations(a, "default", 10, now).unwrap();
+        assert_eq!(
+            assoc.iter().map(|r| r.memory_id).collect::<Vec<_>>(),
+            vec![b, c]
+        );
+        assert_eq!(assoc[0].count, 2);
+
+        store.delete_memory(b).unwrap();
+        let assoc = store.co_recall_associations(a, "default", 10, now).unwrap();
+        assert_eq!(
+            assoc.iter().map(|r| r.memory_id).collect::<Vec<_>>(),
+            vec![c]
+        );
+    }
+
+    // ── Language tests ──────────────────────────────────────
+
+    #[test]
+    fn language_is_detected_on_write() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let german = store
+            .remember_episode("Die Kunden warten seit Montag auf ihre Rechnungen", None)
+            .unwrap();
+        let short = store.remember_episode("ok", None).unwrap();
+
+        let mem = store.get_memory(german).unwrap().unwrap();
+        assert_eq!(mem.language.as_deref(), Some("deu"));
+        let mem = store.get_memory(short).unwrap().unwrap();
+        assert_eq!(mem.language, None);
+    }
+
+    #[test]
+    fn language_migration_backfills_existing_rows() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let id = store
+            .remember_episode(
+                "El despliegue terminó y el servicio se reinició sin problemas",
+                None,
+            )
+            .unwrap();
+        store
+            .conn()
+            .execute_batch("ALTER TABLE memories DROP COLUMN language;")
+            .unwrap();
+
+        store.init_schema().unwrap();
+        let mem = store.get_memory(id).unwrap().unwrap();
+        assert_eq!(mem.language.as_deref(), Some("spa"));
+    }
+
+    #[test]
+    fn relation_migration_normalizes_legacy_facts() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let id = store
+            .remember_fact("Jared", "Works At", "Microsoft", None)
+            .unwrap();
+        store
+            .remember_fact_ns(
+                "Jared",
+                "employed by",
+                "Acme",
+                None,
+                &[],
+                None,
+                None,
+                None,
+                "work",
+            )
+            .unwrap();
+        store
+            .conn()
+            .execute_batch("DROP TABLE relation_synonyms;")
+            .unwrap();
+
+        store.init_schema().unwrap();
+        let (updated_id, updated) = store
+            .upsert_fact("Jared", "works_at", "Google", None, &[], None, None, None)
+            .unwrap();
+        assert!(updated);
+        assert_eq!(updated_id, id);
+        assert_eq!(store.all_memories_ns("default").unwrap().len(), 1);
+        assert_eq!(
+            store.relation_counts_ns("work").unwrap(),
+            vec![("works_at".to_string(), 1)]
+        );
+    }
+
+    // ── Recall feedback tests ───────────────────────────────
+
+    #[test]
+    fn wrong_feedback_tags_for_review_and_is_logged() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let id = store
+            .remember_fact("Jared", "works_at", "Google", None)
+            .unwrap();
+        let mem = store.get_memory(id).unwrap().unwrap();
+
+        let entry = store
+            .record_feedback(
+                &mem,
+                "where does Jared work",
+                Feedback::Wrong,
+                0.5,
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(entry.strength_after, 0.5);
+
+        let mem = store.get_memory(id).unwrap().unwrap();
+        assert_eq!(mem.strength, 0.5);
+        assert_eq!(mem.tags, vec![NEEDS_REVIEW_TAG.to_string()]);
+
+        let log = store.feedback_log("default").unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].feedback, Feedback::Wrong);
+        assert_eq!(log[0].query, "where does Jared work");
+        assert!(store.feedback_log("other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn irrelevant_feedback_takes_back_the_recall_access() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let id = store.remember_episode("standup notes", None).unwrap();
+        store
+            .touch_memory_with_strength_context(id, 1.0, Utc::now(), None)
+            .unwrap();
+        let mem = store.get_memory(id).unwrap().unwrap();
+        assert_eq!(mem.access_count, 1);
+
+        store
+            .record_feedback(&mem, "standup", Feedback::Irrelevant, 0.8, Utc::now())
+            .unwrap();
+        let mem = store.get_memory(id).unwrap().unwrap();
+        assert_eq!(mem.access_count, 0);
+        assert_eq!(mem.strength, 0.8);
+    }
+
+    #[test]
+    fn recall_session_tracks_seen_and_expires_when_idle() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        let now = Utc::now();
+        store
+            .record_recall_session("s1", "default", &[3, 5], &["tag:ops".to_string()], now)
+            .unwrap();
+
+        let state = store.recall_session("s1", "default", now).unwrap();
+        assert_eq!(state.seen, [3, 5].into_iter().collect());
+        assert!((state.activation["tag:ops"] - 1.0).abs() < 1e-9);
+        // Sessions are per namespace.
+        assert!(store
+            .recall_session("s1", "other", now)
+            .unwrap()
+            .seen
+            .is_empty());
+
+        let idle = now + Duration::minutes(crate::session::SESSION_IDLE_TIMEOUT_MINUTES + 1);
+        assert!(store
+            .recall_session("s1", "default", idle)
+            .unwrap()
+            .seen
+            .is_empty());
+        // The next write drops the idle session's rows.
+        store
+            .record_recall_session("s2", "default", &[7], &[], idle)
+            .unwrap();
+        let rows: i64 = store
+            .conn()
+            .query_row(
+                "SELECT COUNT(*) FROM recall_session_seen WHERE session_id = 's1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(rows, 0);
+        assert!(store.end_recall_session("s2", "default").unwrap());
+        assert!(!store.end_recall_session("s2", "default").unwrap());
+    }
 }
 
 fn row_to_memory(row: &rusqlite::Row) -> SqlResult<MemoryRecord> {
@@ -3066,5 +5144,6 @@ fn row_to_memory(row: &rusqlite::Row) -> SqlResult<MemoryRecord> {
             .unwrap_or_else(|| "default".to_string()),
         checksum: row.get::<_, Option<String>>(17)?,
         temporal,
+        language: row.get::<_, Option<String>>(19)?,
     })
 }
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::embed::EmbedError;
+use crate::expand::{tokenize, STOPWORDS};
+
+/// Builds one summary text from a cluster of related memories.
+///
+/// Consolidation uses [`ExtractiveSummarizer`] by default; plug in a local or
+/// remote language model by implementing this trait and passing it to
+/// [`ConchDB::set_summarizer`](crate::ConchDB::set_summarizer).
+pub trait Summarizer: Send + Sync {
+    /// Summarize `texts`, given oldest first.
+    fn summarize(&self, texts: &[&str]) -> Result<String, EmbedError>;
+}
+
+/// Summarizes without a model by picking source sentences.
+///
+/// Sentences are chosen greedily by how many not-yet-covered content words
+/// they add, each word weighted by the number of sources mentioning it. Shared
+/// facts are covered first, then details found in only one source, and
+/// sentences that repeat what is already covered are skipped. The chosen
+/// sentences keep their original order.
+#[derive(Debug, Clone)]
+pub struct ExtractiveSummarizer {
+    /// Maximum sentences in a summary.
+    pub max_sentences: usize,
+}
+
+impl Default for ExtractiveSummarizer {
+    fn default() -> Self {
+        Self { max_sentences: 6 }
+    }
+}
+
+impl Summarizer for ExtractiveSummarizer {
+    fn summarize(&self, texts: &[&str]) -> Result<String, EmbedError> {
+        let mut weights: HashMap<String, usize> = HashMap::new();
+        let mut sentences: Vec<(String, HashSet<String>)> = Vec::new();
+        for text in texts {
+            let mut in_text = HashSet::new();
+            for sentence in split_sentences(text) {
+                let words = content_words(&sentence);
+                in_text.extend(words.iter().cloned());
+                sentences.push((sentence, words));
+            }
+            for word in in_text {
+                *weights.entry(word).or_insert(0) += 1;
+            }
+        }
+
+        let mut covered: HashSet<String> = HashSet::new();
+        let mut chosen: Vec<usize> = Vec::new();
+        while chosen.len() < self.max_sentences {
+            let best = sentences
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !chosen.contains(i))
+                .map(|(i, (_, words))| {
+                    let gain: usize = words
+                        .iter()
+                        .filter(|w| !covered.contains(*w))
+                        .map(|w| weights[w])
+                        .sum();
+                    (i, gain)
+                })
+                .filter(|&(_, gain)| gain > 0)
+                // Highest gain; ties go to the earliest sentence.
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)));
+            let Some((i, _)) = best else {
+                break;
+            };
+            covered.extend(sentences[i].1.iter().cloned());
+            chosen.push(i);
+        }
+
+        chosen.sort_unstable();
+        Ok(chosen
+            .into_iter()
+            .map(|i| sentences[i].0.as_str())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
+/// Split text into trimmed sentences at `.`, `!` or `?` followed by
+/// whitespace, and at line breaks.
+pub(crate) fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            push_sentence(&mut sentences, &mut current);
+            continue;
+        }
+        current.push(c);
+        let at_boundary = chars.peek().is_none_or(|next| next.is_whitespace());
+        if matches!(c, '.' | '!' | '?') && at_boundary {
+            push_sentence(&mut sentences, &mut current);
+        }
+    }
+    push_sentence(&mut sentences, &mut current);
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let sentence = current.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_string());
+    }
+    current.clear();
+}
+
+fn content_words(sentence: &str) -> HashSet<String> {
+    tokenize(sentence)
+        .into_iter()
+        .filter(|t| t.chars().count() > 1 && !STOPWORDS.contains(&t.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_sentence_punctuation_and_lines() {
+        assert_eq!(
+            split_sentences("Deployed v2.1 today. Rollback worked!\nNext: monitor"),
+            vec!["Deployed v2.1 today.", "Rollback worked!", "Next: monitor"]
+        );
+    }
+
+    #[test]
+    fn keeps_details_unique_to_weaker_sources_and_drops_repeats() {
+        let summary = ExtractiveSummarizer::default()
+            .summarize(&[
+                "Deployed billing to production.",
+                "Deployed billing to production. The migration took 40 minutes.",
+                "Billing deployed to production. Alice approved the release.",
+            ])
+            .unwrap();
+        assert_eq!(
+            summary,
+            "Deployed billing to production. The migration took 40 minutes. \
+             Alice approved the release."
+        );
+    }
+
+    #[test]
+    fn respects_max_sentences() {
+        let summarizer = ExtractiveSummarizer { max_sentences: 1 };
+        let summary = summarizer
+            .summarize(&["Alpha beta gamma. Delta epsilon."])
+            .unwrap();
+        assert_eq!(summary, "Alpha beta gamma.");
+    }
+}
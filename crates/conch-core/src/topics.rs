@@ -0,0 +1,279 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+
+use crate::embed::cosine_similarity;
+use crate::expand::{tokenize, STOPWORDS};
+use crate::lsh::next_u64;
+use crate::memory::Topic;
+use crate::store::MemoryStore;
+
+/// Upper bound on the number of topics picked automatically.
+pub const MAX_TOPICS: usize = 32;
+
+/// Keywords kept per topic; the first few form its label.
+const KEYWORDS_PER_TOPIC: usize = 8;
+const LABEL_KEYWORDS: usize = 3;
+
+const KMEANS_MAX_ITERATIONS: usize = 25;
+
+/// Fixed seed so rebuilding unchanged memories yields the same topics.
+const SEED: u64 = 0x746f_7069_6373_0001;
+
+/// BM25 parameters for keyword weights.
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// Topics picked automatically for `n` memories: about `sqrt(n / 2)`.
+pub fn default_topic_count(n: usize) -> usize {
+    ((n as f64 / 2.0).sqrt().round() as usize).clamp(1, MAX_TOPICS)
+}
+
+/// Cluster the embedded, unarchived memories of `namespace` into `k` topics
+/// (default [`default_topic_count`]) with spherical k-means, label each topic
+/// with its highest-weighted BM25 keywords and store the result, replacing
+/// the namespace's previous topics. Returns the new topics, largest first.
+pub fn build_topics(
+    store: &MemoryStore,
+    namespace: &str,
+    k: Option<usize>,
+) -> Result<Vec<Topic>, rusqlite::Error> {
+    let memories: Vec<_> = store
+        .active_memories_ns(namespace)?
+        .into_iter()
+        .filter(|mem| mem.embedding.is_some())
+        .collect();
+    let k = k
+        .unwrap_or_else(|| default_topic_count(memories.len()))
+        .min(memories.len());
+
+    let mut drafts = Vec::new();
+    if k > 0 {
+        let points: Vec<&[f32]> = memories
+            .iter()
+            .filter_map(|mem| mem.embedding.as_deref())
+            .collect();
+        let assignment = kmeans(&points, k);
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); k];
+        for (i, &cluster) in assignment.iter().enumerate() {
+            members[cluster].push(i);
+        }
+        members.retain(|g| !g.is_empty());
+        let documents: Vec<Vec<String>> = members
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .flat_map(|&i| content_terms(&memories[i].text_for_embedding()))
+                    .collect()
+            })
+            .collect();
+        for (group, keywords) in members.iter().zip(topic_keywords(&documents)) {
+            let label = keywords
+                .iter()
+                .take(LABEL_KEYWORDS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            let ids = group.iter().map(|&i| memories[i].id).collect();
+            drafts.push((label, keywords, ids));
+        }
+    }
+
+    store.replace_topics(namespace, &drafts, Utc::now())?;
+    store.topics_ns(namespace)
+}
+
+/// Spherical k-means: points and centroids are compared by cosine similarity.
+/// Seeded with k-means++ so well-separated themes start in different clusters.
+/// Returns the cluster of each point.
+fn kmeans(points: &[&[f32]], k: usize) -> Vec<usize> {
+    let mut state = SEED ^ points.len() as u64;
+    let mut uniform = move || (next_u64(&mut state) >> 11) as f64 / (1u64 << 53) as f64;
+
+    let mut centroids: Vec<Vec<f32>> =
+        vec![points[(uniform() * points.len() as f64) as usize].to_vec()];
+    while centroids.len() < k {
+        // Pick the next seed with probability proportional to its squared
+        // distance (1 - cosine) from the nearest existing centroid.
+        let weights: Vec<f64> = points
+            .iter()
+            .map(|p| {
+                let nearest = centroids
+                    .iter()
+                    .map(|c| cosine_similarity(p, c))
+                    .fold(f32::MIN, f32::max);
+                (1.0 - nearest as f64).max(0.0).powi(2)
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+        let mut target = uniform() * total;
+        let mut pick = points.len() - 1;
+        for (i, w) in weights.iter().enumerate() {
+            if target < *w {
+                pick = i;
+                break;
+            }
+            target -= w;
+        }
+        centroids.push(points[pick].to_vec());
+    }
+
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let mut changed = false;
+        for (i, p) in points.iter().enumerate() {
+            let best = centroids
+                .iter()
+                .enumerate()
+                .map(|(c, centroid)| (c, cosine_similarity(p, centroid)))
+                .fold(
+                    (0, f32::MIN),
+                    |best, cur| if cur.1 > best.1 { cur } else { best },
+                )
+                .0;
+            if assignment[i] != best {
+                assignment[i] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0f32; centroid.len()];
+            let mut count = 0;
+            for (p, _) in points.iter().zip(&assignment).filter(|(_, &a)| a == c) {
+                for (s, x) in sum.iter_mut().zip(normalized(p)) {
+                    *s += x;
+                }
+                count += 1;
+            }
+            // An emptied cluster keeps its centroid.
+            if count > 0 {
+                *centroid = sum;
+            }
+        }
+    }
+    assignment
+}
+
+fn normalized(v: &[f32]) -> impl Iterator<Item = f32> + '_ {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let scale = if norm > 0.0 { 1.0 / norm } else { 0.0 };
+    v.iter().map(move |x| x * scale)
+}
+
+/// Keywords of each topic, best first. Each topic's members are read as one
+/// document and its terms weighted by BM25 against the other topics: terms
+/// frequent in the topic but rare in the others rank highest.
+fn topic_keywords(documents: &[Vec<String>]) -> Vec<Vec<String>> {
+    let n = documents.len() as f64;
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for doc in documents {
+        for term in doc.iter().map(String::as_str).collect::<HashSet<_>>() {
+            *df.entry(term).or_insert(0) += 1;
+        }
+    }
+    let avg_len = (documents.iter().map(Vec::len).sum::<usize>() as f64 / n).max(1.0);
+
+    documents
+        .iter()
+        .map(|doc| {
+            let mut tf: HashMap<&str, usize> = HashMap::new();
+            for term in doc {
+                *tf.entry(term.as_str()).or_insert(0) += 1;
+            }
+            let len_norm = 1.0 - BM25_B + BM25_B * doc.len() as f64 / avg_len;
+            let mut weighted: Vec<(&str, f64)> = tf
+                .into_iter()
+                .map(|(term, tf)| {
+                    let df = df[term] as f64;
+                    let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    let tf = tf as f64;
+                    (term, idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * len_norm))
+                })
+                .collect();
+            weighted.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.0.cmp(b.0))
+            });
+            weighted
+                .into_iter()
+                .take(KEYWORDS_PER_TOPIC)
+                .map(|(term, _)| term.to_string())
+                .collect()
+        })
+        .collect()
+}
+
+fn content_terms(text: &str) -> Vec<String> {
+    tokenize(text)
+        .into_iter()
+        .filter(|t| t.chars().count() > 1 && !STOPWORDS.contains(&t.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed(store: &MemoryStore) {
+        for (text, emb) in [
+            ("Postgres replica lag alert", [1.0, 0.0, 0.0]),
+            ("Postgres vacuum tuning", [0.95, 0.1, 0.0]),
+            ("Postgres backup restored", [0.9, 0.0, 0.1]),
+            ("Lunch order pizza friday", [0.0, 1.0, 0.0]),
+            ("Team lunch pizza place", [0.1, 0.95, 0.0]),
+        ] {
+            store.remember_episode(text, Some(&emb)).unwrap();
+        }
+    }
+
+    #[test]
+    fn default_topic_count_grows_slowly_and_is_capped() {
+        assert_eq!(default_topic_count(1), 1);
+        assert_eq!(default_topic_count(50), 5);
+        assert_eq!(default_topic_count(1_000_000), MAX_TOPICS);
+    }
+
+    #[test]
+    fn build_topics_groups_by_theme_and_labels_with_keywords() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        seed(&store);
+
+        let topics = build_topics(&store, "default", Some(2)).unwrap();
+        assert_eq!(topics.len(), 2);
+        assert_eq!(topics[0].size, 3);
+        assert_eq!(topics[0].keywords[0], "postgres");
+        assert!(topics[0].label.starts_with("postgres"));
+        assert_eq!(topics[1].size, 2);
+        assert!(topics[1].label.contains("pizza"));
+        assert!(topics[1].label.contains("lunch"));
+        assert!((topics[1].strength - 1.0).abs() < 1e-9);
+
+        let members: Vec<i64> = store
+            .topic_memories(topics[1].id)
+            .unwrap()
+            .iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(members, vec![4, 5]);
+    }
+
+    #[test]
+    fn rebuilding_replaces_previous_topics() {
+        let store = MemoryStore::open_in_memory().unwrap();
+        seed(&store);
+        let first = build_topics(&store, "default", Some(2)).unwrap();
+        let second = build_topics(&store, "default", Some(1)).unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].size, 5);
+        assert!(store.topic_memories(first[0].id).unwrap().is_empty());
+        assert!(build_topics(&store, "empty", None).unwrap().is_empty());
+    }
+}
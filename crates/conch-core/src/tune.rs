@@ -0,0 +1,217 @@
+use serde::Serialize;
+
+use crate::eval::EvalReport;
+use crate::recall::{FusionStrategy, RecallScoreCoefficients};
+
+/// Candidate values for the score exponents.
+const EXPONENT_GRID: &[f64] = &[0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
+/// Candidate values for the RRF keyword and vector weights.
+const RRF_WEIGHT_GRID: &[f64] = &[0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
+/// Candidate values for the convex keyword weight.
+const CONVEX_WEIGHT_GRID: &[f64] = &[0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+/// Full passes over the parameters before giving up on further gains.
+const MAX_ROUNDS: usize = 3;
+/// Number of parameter slots (see [`slot`]).
+const SLOTS: usize = 6;
+
+/// Metric a tuning run maximises.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TuneMetric {
+    Mrr,
+    #[default]
+    Ndcg,
+    Recall,
+}
+
+impl TuneMetric {
+    pub fn of(self, report: &EvalReport) -> f64 {
+        match self {
+            TuneMetric::Mrr => report.mrr,
+            TuneMetric::Ndcg => report.ndcg,
+            TuneMetric::Recall => report.recall,
+        }
+    }
+}
+
+impl std::fmt::Display for TuneMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TuneMetric::Mrr => write!(f, "mrr"),
+            TuneMetric::Ndcg => write!(f, "ndcg"),
+            TuneMetric::Recall => write!(f, "recall"),
+        }
+    }
+}
+
+impl std::str::FromStr for TuneMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mrr" => Ok(TuneMetric::Mrr),
+            "ndcg" => Ok(TuneMetric::Ndcg),
+            "recall" => Ok(TuneMetric::Recall),
+            other => Err(format!(
+                "unknown metric \"{other}\" (expected mrr, ndcg or recall)"
+            )),
+        }
+    }
+}
+
+/// The tunable recall parameters: score exponents and fusion stage weights.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TuneParams {
+    pub coefficients: RecallScoreCoefficients,
+    pub fusion: FusionStrategy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TuneResult {
+    pub metric: TuneMetric,
+    /// Metric with the starting parameters.
+    pub baseline: f64,
+    /// Metric with the tuned parameters.
+    pub tuned: f64,
+    pub params: TuneParams,
+    /// Number of suite evaluations run.
+    pub evaluations: usize,
+    /// Full report for the tuned parameters.
+    pub report: EvalReport,
+}
+
+impl TuneResult {
+    pub fn gain(&self) -> f64 {
+        self.tuned - self.baseline
+    }
+}
+
+/// Coordinate search: sweep each parameter over its grid while holding the
+/// others fixed, keep any strict improvement, and repeat until a full pass
+/// gains nothing. Ties keep the current value, so the result only moves
+/// away from `start` when the suite shows it helps.
+pub fn coordinate_search<E>(
+    start: TuneParams,
+    metric: TuneMetric,
+    mut evaluate: impl FnMut(&TuneParams) -> Result<EvalReport, E>,
+) -> Result<TuneResult, E> {
+    let mut best = start;
+    let mut best_report = evaluate(&best)?;
+    let baseline = metric.of(&best_report);
+    let mut evaluations = 1;
+
+    for _ in 0..MAX_ROUNDS {
+        let mut improved = false;
+        for i in 0..SLOTS {
+            let Some((current, grid)) = slot(&mut best, i).map(|(v, g)| (*v, g)) else {
+                continue;
+            };
+            for &value in grid {
+                if (value - current).abs() < 1e-12 {
+                    continue;
+                }
+                let mut candidate = best;
+                if let Some((v, _)) = slot(&mut candidate, i) {
+                    *v = value;
+                }
+                let report = evaluate(&candidate)?;
+                evaluations += 1;
+                if metric.of(&report) > metric.of(&best_report) + 1e-9 {
+                    best = candidate;
+                    best_report = report;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    Ok(TuneResult {
+        metric,
+        baseline,
+        tuned: metric.of(&best_report),
+        params: best,
+        evaluations,
+        report: best_report,
+    })
+}
+
+/// The `i`-th tunable parameter and its candidate grid. Fusion weights only
+/// exist for the weighted strategies.
+fn slot(params: &mut TuneParams, i: usize) -> Option<(&mut f64, &'static [f64])> {
+    let c = &mut params.coefficients;
+    match (i, &mut params.fusion) {
+        (0, _) => Some((&mut c.rrf_exp, EXPONENT_GRID)),
+        (1, _) => Some((&mut c.decay_exp, EXPONENT_GRID)),
+        (2, _) => Some((&mut c.recency_exp, EXPONENT_GRID)),
+        (3, _) => Some((&mut c.access_exp, EXPONENT_GRID)),
+        (4, FusionStrategy::Rrf { keyword_weight, .. }) => Some((keyword_weight, RRF_WEIGHT_GRID)),
+        (5, FusionStrategy::Rrf { vector_weight, .. }) => Some((vector_weight, RRF_WEIGHT_GRID)),
+        (4, FusionStrategy::Convex { keyword_weight }) => {
+            Some((keyword_weight, CONVEX_WEIGHT_GRID))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(score: f64) -> EvalReport {
+        EvalReport {
+            suite: None,
+            k: 10,
+            mrr: score,
+            ndcg: score,
+            recall: score,
+            cases: Vec::new(),
+        }
+    }
+
+    fn start() -> TuneParams {
+        TuneParams {
+            coefficients: RecallScoreCoefficients::default(),
+            fusion: FusionStrategy::default(),
+        }
+    }
+
+    #[test]
+    fn finds_the_best_grid_point_per_parameter() {
+        // Peaks at rrf_exp = 2.0 and keyword_weight = 3.0, independent of the rest.
+        let result = coordinate_search(start(), TuneMetric::Ndcg, |p| {
+            let keyword = match p.fusion {
+                FusionStrategy::Rrf { keyword_weight, .. } => keyword_weight,
+                _ => 0.0,
+            };
+            Ok::<_, ()>(report(
+                -(p.coefficients.rrf_exp - 2.0).powi(2) - (keyword - 3.0).powi(2),
+            ))
+        })
+        .unwrap();
+
+        assert_eq!(result.params.coefficients.rrf_exp, 2.0);
+        assert_eq!(result.params.coefficients.decay_exp, 1.0);
+        match result.params.fusion {
+            FusionStrategy::Rrf { keyword_weight, .. } => assert_eq!(keyword_weight, 3.0),
+            other => panic!("fusion changed kind: {other:?}"),
+        }
+        assert!((result.gain() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_objective_keeps_the_start() {
+        let result =
+            coordinate_search(start(), TuneMetric::Mrr, |_| Ok::<_, ()>(report(0.5))).unwrap();
+        assert_eq!(result.params, start());
+        assert_eq!(result.gain(), 0.0);
+    }
+
+    #[test]
+    fn metric_parses_by_name() {
+        assert_eq!("nDCG".parse::<TuneMetric>().unwrap(), TuneMetric::Ndcg);
+        assert!("f1".parse::<TuneMetric>().is_err());
+    }
+}
@@ -1,4 +1,7 @@
-use conch_core::{ConchDB, MemoryKind, RecallResult};
+use conch_core::{
+    ConchDB, ConsolidateMode, ConsolidateOptions, ContextFormat, Feedback, FusionStrategy,
+    MemoryKind, RecallContext, RecallOptions, RecallResult, DEFAULT_DIVERSITY_LAMBDA,
+};
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
     model::*,
@@ -67,6 +70,51 @@ struct RecallParams {
     limit: Option<usize>,
     /// Optional tag to filter results by
     tag: Option<String>,
+    /// Rerank top candidates with a local cross-encoder (default: namespace policy, usually off)
+    rerank: Option<bool>,
+    /// Expand the query through entities in the fact graph (default: true)
+    expand: Option<bool>,
+    /// Graph hops spreading activation may follow to add related memories (default: 2, 0 disables)
+    hops: Option<usize>,
+    /// Diversify results so near-duplicates don't crowd out distinct memories (default: false)
+    diverse: Option<bool>,
+    /// MMR lambda when diversifying: 1.0 = pure relevance, 0.0 = pure novelty (default: 0.7)
+    diversity_lambda: Option<f64>,
+    /// Fusion strategy: "rrf[:k=60,keyword=1,vector=1]", "convex[:keyword=0.5]", "vector" or "keyword" (default: namespace policy, usually rrf)
+    fusion: Option<String>,
+    /// Conversation or agent session id: memories already returned in this session are skipped and its recent entities and tags prime the ranking (expires after 30 minutes idle)
+    session_id: Option<String>,
+    /// Channel the question comes from; memories learned there are favoured
+    channel: Option<String>,
+    /// Source of the question (e.g. "discord", "cron"); memories from it are favoured
+    source: Option<String>,
+    /// Working directory; memories about its project are favoured
+    cwd: Option<String>,
+    /// Only recall memories in this topic (ids come from the `topics` tool)
+    topic: Option<i64>,
+    /// Namespace for memory isolation (default: "default")
+    namespace: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ContextParams {
+    query: String,
+    /// Approximate token budget for the block (default: 1000)
+    budget_tokens: Option<usize>,
+    /// "markdown" or "xml" (default: "markdown")
+    format: Option<String>,
+    /// Namespace for memory isolation (default: "default")
+    namespace: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct FeedbackParams {
+    /// Memory ID from the recall results
+    id: i64,
+    /// The query that returned the memory
+    query: String,
+    /// "useful" (reinforce), "irrelevant" (undo the recall boost) or "wrong" (demote and flag for review)
+    feedback: String,
     /// Namespace for memory isolation (default: "default")
     namespace: Option<String>,
 }
@@ -119,6 +167,32 @@ struct WhyParams {
 struct ConsolidateParams {
     /// If true, only preview what would be consolidated without making changes
     dry_run: Option<bool>,
+    /// Cosine similarity above which memories are clustered, in (0, 1] (default: 0.80)
+    threshold: Option<f32>,
+    /// Replace clusters of episodes or actions with an extractive summary memory linked to the originals, which are archived instead of deleted (default: false)
+    summarize: Option<bool>,
+    /// Only look for clusters involving memories added since the last consolidation (default: false)
+    incremental: Option<bool>,
+    /// Namespace for memory isolation (default: "default")
+    namespace: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct TopicsParams {
+    /// Recompute topics from the current embeddings (default: false; topics are built on first use)
+    rebuild: Option<bool>,
+    /// Number of topics when rebuilding, at least 1 (default: about sqrt(n/2), at most 32)
+    k: Option<usize>,
+    /// Namespace for memory isolation (default: "default")
+    namespace: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ExtractParams {
+    /// Only report the candidate facts without storing them (default: false)
+    dry_run: Option<bool>,
+    /// Namespace for memory isolation (default: "default")
+    namespace: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -336,7 +410,33 @@ impl ConchServer {
             Ok(c) => c,
             Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
         };
-        match conch.recall_with_tag(&p.query, p.limit.unwrap_or(5), p.tag.as_deref()) {
+        let fusion = match p.fusion.as_deref().map(str::parse::<FusionStrategy>) {
+            Some(Err(e)) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            Some(Ok(fusion)) => Some(fusion),
+            None => None,
+        };
+        let context = RecallContext {
+            channel: p.channel,
+            source: p.source,
+            session_id: p.session_id.clone(),
+            cwd: p.cwd,
+        };
+        let options = RecallOptions {
+            tag: p.tag,
+            rerank: p.rerank,
+            disable_expansion: !p.expand.unwrap_or(true),
+            spread_hops: p.hops,
+            diversity: p
+                .diverse
+                .unwrap_or(false)
+                .then(|| p.diversity_lambda.unwrap_or(DEFAULT_DIVERSITY_LAMBDA)),
+            fusion,
+            context: (!context.is_empty()).then_some(context),
+            session_id: p.session_id,
+            topic: p.topic,
+            ..Default::default()
+        };
+        match conch.recall_with_options(&p.query, p.limit.unwrap_or(5), &options) {
             Ok(results) => {
                 let responses: Vec<MemoryResponse> =
                     results.into_iter().map(MemoryResponse::from).collect();
@@ -401,7 +501,7 @@ impl ConchServer {
 
     #[tool(
         name = "related",
-        description = "Graph traversal: find facts connected to a subject entity via 1-hop and multi-hop relationships. Returns a graph of related memories with hop distance."
+        description = "Graph traversal: find facts and other memories connected to a subject entity, through fact subjects/objects and entity mentions, via 1-hop and multi-hop relationships. Returns a graph of related memories with hop distance."
     )]
     async fn related(&self, params: Parameters<RelatedParams>) -> Result<CallToolResult, McpError> {
         let p = params.0;
@@ -420,7 +520,7 @@ impl ConchServer {
 
     #[tool(
         name = "why",
-        description = "Provenance: show full audit context for a memory — when created, by whom, access count, strength, source, session, channel, and 1-hop related facts."
+        description = "Provenance: show full audit context for a memory — when created, by whom, access count, strength, source, session, channel, and 1-hop related memories."
     )]
     async fn why(&self, params: Parameters<WhyParams>) -> Result<CallToolResult, McpError> {
         let p = params.0;
@@ -439,6 +539,56 @@ impl ConchServer {
         }
     }
 
+    #[tool(
+        name = "context",
+        description = "Recall memories for a query and return them as a compact prompt-ready block (facts, pending intents, recent actions, episodes) that fits a token budget. Each memory is cited by id as [#id]."
+    )]
+    async fn context(&self, params: Parameters<ContextParams>) -> Result<CallToolResult, McpError> {
+        let p = params.0;
+        let format = match p
+            .format
+            .as_deref()
+            .map(str::parse::<ContextFormat>)
+            .transpose()
+        {
+            Ok(f) => f.unwrap_or_default(),
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let conch = match self.open_db(p.namespace.as_deref()) {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match conch.context(&p.query, p.budget_tokens.unwrap_or(1000), format) {
+            Ok(block) => Ok(CallToolResult::success(vec![Content::text(block.text)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "feedback",
+        description = "Report whether a recalled memory helped. useful reinforces it, irrelevant undoes the boost recall gave it, wrong demotes it and tags it needs_review. Feedback is logged for tuning."
+    )]
+    async fn feedback(
+        &self,
+        params: Parameters<FeedbackParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let p = params.0;
+        let feedback: Feedback = match p.feedback.parse() {
+            Ok(f) => f,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let conch = match self.open_db(p.namespace.as_deref()) {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match conch.feedback(p.id, &p.query, feedback) {
+            Ok(entry) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&entry).unwrap(),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
     #[tool(
         name = "stats",
         description = "Get memory statistics. Supports namespace isolation."
@@ -459,7 +609,7 @@ impl ConchServer {
 
     #[tool(
         name = "consolidate",
-        description = "Consolidate related memories (sleep-like memory consolidation). Finds clusters of similar memories, boosts the strongest, and archives duplicates."
+        description = "Consolidate related memories (sleep-like memory consolidation). Finds clusters of similar memories of the same kind, boosts the strongest, and archives duplicates. Never merges conflicting facts. Supports namespace isolation."
     )]
     async fn consolidate(
         &self,
@@ -467,19 +617,33 @@ impl ConchServer {
     ) -> Result<CallToolResult, McpError> {
         let p = params.0;
         let dry_run = p.dry_run.unwrap_or(false);
-        let conch = match self.open_db(None) {
+        let options = ConsolidateOptions {
+            threshold: p.threshold,
+            mode: if p.summarize.unwrap_or(false) {
+                ConsolidateMode::Summarize
+            } else {
+                ConsolidateMode::Merge
+            },
+            incremental: p.incremental.unwrap_or(false),
+        };
+        if p.threshold.is_some_and(|t| t <= 0.0 || t > 1.0) {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "'threshold' must be in (0, 1]".to_string(),
+            )]));
+        }
+        let conch = match self.open_db(p.namespace.as_deref()) {
             Ok(c) => c,
             Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
         };
         if dry_run {
-            match conch.consolidate_clusters() {
+            match conch.consolidate_clusters(&options, |_| {}) {
                 Ok(clusters) => Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&clusters).unwrap(),
                 )])),
                 Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
             }
         } else {
-            match conch.consolidate(false) {
+            match conch.consolidate(false, &options, |_| {}) {
                 Ok(result) => Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&result).unwrap(),
                 )])),
@@ -488,6 +652,47 @@ impl ConchServer {
         }
     }
 
+    #[tool(
+        name = "topics",
+        description = "List the topics this agent's memories group into: a keyword label, member count and average strength per topic. Pass a topic id to recall to search within it."
+    )]
+    async fn topics(&self, params: Parameters<TopicsParams>) -> Result<CallToolResult, McpError> {
+        let p = params.0;
+        let conch = match self.open_db(p.namespace.as_deref()) {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        let topics = match conch.topics() {
+            Ok(topics) if !p.rebuild.unwrap_or(false) && !topics.is_empty() => Ok(topics),
+            Ok(_) => conch.build_topics(p.k),
+            Err(e) => Err(e),
+        };
+        match topics {
+            Ok(topics) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&topics).unwrap(),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "extract",
+        description = "Extract subject-relation-object facts from episodes and actions with pattern rules (e.g. \"X works at Y\"). New facts are stored with lower strength and linked to the memory they came from; facts already known are only linked."
+    )]
+    async fn extract(&self, params: Parameters<ExtractParams>) -> Result<CallToolResult, McpError> {
+        let p = params.0;
+        let conch = match self.open_db(p.namespace.as_deref()) {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match conch.extract_facts(p.dry_run.unwrap_or(false)) {
+            Ok(report) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&report).unwrap(),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
     #[tool(
         name = "importance",
         description = "Show or recompute importance scores for all memories. Importance affects decay rate — high-importance memories decay slower."